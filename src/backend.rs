@@ -0,0 +1,313 @@
+use crate::config::ExclusionType;
+use crate::error::BackendErrorKind;
+use crate::explorer::{self, ExclusionMechanism};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Environment variable naming a file where exclusions are recorded instead of applied
+pub const BACKEND_FILE_ENV: &str = "ASIMEOW_BACKEND_FILE";
+
+/// A backup system whose exclusions are managed by the explorer
+pub trait BackupExclusionBackend: Send + Sync {
+    /// Returns true if the path is excluded from backups
+    fn is_excluded(&self, path: &Path) -> bool;
+
+    /// Adds an exclusion for the path, returning true on success
+    fn add(&self, path: &Path, exclusion_type: ExclusionType) -> bool;
+
+    /// Removes the exclusion of the path, returning true on success
+    fn remove(&self, path: &Path, exclusion_type: ExclusionType) -> bool;
+
+    /// Adds exclusions for several paths, returning true if every one succeeded.
+    /// Backends with a per-call cost override it to batch the changes.
+    fn add_all(&self, paths: &[PathBuf], exclusion_type: ExclusionType) -> bool {
+        // Every path is attempted, even after a failure
+        let failed = paths
+            .iter()
+            .filter(|path| !self.add(path, exclusion_type))
+            .count();
+        failed == 0
+    }
+
+    /// Removes the exclusions of several paths, returning true if every one succeeded
+    fn remove_all(&self, paths: &[PathBuf], exclusion_type: ExclusionType) -> bool {
+        // Every path is attempted, even after a failure
+        let failed = paths
+            .iter()
+            .filter(|path| !self.remove(path, exclusion_type))
+            .count();
+        failed == 0
+    }
+
+    /// Returns how the path is excluded, walking its ancestors, or None if it is included.
+    /// Backends without exclusion types report their own exclusions as sticky
+    fn exclusion_mechanism(&self, path: &Path) -> Option<ExclusionMechanism> {
+        if self.is_excluded(path) {
+            return Some(ExclusionMechanism::Sticky);
+        }
+        path.ancestors()
+            .skip(1)
+            .find(|ancestor| self.is_excluded(ancestor))
+            .map(|ancestor| ExclusionMechanism::Inherited(ancestor.to_path_buf()))
+    }
+
+    /// Fails if the backup system cannot be reached, e.g. its tool is not installed
+    fn check_available(&self) -> crate::error::Result<()> {
+        Ok(())
+    }
+
+    /// Returns true if exclusions of this type cannot be changed without root privileges
+    fn requires_elevation(&self, _exclusion_type: ExclusionType) -> bool {
+        false
+    }
+
+    /// Returns true if changes are only reported, as in a dry run, because there is no
+    /// backup system to apply them to
+    fn reports_only(&self) -> bool {
+        false
+    }
+
+    /// Excludes the path unless it is already excluded.
+    /// Returns true if the path was newly excluded.
+    fn exclude(&self, path: &Path, exclusion_type: ExclusionType) -> bool {
+        !self.is_excluded(path) && self.add(path, exclusion_type)
+    }
+
+    /// Includes the path unless it is already included.
+    /// Returns true if the exclusion was removed.
+    fn include(&self, path: &Path, exclusion_type: ExclusionType) -> bool {
+        self.is_excluded(path) && self.remove(path, exclusion_type)
+    }
+}
+
+/// Returns the backend used when none is injected: a `FileBackend` when `ASIMEOW_BACKEND_FILE`
+/// is set, `tmutil` on macOS, and a `NoopBackend` on other systems (e.g. Linux CI)
+pub fn default_backend() -> Arc<dyn BackupExclusionBackend> {
+    backend_with_privilege_helper(None)
+}
+
+/// Same as `default_backend`, running `tmutil` through the privilege helper (e.g. `sudo -n`)
+/// for fixed-path exclusions when not running as root
+pub fn backend_with_privilege_helper(
+    privilege_helper: Option<String>,
+) -> Arc<dyn BackupExclusionBackend> {
+    if let Some(path) = std::env::var_os(BACKEND_FILE_ENV).filter(|path| !path.is_empty()) {
+        return Arc::new(FileBackend::new(path));
+    }
+    if cfg!(target_os = "macos") {
+        Arc::new(TmutilBackend { privilege_helper })
+    } else {
+        Arc::new(NoopBackend)
+    }
+}
+
+/// Time Machine exclusions managed through `tmutil`
+#[derive(Debug, Default, Clone)]
+pub struct TmutilBackend {
+    /// Command prefix running `tmutil` for fixed-path exclusions when not running as root
+    pub privilege_helper: Option<String>,
+}
+
+impl BackupExclusionBackend for TmutilBackend {
+    fn is_excluded(&self, path: &Path) -> bool {
+        explorer::is_excluded_from_timemachine(path)
+    }
+
+    /// Tells sticky from fixed-path exclusions, with the attribute and the preferences
+    fn exclusion_mechanism(&self, path: &Path) -> Option<ExclusionMechanism> {
+        explorer::exclusion_mechanism(path)
+    }
+
+    fn add(&self, path: &Path, exclusion_type: ExclusionType) -> bool {
+        self.add_all(&[path.to_path_buf()], exclusion_type)
+    }
+
+    fn remove(&self, path: &Path, exclusion_type: ExclusionType) -> bool {
+        self.remove_all(&[path.to_path_buf()], exclusion_type)
+    }
+
+    /// `tmutil addexclusion` takes several paths, so a batch costs a single process
+    fn add_all(&self, paths: &[PathBuf], exclusion_type: ExclusionType) -> bool {
+        explorer::run_tmutil(
+            "addexclusion",
+            paths,
+            exclusion_type,
+            self.privilege_helper.as_deref(),
+        )
+    }
+
+    fn remove_all(&self, paths: &[PathBuf], exclusion_type: ExclusionType) -> bool {
+        explorer::run_tmutil(
+            "removeexclusion",
+            paths,
+            exclusion_type,
+            self.privilege_helper.as_deref(),
+        )
+    }
+
+    fn check_available(&self) -> crate::error::Result<()> {
+        let mut command = explorer::tmutil_command();
+        match command.arg("version").output() {
+            Ok(_) => Ok(()),
+            Err(e) => Err(crate::error::Error::Backend {
+                path: PathBuf::from(command.get_program()),
+                kind: BackendErrorKind::Unavailable,
+                message: format!(
+                    "Cannot run {}: {} (Time Machine is not available on this system)",
+                    command.get_program().to_string_lossy(),
+                    e
+                ),
+            }),
+        }
+    }
+
+    /// Fixed-path exclusions are stored in the system preferences, writable by root only
+    fn requires_elevation(&self, exclusion_type: ExclusionType) -> bool {
+        exclusion_type == ExclusionType::Fixed
+            && self.privilege_helper.is_none()
+            && !explorer::is_elevated()
+    }
+}
+
+/// In-memory backend recording exclusions, so tests can assert which paths were excluded
+#[derive(Debug, Default)]
+pub struct MockBackend {
+    excluded: RwLock<BTreeMap<PathBuf, ExclusionType>>,
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a backend where the given paths are already excluded (sticky)
+    pub fn with_excluded<I: IntoIterator<Item = PathBuf>>(paths: I) -> Self {
+        MockBackend {
+            excluded: RwLock::new(
+                paths
+                    .into_iter()
+                    .map(|path| (path, ExclusionType::Sticky))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the excluded paths, sorted
+    pub fn excluded_paths(&self) -> Vec<PathBuf> {
+        self.excluded.read().unwrap().keys().cloned().collect()
+    }
+
+    /// Returns the exclusion type of an excluded path
+    pub fn exclusion_type(&self, path: &Path) -> Option<ExclusionType> {
+        self.excluded.read().unwrap().get(path).copied()
+    }
+}
+
+impl BackupExclusionBackend for MockBackend {
+    /// Only the exact path is checked, like `tmutil isexcluded` for sticky exclusions
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.excluded.read().unwrap().contains_key(path)
+    }
+
+    fn add(&self, path: &Path, exclusion_type: ExclusionType) -> bool {
+        self.excluded
+            .write()
+            .unwrap()
+            .insert(path.to_path_buf(), exclusion_type);
+        true
+    }
+
+    fn remove(&self, path: &Path, _exclusion_type: ExclusionType) -> bool {
+        self.excluded.write().unwrap().remove(path).is_some()
+    }
+}
+
+/// Backend for systems without Time Machine: nothing is excluded and every change is
+/// accepted, while callers report the changes as in a dry run
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopBackend;
+
+impl BackupExclusionBackend for NoopBackend {
+    fn is_excluded(&self, _path: &Path) -> bool {
+        false
+    }
+
+    fn add(&self, _path: &Path, _exclusion_type: ExclusionType) -> bool {
+        true
+    }
+
+    fn remove(&self, _path: &Path, _exclusion_type: ExclusionType) -> bool {
+        true
+    }
+
+    fn reports_only(&self) -> bool {
+        true
+    }
+}
+
+/// Backend recording the intended exclusions in a file, one path per line, instead of
+/// calling `tmutil`, e.g. to develop the traversal engine without a Mac
+#[derive(Debug)]
+pub struct FileBackend {
+    path: PathBuf,
+    // Serializes the read-modify-write cycles of concurrent workers
+    lock: RwLock<()>,
+}
+
+impl FileBackend {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        FileBackend {
+            path: path.into(),
+            lock: RwLock::new(()),
+        }
+    }
+
+    /// Returns the recorded paths, in the order they were excluded
+    pub fn excluded_paths(&self) -> Vec<PathBuf> {
+        let _guard = self.lock.read().unwrap();
+        self.read_paths()
+    }
+
+    fn read_paths(&self) -> Vec<PathBuf> {
+        fs::read_to_string(&self.path)
+            .map(|content| {
+                content
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(PathBuf::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl BackupExclusionBackend for FileBackend {
+    fn is_excluded(&self, path: &Path) -> bool {
+        let _guard = self.lock.read().unwrap();
+        self.read_paths().iter().any(|recorded| recorded == path)
+    }
+
+    fn add(&self, path: &Path, _exclusion_type: ExclusionType) -> bool {
+        let _guard = self.lock.write().unwrap();
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", path.display()))
+            .is_ok()
+    }
+
+    fn remove(&self, path: &Path, _exclusion_type: ExclusionType) -> bool {
+        let _guard = self.lock.write().unwrap();
+        let remaining: Vec<String> = self
+            .read_paths()
+            .iter()
+            .filter(|recorded| recorded.as_path() != path)
+            .map(|recorded| format!("{}\n", recorded.display()))
+            .collect();
+        fs::write(&self.path, remaining.concat()).is_ok()
+    }
+}
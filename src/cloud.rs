@@ -0,0 +1,118 @@
+use crate::config::{Backend, Rule};
+use crate::dropbox::DropboxFolders;
+use crate::i18n::tr;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Cloud service uploading a folder. Time Machine exclusions do not stop it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudSync {
+    /// iCloud Drive, including the synced Desktop and Documents folders
+    ICloud,
+    Dropbox,
+    OneDrive,
+}
+
+impl CloudSync {
+    /// Returns the backend stopping this service from uploading a path, if there is one
+    pub fn backend(self) -> Option<Backend> {
+        match self {
+            CloudSync::ICloud => Some(Backend::ICloud),
+            CloudSync::Dropbox => Some(Backend::Dropbox),
+            CloudSync::OneDrive => None,
+        }
+    }
+}
+
+impl fmt::Display for CloudSync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CloudSync::ICloud => write!(f, "iCloud Drive"),
+            CloudSync::Dropbox => write!(f, "Dropbox"),
+            CloudSync::OneDrive => write!(f, "OneDrive"),
+        }
+    }
+}
+
+/// Returns the OneDrive folders: the macOS File Provider location, and the `~/OneDrive*`
+/// folders of older clients
+pub fn onedrive_folders(home: &Path) -> Vec<PathBuf> {
+    let mut folders = Vec::new();
+    for pattern in [
+        home.join("Library/CloudStorage/OneDrive*"),
+        home.join("OneDrive*"),
+    ] {
+        if let Ok(paths) = glob::glob(&pattern.to_string_lossy()) {
+            folders.extend(
+                paths
+                    .filter_map(|path| path.ok())
+                    .filter(|path| path.is_dir()),
+            );
+        }
+    }
+    folders.sort();
+    folders.dedup();
+    folders
+}
+
+/// Folders uploaded by a cloud service, resolved once per scan as reading them means
+/// stats, config files and globs in the home directory
+#[derive(Debug, Clone, Default)]
+pub struct SyncedFolders {
+    pub icloud: Vec<PathBuf>,
+    pub dropbox: DropboxFolders,
+    pub onedrive: Vec<PathBuf>,
+}
+
+impl SyncedFolders {
+    /// Reads the synced folders of the user with this home directory
+    pub fn of(home: &Path) -> Self {
+        SyncedFolders {
+            icloud: crate::icloud::icloud_drive_folders(home),
+            dropbox: DropboxFolders::of(home),
+            onedrive: onedrive_folders(home),
+        }
+    }
+
+    /// Returns true if the path is synchronized with iCloud Drive
+    pub fn is_under_icloud_drive(&self, path: &Path) -> bool {
+        self.icloud.iter().any(|folder| path.starts_with(folder))
+    }
+}
+
+/// Returns the cloud service uploading `path`, if any
+pub fn synced_by(path: &Path, folders: &SyncedFolders) -> Option<CloudSync> {
+    if folders.is_under_icloud_drive(path) {
+        return Some(CloudSync::ICloud);
+    }
+    if crate::dropbox::sync_root(path, &folders.dropbox).is_some() {
+        return Some(CloudSync::Dropbox);
+    }
+    folders
+        .onedrive
+        .iter()
+        .any(|folder| path.starts_with(folder))
+        .then_some(CloudSync::OneDrive)
+}
+
+/// Returns the warning for an exclusion of `rule` that a cloud service keeps uploading,
+/// pointing to the backend that stops it. None when the path is not synced, or when the
+/// rule already applies that backend
+pub fn sync_warning(path: &Path, rule: &Rule, folders: &SyncedFolders) -> Option<String> {
+    let service = synced_by(path, folders)?;
+    let mut args = vec![
+        ("path", path.display().to_string().into()),
+        ("rule", rule.name.as_str().into()),
+        ("service", service.to_string().into()),
+    ];
+    let warning = match service.backend() {
+        Some(backend) if rule.backends().contains(&backend) => return None,
+        Some(backend) => {
+            let backend = serde_yaml::to_string(&backend).unwrap_or_default();
+            args.push(("backend", backend.trim().to_string().into()));
+            tr("scan-cloud-add-backend", &args)
+        }
+        None => tr("scan-cloud-move-out", &args),
+    };
+    Some(warning)
+}
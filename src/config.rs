@@ -1,27 +1,682 @@
+use crate::error::Error;
+use crate::i18n::tr;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Config {
+    #[serde(default)]
     pub roots: Vec<Root>,
     #[serde(default)]
     pub ignore: Vec<String>,
+    /// Levels of directories scanned below each root, unless the root sets its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<usize>,
+    /// Paths that are always excluded, without scanning (`~` and `$VAR` are expanded)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_paths: Vec<String>,
+    /// Paths (or globs) that are never excluded, even if a rule matches them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keep: Vec<String>,
+    /// Paths (or globs) that are never excluded and reported if currently excluded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protected: Vec<String>,
+    /// Opt-in use of `.gitignore` entries as exclusion candidates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gitignore: Option<GitignoreConfig>,
+    /// Opt-in detection of cache-like directories not matched by any rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heuristics: Option<HeuristicsConfig>,
+    /// Opt-in detection of build outputs left behind by deleted projects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orphans: Option<OrphansConfig>,
+    /// Opt-in reporting of directories with a huge number of direct entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_limit: Option<EntryLimitConfig>,
+    /// Whether the first or all matching rules are applied to each entry
+    #[serde(default, skip_serializing_if = "is_first_match")]
+    pub match_mode: MatchMode,
+    /// Directories under which resolved symlink targets may be excluded (home when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symlink_bases: Vec<String>,
+    /// Set to `false` to skip symlinked directories below the roots (followed when omitted,
+    /// each target scanned once)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub follow_symlinks: Option<bool>,
+    /// Command prefix running `tmutil` for fixed-path exclusions when not running as root,
+    /// e.g. `sudo -n` or a custom helper
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privilege_helper: Option<String>,
+    /// Answer yes to every confirmation, like `--yes`, e.g. for scheduled runs
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub assume_yes: bool,
+    /// Path of the `tmutil` executable, when not the one on the PATH
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tmutil_path: Option<String>,
+    /// Command prefix for every `tmutil` call, e.g. `sudo -n` or a logging shim
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tmutil_wrapper: Option<String>,
     pub rules: Vec<Rule>,
+    /// Only print the exclusions a scan would make, without changing Time Machine settings
+    #[serde(skip)]
+    pub dry_run: bool,
+    /// Keep the scan output off stdout, e.g. when it carries an export
+    #[serde(skip)]
+    pub quiet: bool,
+    /// Ask before each new exclusion, see `explorer::ReviewAnswer`
+    #[serde(skip)]
+    pub interactive: bool,
+    /// Show a live dashboard of the scan instead of its per-path output
+    #[serde(skip)]
+    pub watch_progress: bool,
+    /// Format of the scan output, see `output::OutputFormat`
+    #[serde(skip)]
+    pub format: crate::output::OutputFormat,
+    /// Measure the exclusions found, as this mode says, see `ExplorerStats::bytes`
+    #[serde(skip)]
+    pub size_mode: Option<crate::size::SizeMode>,
+    /// Only apply exclusions to paths below this directory, once symlinks are resolved,
+    /// e.g. the user's home in `--all-users` scans
+    #[serde(skip)]
+    pub confine_to: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitignoreMode {
+    /// Only print the candidates
+    Propose,
+    /// Exclude the candidates from Time Machine
+    Exclude,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitignoreConfig {
+    pub mode: GitignoreMode,
+    /// Minimum size of an ignored directory to be considered, in megabytes
+    #[serde(default = "default_gitignore_min_size_mb")]
+    pub min_size_mb: u64,
+}
+
+fn default_gitignore_min_size_mb() -> u64 {
+    100
+}
+
+/// Thresholds used to report directories that look like rebuildable caches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeuristicsConfig {
+    /// Glob patterns of cache-like directory names (case-insensitive)
+    #[serde(default = "default_heuristics_names")]
+    pub names: Vec<String>,
+    /// Minimum size of a candidate directory, in megabytes
+    #[serde(default = "default_heuristics_min_size_mb")]
+    pub min_size_mb: u64,
+    /// Minimum number of files for the "many small files" signal
+    #[serde(default = "default_heuristics_min_files")]
+    pub min_files: u64,
+    /// Maximum average file size for the "many small files" signal, in kilobytes
+    #[serde(default = "default_heuristics_max_average_file_kb")]
+    pub max_average_file_kb: u64,
+    /// Directories created or modified within this many days count as regenerated
+    #[serde(default = "default_heuristics_recent_days")]
+    pub recent_days: u64,
+}
+
+impl Default for HeuristicsConfig {
+    fn default() -> Self {
+        HeuristicsConfig {
+            names: default_heuristics_names(),
+            min_size_mb: default_heuristics_min_size_mb(),
+            min_files: default_heuristics_min_files(),
+            max_average_file_kb: default_heuristics_max_average_file_kb(),
+            recent_days: default_heuristics_recent_days(),
+        }
+    }
+}
+
+fn default_heuristics_names() -> Vec<String> {
+    [
+        "*cache*",
+        "tmp",
+        "temp",
+        "build",
+        "builds",
+        "out",
+        "output",
+        "dist",
+        "obj",
+        "gen",
+        "generated",
+        "artifacts",
+    ]
+    .iter()
+    .map(|name| name.to_string())
+    .collect()
+}
+
+fn default_heuristics_min_size_mb() -> u64 {
+    500
+}
+
+fn default_heuristics_min_files() -> u64 {
+    1000
+}
+
+fn default_heuristics_max_average_file_kb() -> u64 {
+    64
+}
+
+fn default_heuristics_recent_days() -> u64 {
+    7
+}
+
+/// Build output directories reported when no rule matches next to them, e.g. a
+/// `target` folder whose `Cargo.toml` was deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphansConfig {
+    /// Glob patterns of build output directory names (case-insensitive)
+    #[serde(default = "default_orphans_names")]
+    pub names: Vec<String>,
+    /// Minimum size of an orphaned directory, in megabytes
+    #[serde(default = "default_orphans_min_size_mb")]
+    pub min_size_mb: u64,
+}
+
+impl Default for OrphansConfig {
+    fn default() -> Self {
+        OrphansConfig {
+            names: default_orphans_names(),
+            min_size_mb: default_orphans_min_size_mb(),
+        }
+    }
+}
+
+fn default_orphans_names() -> Vec<String> {
+    [
+        "target",
+        "node_modules",
+        "build",
+        ".build",
+        "_build",
+        "dist",
+        "bower_components",
+        ".stack-work",
+        "dist-newstyle",
+    ]
+    .iter()
+    .map(|name| name.to_string())
+    .collect()
+}
+
+fn default_orphans_min_size_mb() -> u64 {
+    10
+}
+
+/// Threshold used to report directories with too many entries (e.g. old `node_modules` graveyards)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryLimitConfig {
+    /// Number of direct entries above which a directory is reported
+    #[serde(default = "default_entry_limit_max_entries")]
+    pub max_entries: usize,
+    /// Do not descend into the reported directories
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub stop: bool,
+}
+
+impl Default for EntryLimitConfig {
+    fn default() -> Self {
+        EntryLimitConfig {
+            max_entries: default_entry_limit_max_entries(),
+            stop: false,
+        }
+    }
+}
+
+fn default_entry_limit_max_entries() -> usize {
+    10000
+}
+
+impl Config {
+    /// Keeps only the enabled rules selected by the filter. Fails if a selected rule name
+    /// is not configured, so a typo does not silently run no rule at all.
+    pub fn apply_rule_filter(&mut self, filter: &RuleFilter) -> crate::error::Result<()> {
+        if let Some(unknown) = filter
+            .rule_names
+            .iter()
+            .find(|name| !self.rules.iter().any(|rule| &rule.name == *name))
+        {
+            let names: Vec<&str> = self.rules.iter().map(|rule| rule.name.as_str()).collect();
+            return Err(Error::config(tr(
+                "config-unknown-rule",
+                &[
+                    ("rule", unknown.as_str().into()),
+                    ("rules", names.join(", ").into()),
+                ],
+            )));
+        }
+        self.rules.retain(|rule| filter.matches(rule));
+        Ok(())
+    }
+
+    /// Limits the scan of every root to `max_depth` levels, e.g. from `--max-depth`,
+    /// replacing the configured limits. Does nothing when None
+    pub fn override_max_depth(&mut self, max_depth: Option<usize>) {
+        if max_depth.is_none() {
+            return;
+        }
+        self.max_depth = max_depth;
+        for root in &mut self.roots {
+            root.max_depth = None;
+        }
+    }
+
+    /// Keeps only the configured roots selected by path (as configured or expanded) or by
+    /// name (the last path component, e.g. `Development`), and the `exclude_paths` entries
+    /// within them. Fails if a selector matches no root.
+    pub fn retain_roots(&mut self, selectors: &[String]) -> crate::error::Result<()> {
+        if selectors.is_empty() {
+            return Ok(());
+        }
+        let expanded_roots: Vec<PathBuf> = self
+            .roots
+            .iter()
+            .map(|root| expand_path(&root.path).unwrap_or_else(|_| PathBuf::from(&root.path)))
+            .collect();
+        let root_matches = |selector: &str, index: usize| {
+            let root = &expanded_roots[index];
+            selector == self.roots[index].path
+                || expand_path(selector).is_ok_and(|path| path.components().eq(root.components()))
+                || root.file_name().is_some_and(|name| name == selector)
+        };
+
+        let mut selected = vec![false; self.roots.len()];
+        for selector in selectors {
+            let mut found = false;
+            for (index, is_selected) in selected.iter_mut().enumerate() {
+                if root_matches(selector, index) {
+                    *is_selected = true;
+                    found = true;
+                }
+            }
+            if !found {
+                let names: Vec<&str> = self.roots.iter().map(|root| root.path.as_str()).collect();
+                return Err(Error::config(tr(
+                    "config-unknown-root",
+                    &[
+                        ("root", selector.as_str().into()),
+                        ("roots", names.join(", ").into()),
+                    ],
+                )));
+            }
+        }
+
+        let selected_roots: Vec<PathBuf> = expanded_roots
+            .into_iter()
+            .zip(&selected)
+            .filter(|(_, is_selected)| **is_selected)
+            .map(|(root, _)| root)
+            .collect();
+        self.roots = std::mem::take(&mut self.roots)
+            .into_iter()
+            .zip(&selected)
+            .filter(|(_, is_selected)| **is_selected)
+            .map(|(root, _)| root)
+            .collect();
+        self.exclude_paths.retain(|entry| {
+            expand_path(entry)
+                .is_ok_and(|path| selected_roots.iter().any(|root| path.starts_with(root)))
+        });
+        Ok(())
+    }
+
+    /// Adds a rule excluding the directories matching each ad-hoc pattern (`--exclude-from`),
+    /// on top of the configured rules
+    pub fn add_pattern_rules(&mut self, patterns: &[String]) {
+        self.rules.extend(patterns.iter().map(|pattern| Rule {
+            name: format!("exclude-from:{}", pattern),
+            file_match: format!("{}/", pattern),
+            ..Default::default()
+        }));
+    }
+
+    /// Returns whether symlinked directories below the roots are traversed, as they are
+    /// unless `follow_symlinks` is `false`
+    pub fn follows_symlinks(&self) -> bool {
+        self.follow_symlinks.unwrap_or(true)
+    }
+
+    /// Sorts the rules by descending priority, keeping the config order for ties
+    pub fn sort_rules_by_priority(&mut self) {
+        self.rules
+            .sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Root {
     pub path: String,
+    /// Patterns relative to the root (e.g. `experiments/*/sandbox`) of directories to skip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    /// Levels of directories scanned below this root, overriding the global `max_depth`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<usize>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Rule {
     pub name: String,
     pub file_match: String,
-    pub exclusions: Vec<String>,
+    /// Additional entries (names, nested paths or globs) that must all exist next to the match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// Paths or globs (e.g. `~/Development/**`) restricting where the rule applies (everywhere when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub applies_to: Vec<String>,
+    /// Entries to exclude relative to the matched folder; when empty the matched entry itself is excluded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusions: Vec<Exclusion>,
+    /// Only exclude resolved paths at least this large, in megabytes (e.g. disk images)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_size_mb: Option<u64>,
+    /// Rules with a higher priority are evaluated first; ties keep the config order
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub priority: i32,
+    /// What to do with the resolved exclusion paths
+    #[serde(default, skip_serializing_if = "is_exclude_action")]
+    pub action: RuleAction,
+    /// How serious a match is, shown for `warn` and `report` actions
+    #[serde(default, skip_serializing_if = "is_info_severity")]
+    pub severity: Severity,
+    /// Traversal behavior below the matched folder: `stop`, `continue` or `depth(N)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descend: Option<Descend>,
+    /// Set to `false` to disable the rule without removing it (enabled when omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Free-form labels used to select subsets of rules from the CLI
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Exclude the target of symlinked exclusion entries (e.g. `bazel-out`) instead of the link
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub resolve_symlinks: bool,
+    /// Also apply the exclusions in every subdirectory of the matched folder (e.g. multi-module builds)
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub recursive: bool,
+    /// Time Machine exclusion type: `sticky` (follows the item) or `fixed` (the path, needs root)
+    #[serde(default, skip_serializing_if = "is_sticky")]
+    pub exclusion_type: ExclusionType,
+    /// Where the exclusions are applied (Time Machine only when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backends: Vec<Backend>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// An entry of a rule's `exclusions` list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Exclusion {
+    /// Path relative to the matched folder, possibly nested or containing globs
+    Path(String),
+    /// Path printed by a command (e.g. `pnpm store path`), resolved once per scan, or
+    /// once per matched folder and run from it when `in_project` is set (e.g. `bazel info output_base`)
+    Command {
+        command: String,
+        #[serde(default, skip_serializing_if = "is_false")]
+        in_project: bool,
+    },
+}
+
+impl Exclusion {
+    /// Returns the relative path of a static entry
+    pub fn as_path(&self) -> Option<&str> {
+        match self {
+            Exclusion::Path(path) => Some(path),
+            Exclusion::Command { .. } => None,
+        }
+    }
+}
+
+impl From<&str> for Exclusion {
+    fn from(path: &str) -> Self {
+        Exclusion::Path(path.to_string())
+    }
+}
+
+impl From<String> for Exclusion {
+    fn from(path: String) -> Self {
+        Exclusion::Path(path)
+    }
+}
+
+impl std::fmt::Display for Exclusion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Exclusion::Path(path) => write!(f, "{}", path),
+            Exclusion::Command { command, .. } => write!(f, "$({})", command),
+        }
+    }
+}
+
+/// Selects a subset of the configured rules, e.g. from CLI filters
+#[derive(Debug, Default, Clone)]
+pub struct RuleFilter {
+    /// Keep only rules having at least one of these tags (all rules when empty)
+    pub only_tags: Vec<String>,
+    /// Drop rules having any of these tags
+    pub skip_tags: Vec<String>,
+    /// Keep only rules with one of these names (all rules when empty)
+    pub rule_names: Vec<String>,
+}
+
+impl RuleFilter {
+    /// Returns true if the rule is enabled and selected by the filter
+    pub fn matches(&self, rule: &Rule) -> bool {
+        rule.is_enabled()
+            && (self.only_tags.is_empty() || rule.tags.iter().any(|t| self.only_tags.contains(t)))
+            && !rule.tags.iter().any(|t| self.skip_tags.contains(t))
+            && (self.rule_names.is_empty() || self.rule_names.contains(&rule.name))
+    }
+}
+
+/// Traversal behavior below a folder matched by a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Descend {
+    /// Do not traverse any subdirectory of the matched folder
+    Stop,
+    /// Keep traversing the subdirectories that were not excluded
+    Continue,
+    /// Keep traversing at most N levels below the matched folder
+    Depth(usize),
+}
+
+impl std::str::FromStr for Descend {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        match value {
+            "stop" => Ok(Descend::Stop),
+            "continue" => Ok(Descend::Continue),
+            _ => value
+                .strip_prefix("depth(")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(|depth| depth.trim().parse().ok())
+                .map(Descend::Depth)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid descend value '{}', expected stop, continue or depth(N)",
+                        value
+                    )
+                }),
+        }
+    }
+}
+
+impl TryFrom<String> for Descend {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<Descend> for String {
+    fn from(descend: Descend) -> Self {
+        match descend {
+            Descend::Stop => "stop".to_string(),
+            Descend::Continue => "continue".to_string(),
+            Descend::Depth(depth) => format!("depth({})", depth),
+        }
+    }
+}
+
+impl Rule {
+    /// Returns true unless the rule is explicitly disabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    /// Returns the backends the exclusions are applied to, Time Machine when none is set.
+    /// iCloud Drive comes last, as it renames the path the other backends act on
+    pub fn backends(&self) -> Vec<Backend> {
+        if self.backends.is_empty() {
+            return vec![Backend::TimeMachine];
+        }
+        let mut backends = self.backends.clone();
+        backends.sort_by_key(|backend| *backend == Backend::ICloud);
+        backends
+    }
+
+    /// Returns the traversal behavior after a match. Without an explicit `descend`,
+    /// excluding rules using the `.` or `..` entries stop, all others continue.
+    pub fn descend_behavior(&self) -> Descend {
+        if let Some(descend) = self.descend {
+            return descend;
+        }
+
+        let excludes_folder = self
+            .exclusions
+            .iter()
+            .any(|exclusion| matches!(exclusion.as_path(), Some(".") | Some("..")));
+        if self.action == RuleAction::Exclude && excludes_folder {
+            Descend::Stop
+        } else {
+            Descend::Continue
+        }
+    }
+}
+
+/// Kind of Time Machine exclusion
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExclusionType {
+    /// Stored as an extended attribute of the item, so it follows moves and is lost on deletion
+    #[default]
+    Sticky,
+    /// Stored as a path in the Time Machine preferences (`tmutil addexclusion -p`), so it
+    /// survives the item being deleted and recreated, but requires root privileges
+    Fixed,
+}
+
+fn is_sticky(exclusion_type: &ExclusionType) -> bool {
+    *exclusion_type == ExclusionType::Sticky
+}
+
+/// A system an exclusion can be applied to
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    /// Time Machine backups, through `tmutil addexclusion`
+    #[default]
+    TimeMachine,
+    /// Spotlight indexing, through a `.metadata_never_index` file in excluded directories
+    Spotlight,
+    /// Dropbox sync, through the `com.dropbox.ignored` attribute (or Maestral's `.mignore`
+    /// file) for paths under a Dropbox folder
+    Dropbox,
+    /// iCloud Drive sync, by renaming directories under iCloud Drive with the `.nosync`
+    /// suffix and leaving a symlink under the original name. Opt-in, as it moves files
+    #[serde(rename = "icloud")]
+    ICloud,
+}
+
+impl std::fmt::Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Backend::TimeMachine => write!(f, "Time Machine"),
+            Backend::Spotlight => write!(f, "Spotlight"),
+            Backend::Dropbox => write!(f, "Dropbox"),
+            Backend::ICloud => write!(f, "iCloud Drive"),
+        }
+    }
+}
+
+/// What a rule does with the paths it matches
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    /// Exclude the matched paths from Time Machine
+    #[default]
+    Exclude,
+    /// Flag the matched paths as a backup hygiene issue, never touching Time Machine settings
+    Warn,
+    /// Only list the matched paths, never touching Time Machine settings
+    Report,
+}
+
+/// Severity of a rule match
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+fn is_info_severity(severity: &Severity) -> bool {
+    *severity == Severity::Info
+}
+
+fn is_exclude_action(action: &RuleAction) -> bool {
+    *action == RuleAction::Exclude
+}
+
+fn is_first_match(mode: &MatchMode) -> bool {
+    *mode == MatchMode::First
+}
+
+fn is_zero(value: &i32) -> bool {
+    *value == 0
+}
+
+/// How many rules may match a single directory entry
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    /// Only the first matching rule (in priority order) is applied
+    #[default]
+    First,
+    /// Every matching rule is applied
+    All,
 }
 
 /// Creates a default config file with common development project rules
@@ -41,128 +696,259 @@ pub fn create_default_config(local: bool, specified_path: Option<&str>) -> Resul
     // Check if the file already exists
     let path_obj = Path::new(&config_path);
     if path_obj.exists() {
-        return Err(anyhow::anyhow!(
-            "Config file already exists at: {}",
-            config_path
-        ));
+        return Err(anyhow::anyhow!(tr(
+            "config-exists",
+            &[("path", config_path.as_str().into())]
+        )));
     }
 
-    // Ensure the directory exists
-    ensure_dir_exists(&config_path)?;
+    write_config(&config_path, &default_config())?;
+
+    println!(
+        "{}",
+        tr("config-created", &[("path", config_path.as_str().into())])
+    );
+    println!("{}", tr("config-edit-roots", &[]));
+
+    Ok(())
+}
 
-    // Create a default config with common rules
-    let config = Config {
+/// The default configuration written by `init`: the home directory as root and common
+/// development rules
+pub fn default_config() -> Config {
+    Config {
         roots: vec![Root {
             path: "~/".to_string(),
+            ..Default::default()
         }],
         ignore: vec![".git".to_string()],
         rules: vec![
             Rule {
                 name: "net".to_string(),
                 file_match: "*.csproj".to_string(),
-                exclusions: vec!["obj".to_string(), "bin".to_string(), "packages".to_string()],
+                exclusions: vec!["obj".into(), "bin".into(), "packages".into()],
+                ..Default::default()
             },
             Rule {
                 name: "rust".to_string(),
                 file_match: "cargo.toml".to_string(),
-                exclusions: vec!["target".to_string()],
+                exclusions: vec!["target".into()],
+                ..Default::default()
             },
             Rule {
                 name: "go".to_string(),
                 file_match: "go.mod".to_string(),
-                exclusions: vec!["vendor".to_string()],
+                exclusions: vec!["vendor".into()],
+                ..Default::default()
             },
             Rule {
                 name: "node".to_string(),
                 file_match: "package.json".to_string(),
-                exclusions: vec!["node_modules".to_string(), "dist".to_string()],
+                exclusions: vec!["node_modules".into(), "dist".into()],
+                ..Default::default()
             },
             Rule {
                 name: "python".to_string(),
                 file_match: "requirements.txt".to_string(),
-                exclusions: vec!["__pycache__".to_string(), ".venv".to_string()],
+                exclusions: vec!["__pycache__".into(), ".venv".into()],
+                ..Default::default()
+            },
+            Rule {
+                // Virtualenvs of any name (`.venv`, `venv`, `env311`, ...) contain a pyvenv.cfg;
+                // named like the rule of config.yaml, so merged configs keep a single rule
+                name: "pyenv".to_string(),
+                file_match: "pyvenv.cfg".to_string(),
+                exclusions: vec![".".into()],
+                ..Default::default()
             },
             Rule {
                 name: "java".to_string(),
                 file_match: "pom.xml".to_string(),
-                exclusions: vec!["target".to_string()],
+                exclusions: vec!["target".into()],
+                ..Default::default()
             },
             Rule {
                 name: "php".to_string(),
                 file_match: "composer.json".to_string(),
-                exclusions: vec!["vendor".to_string()],
+                exclusions: vec!["vendor".into()],
+                ..Default::default()
             },
             Rule {
                 name: "vagrant".to_string(),
                 file_match: "Vagrantfile".to_string(),
-                exclusions: vec![".vagrant".to_string()],
+                exclusions: vec![".vagrant".into()],
+                ..Default::default()
             },
             Rule {
                 name: "bower".to_string(),
                 file_match: "bower.json".to_string(),
-                exclusions: vec!["bower_components".to_string()],
+                exclusions: vec!["bower_components".into()],
+                ..Default::default()
             },
             Rule {
                 name: "haskell".to_string(),
                 file_match: "stack.yaml".to_string(),
-                exclusions: vec![".stack-work".to_string()],
+                exclusions: vec![".stack-work".into()],
+                ..Default::default()
+            },
+            Rule {
+                name: "haskell-cabal".to_string(),
+                file_match: "*.cabal".to_string(),
+                exclusions: vec!["dist-newstyle".into()],
+                ..Default::default()
+            },
+            Rule {
+                name: "nix-flake".to_string(),
+                file_match: "flake.nix".to_string(),
+                exclusions: nix_exclusions(),
+                resolve_symlinks: true,
+                ..Default::default()
+            },
+            Rule {
+                name: "nix".to_string(),
+                file_match: "default.nix".to_string(),
+                exclusions: nix_exclusions(),
+                resolve_symlinks: true,
+                ..Default::default()
             },
             Rule {
                 name: "carthage".to_string(),
                 file_match: "Cartfile".to_string(),
-                exclusions: vec!["Carthage".to_string()],
+                exclusions: vec!["Carthage".into()],
+                ..Default::default()
             },
             Rule {
                 name: "cocoapods".to_string(),
                 file_match: "Podfile".to_string(),
-                exclusions: vec!["Pods".to_string()],
+                exclusions: vec!["Pods".into()],
+                ..Default::default()
             },
             Rule {
                 name: "swift".to_string(),
                 file_match: "Package.swift".to_string(),
-                exclusions: vec![".build".to_string()],
+                exclusions: vec![".build".into()],
+                ..Default::default()
             },
             Rule {
                 name: "elixir".to_string(),
                 file_match: "mix.exs".to_string(),
-                exclusions: vec!["_build".to_string()],
+                exclusions: vec!["_build".into()],
+                ..Default::default()
+            },
+            Rule {
+                name: "unity".to_string(),
+                file_match: "Assets/".to_string(),
+                requires: vec!["ProjectSettings".to_string()],
+                exclusions: vec!["Library".into(), "Temp".into(), "Obj".into(), "Logs".into()],
+                ..Default::default()
+            },
+            Rule {
+                name: "unreal".to_string(),
+                file_match: "*.uproject".to_string(),
+                exclusions: vec![
+                    "Binaries".into(),
+                    "Intermediate".into(),
+                    "Saved".into(),
+                    "DerivedDataCache".into(),
+                ],
+                ..Default::default()
+            },
+            Rule {
+                name: "godot".to_string(),
+                file_match: "project.godot".to_string(),
+                exclusions: vec![".godot".into(), ".import".into()],
+                ..Default::default()
+            },
+            Rule {
+                name: "bazel".to_string(),
+                file_match: "MODULE.bazel".to_string(),
+                exclusions: bazel_exclusions(),
+                resolve_symlinks: true,
+                ..Default::default()
+            },
+            // Only the Bazel file names: a `WORKSPACE*` pattern, matched case-insensitively,
+            // also catches the `workspace.json` of Nx and Angular projects
+            Rule {
+                name: "bazel-workspace".to_string(),
+                file_match: "WORKSPACE".to_string(),
+                exclusions: bazel_exclusions(),
+                resolve_symlinks: true,
+                ..Default::default()
+            },
+            Rule {
+                name: "bazel-workspace-bazel".to_string(),
+                file_match: "WORKSPACE.bazel".to_string(),
+                exclusions: bazel_exclusions(),
+                resolve_symlinks: true,
+                ..Default::default()
+            },
+            Rule {
+                name: "buck".to_string(),
+                file_match: ".buckconfig".to_string(),
+                exclusions: vec!["buck-out".into()],
+                ..Default::default()
             },
             Rule {
                 name: "project".to_string(),
                 file_match: "*.prj".to_string(),
-                exclusions: vec!["bin".to_string(), "debug".to_string()],
+                exclusions: vec!["bin".into(), "debug".into()],
+                ..Default::default()
             },
         ],
-    };
+        ..Default::default()
+    }
+}
+
+/// Writes a configuration as YAML to `config_path`, creating its directory if needed
+pub fn write_config(config_path: &str, config: &Config) -> crate::error::Result<()> {
+    ensure_dir_exists(config_path)?;
 
     // Serialize the config to YAML
-    let yaml =
-        serde_yaml::to_string(&config).context("Failed to serialize default config to YAML")?;
+    let yaml = serde_yaml::to_string(config).context("Failed to serialize config to YAML")?;
 
     // Create the file and write the YAML content
-    let mut file = fs::File::create(&config_path)
-        .with_context(|| format!("Failed to create config file at: {}", config_path))?;
+    let io_error = |source| Error::Io {
+        path: PathBuf::from(config_path),
+        source,
+    };
+    let mut file = fs::File::create(config_path).map_err(io_error)?;
+    file.write_all(yaml.as_bytes()).map_err(io_error)?;
 
-    file.write_all(yaml.as_bytes())
-        .with_context(|| format!("Failed to write to config file at: {}", config_path))?;
+    Ok(())
+}
 
-    println!("✅ Created default config file at: {}", config_path);
-    println!("You may want to edit the file to customize the root paths for your system.");
+/// Nix exclusions: the targets of the `result` build links living outside the
+/// Nix store (links into the store are skipped) and the `.direnv` cache
+fn nix_exclusions() -> Vec<Exclusion> {
+    vec!["result".into(), "result-*".into(), ".direnv".into()]
+}
 
-    Ok(())
+/// Bazel exclusions: the targets of the `bazel-*` convenience symlinks and the
+/// workspace output base (e.g. under `~/.cache/bazel`), when `bazel` is available
+fn bazel_exclusions() -> Vec<Exclusion> {
+    vec![
+        "bazel-*".into(),
+        Exclusion::Command {
+            command: "bazel info output_base".to_string(),
+            in_project: true,
+        },
+    ]
 }
 
 /// Find the configuration file by checking:
 /// 1. The specified path (if provided)
 /// 2. The current directory
 /// 3. The ~/.config/asimeow/ directory
-pub fn find_config_file(specified_path: Option<&str>) -> Result<String> {
+pub fn find_config_file(specified_path: Option<&str>) -> crate::error::Result<String> {
     // If a specific path is provided, use that
     if let Some(path) = specified_path {
         if Path::new(path).exists() {
             return Ok(path.to_string());
         } else {
-            return Err(anyhow::anyhow!("Specified config file not found: {}", path));
+            return Err(Error::config(tr(
+                "config-not-found",
+                &[("path", path.into())],
+            )));
         }
     }
 
@@ -179,9 +965,7 @@ pub fn find_config_file(specified_path: Option<&str>) -> Result<String> {
     }
 
     // No config file found
-    Err(anyhow::anyhow!(
-        "No configuration file found. Run 'asimeow init' to create one in ~/.config/asimeow/ or 'asimeow init --local' for the current directory."
-    ))
+    Err(Error::config(tr("config-none", &[])))
 }
 
 /// Ensure the directory exists for a given file path
@@ -196,42 +980,220 @@ fn ensure_dir_exists(file_path: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn load_config(config_path: Option<&str>, verbose: bool) -> Result<(Config, String)> {
+pub fn load_config(
+    config_path: Option<&str>,
+    verbose: bool,
+) -> crate::error::Result<(Config, String)> {
+    let (config, config_path_str) = read_config(config_path, verbose)?;
+
+    if config.roots.is_empty() && config.exclude_paths.is_empty() {
+        return Err(Error::config(tr("config-no-roots", &[])));
+    }
+
+    Ok((config, config_path_str))
+}
+
+/// Finds and parses the config file like `load_config`, without requiring roots,
+/// e.g. for shared rulesets run against other directories
+pub fn read_config(
+    config_path: Option<&str>,
+    verbose: bool,
+) -> crate::error::Result<(Config, String)> {
     // Find the config file
     let config_path_str = find_config_file(config_path)?;
 
-    // Print the configuration path
-    println!("Using configuration: {}", config_path_str);
+    // Print the configuration path, on stderr so command output can be redirected
+    eprintln!(
+        "{}",
+        tr("config-using", &[("path", config_path_str.as_str().into())])
+    );
 
     if verbose {
-        println!("Reading config from: {}", config_path_str);
+        eprintln!("Reading config from: {}", config_path_str);
     }
 
     // Read and parse the config file
-    let config_content = fs::read_to_string(&config_path_str)
-        .with_context(|| format!("Failed to read config file: {}", config_path_str))?;
+    let config_content = fs::read_to_string(&config_path_str).map_err(|e| {
+        Error::config_caused_by(
+            tr(
+                "config-read-failed",
+                &[("path", config_path_str.as_str().into())],
+            ),
+            e,
+        )
+    })?;
 
-    let config: Config = serde_yaml::from_str(&config_content)
-        .with_context(|| format!("Failed to parse config file: {}", config_path_str))?;
+    let config: Config = serde_yaml::from_str(&config_content).map_err(|e| {
+        Error::config_caused_by(
+            tr(
+                "config-parse-failed",
+                &[("path", config_path_str.as_str().into())],
+            ),
+            e,
+        )
+    })?;
+    crate::explorer::set_tmutil_overrides(
+        config.tmutil_path.clone(),
+        config.tmutil_wrapper.clone(),
+    );
+    if config.assume_yes {
+        crate::privileged::set_assume_yes();
+    }
 
+    // Like the config path, on stderr, leaving stdout to the output of the command
     if verbose {
-        println!("\nLoaded {} rules:", config.rules.len());
+        eprintln!("\nLoaded {} rules:", config.rules.len());
         for rule in &config.rules {
-            println!(
+            eprintln!(
                 "  - {} (pattern: {}, exclusions: {})",
                 rule.name,
                 rule.file_match,
-                rule.exclusions.join(", ")
+                rule.exclusions
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
             );
         }
-        println!();
+        eprintln!();
     }
 
-    if config.roots.is_empty() {
-        return Err(anyhow::anyhow!("No root paths defined in config file"));
+    Ok((config, config_path_str))
+}
+
+/// Parses an `--exclude-from` file, like rsync's: one directory name pattern per line
+/// (e.g. `.venv`, `*.egg-info`, `tmp/`), blank lines and `#` comments skipped. Patterns
+/// are matched against directory names, so paths are rejected.
+pub fn parse_pattern_file(content: &str) -> Result<Vec<String>> {
+    let mut patterns = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let pattern = line.trim_end_matches('/');
+        if pattern.is_empty() || pattern.contains('/') {
+            return Err(anyhow::anyhow!(
+                "Unsupported pattern '{}': only directory name patterns are supported",
+                line
+            ));
+        }
+        patterns.push(pattern.to_string());
     }
+    Ok(patterns)
+}
 
-    Ok((config, config_path_str))
+/// Reads the patterns of `--exclude-from` files
+pub fn read_pattern_files(paths: &[String]) -> Result<Vec<String>> {
+    let mut patterns = Vec::new();
+    for path in paths {
+        let content = fs::read_to_string(expand_tilde(path)?)
+            .with_context(|| format!("Failed to read pattern file: {}", path))?;
+        patterns.extend(
+            parse_pattern_file(&content)
+                .with_context(|| format!("Invalid pattern file: {}", path))?,
+        );
+    }
+    Ok(patterns)
+}
+
+/// Reads and parses an existing config file, applies `update` to it and writes it back when
+/// the update returns added entries. The file is re-serialized, so comments are lost
+fn update_config_file(
+    config_path: &str,
+    update: impl FnOnce(&mut Config) -> Vec<String>,
+) -> Result<Vec<String>> {
+    let config_content = fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read config file: {}", config_path))?;
+    let mut config: Config = serde_yaml::from_str(&config_content)
+        .with_context(|| format!("Failed to parse config file: {}", config_path))?;
+
+    let added = update(&mut config);
+    if added.is_empty() {
+        return Ok(added);
+    }
+
+    let yaml = serde_yaml::to_string(&config).context("Failed to serialize config")?;
+    fs::write(config_path, yaml)
+        .with_context(|| format!("Failed to write config file: {}", config_path))?;
+
+    Ok(added)
+}
+
+/// Appends rules to an existing config file, skipping those whose name is already taken,
+/// and returns the names of the added rules. The file is re-serialized, so comments are lost
+pub fn append_rules(config_path: &str, rules: Vec<Rule>) -> Result<Vec<String>> {
+    update_config_file(config_path, |config| {
+        let mut added = Vec::new();
+        for rule in rules {
+            if config
+                .rules
+                .iter()
+                .all(|existing| existing.name != rule.name)
+            {
+                added.push(rule.name.clone());
+                config.rules.push(rule);
+            }
+        }
+        added
+    })
+}
+
+/// Appends paths to the `protected` entries of an existing config file, skipping those
+/// already listed, and returns the added entries (`~/` relative under the home directory).
+/// The file is re-serialized, so comments are lost
+pub fn append_protected(config_path: &str, paths: &[PathBuf]) -> Result<Vec<String>> {
+    let home = dirs::home_dir();
+    update_config_file(config_path, |config| {
+        let mut added = Vec::new();
+        for path in paths {
+            let entry = match home.as_ref().and_then(|home| path.strip_prefix(home).ok()) {
+                Some(relative) => format!("~/{}", relative.display()),
+                None => path.display().to_string(),
+            };
+            if !config.protected.contains(&entry) {
+                config.protected.push(entry.clone());
+                added.push(entry);
+            }
+        }
+        added
+    })
+}
+
+/// Expands `$VAR` and `${VAR}` environment variables, then a leading `~/`
+pub fn expand_path(path: &str) -> Result<PathBuf> {
+    let mut expanded = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, remaining) = match after.strip_prefix('{') {
+            Some(braced) => {
+                let end = braced
+                    .find('}')
+                    .with_context(|| format!("Unterminated variable in path: {}", path))?;
+                (&braced[..end], &braced[end + 1..])
+            }
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..end], &after[end..])
+            }
+        };
+        if name.is_empty() {
+            expanded.push('$');
+        } else {
+            let value = std::env::var(name).with_context(|| {
+                format!("Environment variable {} is not set in path: {}", name, path)
+            })?;
+            expanded.push_str(&value);
+        }
+        rest = remaining;
+    }
+    expanded.push_str(rest);
+
+    expand_tilde(&expanded)
 }
 
 pub fn expand_tilde(path: &str) -> Result<PathBuf> {
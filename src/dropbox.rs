@@ -0,0 +1,173 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Extended attribute telling the Dropbox client not to sync an item
+pub const DROPBOX_IGNORED_XATTR: &str = "com.dropbox.ignored";
+
+/// Ignore file of Maestral, at the root of its Dropbox folder (gitignore syntax)
+pub const MAESTRAL_IGNORE_FILE: &str = ".mignore";
+
+/// Folder synchronized with Dropbox, by the official client or by Maestral
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncRoot {
+    Dropbox(PathBuf),
+    Maestral(PathBuf),
+}
+
+/// Dropbox folders of the official client and of Maestral, resolved once per scan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DropboxFolders {
+    pub dropbox: Vec<PathBuf>,
+    pub maestral: Vec<PathBuf>,
+}
+
+impl DropboxFolders {
+    /// Reads the Dropbox folders of the user with this home directory
+    pub fn of(home: &Path) -> Self {
+        DropboxFolders {
+            dropbox: dropbox_folders(home),
+            maestral: maestral_folders(home),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DropboxAccount {
+    path: PathBuf,
+}
+
+/// Returns the folders of the official Dropbox client, read from `~/.dropbox/info.json`
+/// (personal and business accounts) and the macOS File Provider location
+pub fn dropbox_folders(home: &Path) -> Vec<PathBuf> {
+    let mut folders: Vec<PathBuf> = fs::read_to_string(home.join(".dropbox/info.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<HashMap<String, DropboxAccount>>(&content).ok())
+        .map(|accounts| accounts.into_values().map(|account| account.path).collect())
+        .unwrap_or_default();
+
+    let cloud_storage = home.join("Library/CloudStorage/Dropbox*");
+    if let Ok(paths) = glob::glob(&cloud_storage.to_string_lossy()) {
+        folders.extend(paths.filter_map(|path| path.ok()));
+    }
+
+    folders.sort();
+    folders.dedup();
+    folders
+}
+
+/// Returns the Dropbox folders of Maestral, read from the `path` of its config files
+pub fn maestral_folders(home: &Path) -> Vec<PathBuf> {
+    let config_dirs = [
+        home.join(".config/maestral"),
+        home.join("Library/Application Support/maestral"),
+    ];
+
+    let mut folders = Vec::new();
+    for config_dir in config_dirs {
+        let Ok(entries) = fs::read_dir(&config_dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let config_file = entry.path();
+            if config_file.extension().is_none_or(|ext| ext != "ini") {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&config_file) else {
+                continue;
+            };
+            folders.extend(content.lines().filter_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "path" && !value.trim().is_empty())
+                    .then(|| PathBuf::from(value.trim()))
+            }));
+        }
+    }
+
+    folders.sort();
+    folders.dedup();
+    folders
+}
+
+/// Returns the Dropbox or Maestral folder containing `path`, if any
+pub fn sync_root(path: &Path, folders: &DropboxFolders) -> Option<SyncRoot> {
+    if let Some(root) = folders.dropbox.iter().find(|root| path.starts_with(root)) {
+        return Some(SyncRoot::Dropbox(root.clone()));
+    }
+    folders
+        .maestral
+        .iter()
+        .find(|root| path.starts_with(root))
+        .map(|root| SyncRoot::Maestral(root.clone()))
+}
+
+/// Stops a path under a Dropbox folder from syncing: the official client honors the
+/// `com.dropbox.ignored` attribute, Maestral its `.mignore` file.
+/// Returns true if the path was newly ignored or false if it was already ignored.
+pub fn ignore(path: &Path, root: &SyncRoot) -> bool {
+    match root {
+        SyncRoot::Dropbox(_) => set_ignored_xattr(path),
+        SyncRoot::Maestral(root) => add_to_mignore(path, root),
+    }
+}
+
+/// Sets the `com.dropbox.ignored` attribute (`user.` namespaced on Linux)
+fn set_ignored_xattr(path: &Path) -> bool {
+    let path_str = path.to_str().unwrap_or_default();
+    let linux_name = format!("user.{}", DROPBOX_IGNORED_XATTR);
+    let (mut check, mut set) = if cfg!(target_os = "macos") {
+        let mut check = Command::new("xattr");
+        check.args(["-p", DROPBOX_IGNORED_XATTR, path_str]);
+        let mut set = Command::new("xattr");
+        set.args(["-w", DROPBOX_IGNORED_XATTR, "1", path_str]);
+        (check, set)
+    } else {
+        let mut check = Command::new("getfattr");
+        check.args(["-n", &linux_name, path_str]);
+        let mut set = Command::new("setfattr");
+        set.args(["-n", &linux_name, "-v", "1", path_str]);
+        (check, set)
+    };
+
+    // The attribute is only written when it is missing
+    let already_ignored = check
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+    !already_ignored && set.status().map(|status| status.success()).unwrap_or(false)
+}
+
+/// Appends the path, relative to the Maestral root, to its `.mignore` file
+fn add_to_mignore(path: &Path, root: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+    let pattern = format!(
+        "/{}{}",
+        relative.display(),
+        if path.is_dir() { "/" } else { "" }
+    );
+
+    let mignore = root.join(MAESTRAL_IGNORE_FILE);
+    let existing = fs::read_to_string(&mignore).unwrap_or_default();
+    if existing.lines().any(|line| line.trim() == pattern) {
+        return false; // Already ignored
+    }
+
+    let separator = if existing.is_empty() || existing.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    let written = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&mignore)
+        .and_then(|mut file| writeln!(file, "{}{}", separator, pattern))
+        .is_ok();
+    crate::explorer::match_parent_ownership(&mignore);
+    written
+}
@@ -0,0 +1,79 @@
+use crate::exit::Failure;
+use std::io;
+use std::path::PathBuf;
+
+/// Why the backup system could not be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendErrorKind {
+    /// The backend cannot run on this system, e.g. `tmutil` is missing
+    Unavailable,
+    /// The change needs root privileges, e.g. fixed-path exclusions
+    ElevationRequired,
+    /// The backend refused a change
+    ExclusionFailed,
+}
+
+/// Error of the library API, so callers can branch on its kind
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The config file is missing or invalid, or the rule or root selection is unknown
+    #[error("{message}")]
+    Config {
+        message: String,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    /// A file or directory could not be read or written
+    #[error("Cannot access {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    /// The backup system failed on `path`
+    #[error("{message}")]
+    Backend {
+        path: PathBuf,
+        kind: BackendErrorKind,
+        message: String,
+    },
+    /// Any other failure
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Result of the library API
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    /// A config error with no underlying cause
+    pub fn config(message: impl Into<String>) -> Self {
+        Error::Config {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// A config error caused by `source`, e.g. a YAML syntax error
+    pub fn config_caused_by(
+        message: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Error::Config {
+            message: message.into(),
+            source: Some(source.into()),
+        }
+    }
+
+    /// Category of the error, picking the exit code of the CLI
+    pub fn failure(&self) -> Option<Failure> {
+        match self {
+            Error::Config { .. } => Some(Failure::Config),
+            Error::Backend {
+                kind: BackendErrorKind::Unavailable | BackendErrorKind::ElevationRequired,
+                ..
+            } => Some(Failure::Environment),
+            Error::Backend { .. } | Error::Io { .. } | Error::Other(_) => None,
+        }
+    }
+}
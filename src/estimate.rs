@@ -0,0 +1,85 @@
+use crate::backend::{backend_with_privilege_helper, BackupExclusionBackend};
+use crate::config::Config;
+use crate::export;
+use crate::i18n::tr;
+use crate::size::{directory_sizes, format_size, SizeMode};
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// A path a scan would exclude, with the space it occupies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EstimateEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    /// Already excluded from Time Machine, so enabling asimeow saves nothing more
+    pub already_excluded: bool,
+}
+
+/// Measures the paths a scan would exclude with `thread_count` threads. Returns the
+/// entries largest first.
+pub fn estimate_entries(
+    exclusions: &[PathBuf],
+    backend: &dyn BackupExclusionBackend,
+    thread_count: usize,
+    mode: SizeMode,
+) -> Vec<EstimateEntry> {
+    let sizes = directory_sizes(exclusions, thread_count, mode);
+
+    let mut entries: Vec<EstimateEntry> = exclusions
+        .iter()
+        .zip(sizes)
+        .map(|(path, size)| EstimateEntry {
+            path: path.clone(),
+            size,
+            already_excluded: backend.is_excluded(path),
+        })
+        .collect();
+    entries.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    entries
+}
+
+/// Runs a read-only scan of the configured roots and prints how many paths would be
+/// excluded and how much space they occupy, without changing Time Machine settings
+pub fn run_estimate(
+    config: Config,
+    thread_count: usize,
+    mode: SizeMode,
+    verbose: bool,
+) -> Result<()> {
+    let backend = backend_with_privilege_helper(config.privilege_helper.clone());
+    let exclusions = export::collect_exclusions(config, thread_count, verbose)?;
+    let entries = estimate_entries(&exclusions, backend.as_ref(), thread_count, mode);
+
+    println!("{}", tr("estimate-header", &[]));
+    println!("------------------------------------");
+    for entry in &entries {
+        let marker = if entry.already_excluded {
+            "🟡"
+        } else {
+            "📋"
+        };
+        println!(
+            "{} {:>10}  {}",
+            marker,
+            format_size(entry.size),
+            entry.path.display()
+        );
+    }
+
+    let (new, already): (Vec<&EstimateEntry>, Vec<&EstimateEntry>) =
+        entries.iter().partition(|entry| !entry.already_excluded);
+    let total = |entries: &[&EstimateEntry]| {
+        [
+            ("count", entries.len().into()),
+            (
+                "size",
+                format_size(entries.iter().map(|entry| entry.size).sum()).into(),
+            ),
+        ]
+    };
+    println!("\n{}", tr("estimate-new", &total(&new)));
+    if !already.is_empty() {
+        println!("{}", tr("estimate-already", &total(&already)));
+    }
+    Ok(())
+}
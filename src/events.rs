@@ -0,0 +1,91 @@
+use crate::explorer::ExplorerStats;
+use crate::output::ScanError;
+use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+/// What a scan did with a path resolved by a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExclusionOutcome {
+    /// The path was excluded by this scan
+    Excluded,
+    /// The path was already excluded
+    AlreadyExcluded,
+    /// A dry run found the path, leaving it untouched
+    DryRun,
+}
+
+/// Kind of a line of the human-readable output, see `Event::Message`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageLevel {
+    /// Detail of a verbose run, e.g. each directory scanned
+    Verbose,
+    /// Finding that is not acted upon, e.g. a report-only path or a cache-like candidate
+    Notice,
+    /// Problem worth the user's attention, e.g. a `warn` rule match or a skipped entry
+    Warning,
+    /// Question of an interactive scan, answered on stdin on the same line
+    Prompt,
+}
+
+/// Progress of a scan, passed to the handler of `ExplorerBuilder::on_event` as it happens.
+/// Events come from the worker threads, so their order across directories is not fixed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A directory is about to be scanned
+    DirScanned { path: PathBuf },
+    /// A rule matched `entry`, an entry of the scanned directory `folder`
+    RuleMatched {
+        rule: String,
+        folder: PathBuf,
+        entry: PathBuf,
+    },
+    /// A path resolved by a rule was excluded, or already was
+    Excluded {
+        rule: String,
+        path: PathBuf,
+        outcome: ExclusionOutcome,
+    },
+    /// An error met while scanning, also counted in `ExplorerStats::errors`
+    Error(ScanError),
+    /// A line of the human-readable output, printed by the reporter: on stdout with the
+    /// text format (warnings on stderr), on stderr next to the JSON formats
+    Message { level: MessageLevel, text: String },
+}
+
+/// Callback receiving the events of a scan, shared by the worker threads
+pub type EventHandler = Arc<dyn Fn(&Event) + Send + Sync>;
+
+/// Events of a scan running on a background thread, see `ExplorerBuilder::stream`. The
+/// iterator yields the events as they happen and ends with the scan
+pub struct ScanStream {
+    receiver: Receiver<Event>,
+    scan: JoinHandle<crate::error::Result<ExplorerStats>>,
+}
+
+impl ScanStream {
+    pub(crate) fn new(
+        receiver: Receiver<Event>,
+        scan: JoinHandle<crate::error::Result<ExplorerStats>>,
+    ) -> Self {
+        ScanStream { receiver, scan }
+    }
+
+    /// Waits for the end of the scan, dropping the events not consumed yet, and returns
+    /// its counters
+    pub fn finish(self) -> crate::error::Result<ExplorerStats> {
+        drop(self.receiver);
+        self.scan
+            .join()
+            .map_err(|_| anyhow::anyhow!("The scan thread panicked"))?
+    }
+}
+
+impl Iterator for ScanStream {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        self.receiver.recv().ok()
+    }
+}
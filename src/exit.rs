@@ -0,0 +1,118 @@
+use crate::explorer::{ExplorerStats, State};
+use crate::i18n::tr;
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+/// The run succeeded and, with `--check`, nothing would change
+pub const SUCCESS: u8 = 0;
+/// `--check` found exclusions to make
+pub const CHANGES: u8 = 1;
+/// The config file is missing or invalid, or the rule or root selection is unknown
+pub const CONFIG_ERROR: u8 = 2;
+/// The system cannot apply exclusions, e.g. `tmutil` is missing or root is required
+pub const ENVIRONMENT_ERROR: u8 = 3;
+/// The scan completed, but some directories could not be processed
+pub const PARTIAL_FAILURE: u8 = 4;
+/// Any other failure, e.g. a file that could not be read or a command that failed
+pub const OTHER_ERROR: u8 = 5;
+
+/// Category of a failure, picking the exit code of the run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    Config,
+    Environment,
+    PartialFailure,
+}
+
+impl Failure {
+    pub fn code(self) -> u8 {
+        match self {
+            Failure::Config => CONFIG_ERROR,
+            Failure::Environment => ENVIRONMENT_ERROR,
+            Failure::PartialFailure => PARTIAL_FAILURE,
+        }
+    }
+}
+
+/// An error tagged with its failure category. It displays as the tagged error, so the
+/// messages and their causes are unchanged.
+#[derive(Debug)]
+struct Categorized {
+    failure: Failure,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for Categorized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl Error for Categorized {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// Tags an error with its failure category
+pub fn categorize(failure: Failure, error: anyhow::Error) -> anyhow::Error {
+    anyhow::Error::new(Categorized { failure, error })
+}
+
+/// Returns the failure category of an error, from the outermost tag or typed library
+/// error (see `error::Error::failure`)
+pub fn failure_of(error: &anyhow::Error) -> Option<Failure> {
+    error.chain().find_map(|cause| {
+        if let Some(categorized) = cause.downcast_ref::<Categorized>() {
+            return Some(categorized.failure);
+        }
+        cause
+            .downcast_ref::<crate::error::Error>()
+            .and_then(crate::error::Error::failure)
+    })
+}
+
+/// Returns the exit code of a failed run
+pub fn code_of(error: &anyhow::Error) -> u8 {
+    failure_of(error).map_or(OTHER_ERROR, Failure::code)
+}
+
+/// Returns the paths a dry-run scan found that are not excluded yet
+pub fn pending_exclusions(state: &State) -> Vec<PathBuf> {
+    let mut pending: Vec<PathBuf> = state
+        .seen_exclusion_paths
+        .read()
+        .unwrap()
+        .iter()
+        .map(PathBuf::from)
+        .filter(|path| !state.backend.is_excluded(path))
+        .collect();
+    pending.sort();
+    pending
+}
+
+/// Turns the outcome of a scan into its exit status: a partial failure when directories
+/// could not be processed
+pub fn scan_status(stats: &ExplorerStats) -> anyhow::Result<()> {
+    if stats.errors > 0 {
+        return Err(categorize(
+            Failure::PartialFailure,
+            anyhow::anyhow!(tr(
+                "exit-partial-failure",
+                &[("count", stats.errors.into())]
+            )),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the exit code of a successful `--check` scan: `CHANGES` when `pending` paths
+/// would be excluded. Pending paths are the answer of the check, not a failure
+pub fn check_status(pending: &[PathBuf]) -> u8 {
+    if pending.is_empty() {
+        SUCCESS
+    } else {
+        CHANGES
+    }
+}
@@ -0,0 +1,702 @@
+use crate::backend::{default_backend, BackupExclusionBackend};
+use crate::config::{self, Config, Descend, Rule, RuleAction};
+use crate::explorer::{self, State};
+use crate::i18n::tr;
+use anyhow::Result;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// What a rule would do with one of its resolved exclusion paths
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExclusionOutcome {
+    Exclude,
+    Warn,
+    Report,
+    /// Left untouched, with the reason
+    Skipped(String),
+}
+
+impl fmt::Display for ExclusionOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExclusionOutcome::Exclude => f.write_str(&tr("explain-outcome-exclude", &[])),
+            ExclusionOutcome::Warn => f.write_str(&tr("explain-outcome-warn", &[])),
+            ExclusionOutcome::Report => f.write_str(&tr("explain-outcome-report", &[])),
+            ExclusionOutcome::Skipped(reason) => f.write_str(&tr(
+                "explain-outcome-skipped",
+                &[("reason", reason.as_str().into())],
+            )),
+        }
+    }
+}
+
+/// Step-by-step evaluation of a single rule against a folder
+#[derive(Debug)]
+pub struct RuleEvaluation {
+    /// Whether the folder is within the rule's `applies_to` entries
+    pub applies: bool,
+    /// Entries of the folder matching the rule's `file_match`
+    pub matched_entries: Vec<PathBuf>,
+    /// `requires` entries missing from the folder
+    pub missing_requirements: Vec<String>,
+    /// Folder holding a `.asimeow-keep` marker that opts the folder out of exclusions
+    pub keep_marker: Option<PathBuf>,
+    /// Resolved exclusion paths and what would happen to them
+    pub exclusions: Vec<(PathBuf, ExclusionOutcome)>,
+    /// Traversal behavior below the folder after a match
+    pub descend: Descend,
+}
+
+impl RuleEvaluation {
+    /// Returns true if the rule matches the folder
+    pub fn matches(&self) -> bool {
+        self.applies && !self.matched_entries.is_empty() && self.missing_requirements.is_empty()
+    }
+}
+
+/// Evaluates a rule against a folder the way a scan would, without changing anything.
+/// Command exclusions are run to resolve their paths.
+pub fn evaluate_rule(folder: &Path, rule: &Rule, config: &Config, verbose: bool) -> RuleEvaluation {
+    let mut matched_entries: Vec<PathBuf> = match fs::read_dir(folder) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|entry_path| explorer::file_match_matches(rule, entry_path))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    matched_entries.sort();
+
+    let mut evaluation = RuleEvaluation {
+        applies: explorer::rule_applies_to(folder, rule),
+        matched_entries,
+        missing_requirements: rule
+            .requires
+            .iter()
+            .filter(|required| explorer::expand_exclusion(folder, required).is_empty())
+            .cloned()
+            .collect(),
+        keep_marker: explorer::find_keep_marker(folder),
+        exclusions: Vec::new(),
+        descend: rule.descend_behavior(),
+    };
+    if !evaluation.matches() {
+        return evaluation;
+    }
+
+    // Without exclusions, the rule targets the matched entries themselves
+    let state = Arc::new(State::new());
+    let paths = if rule.exclusions.is_empty() {
+        evaluation.matched_entries.clone()
+    } else {
+        explorer::resolve_rule_exclusions(folder, rule, &state, verbose)
+    };
+
+    for path in paths {
+        let outcome = exclusion_outcome(&path, rule, config, evaluation.keep_marker.is_some());
+        let path = match &outcome {
+            ExclusionOutcome::Skipped(_) => path,
+            _ if rule.resolve_symlinks && path.is_symlink() => {
+                explorer::resolve_symlink_target(&path, &config.symlink_bases).unwrap_or(path)
+            }
+            _ => path,
+        };
+        evaluation.exclusions.push((path, outcome));
+    }
+    evaluation
+}
+
+/// Applies the checks of a scan to a resolved exclusion path, in the same order
+fn exclusion_outcome(
+    path: &Path,
+    rule: &Rule,
+    config: &Config,
+    keep_marker: bool,
+) -> ExclusionOutcome {
+    if keep_marker {
+        return ExclusionOutcome::Skipped(tr(
+            "explain-skip-marker",
+            &[("marker", explorer::KEEP_MARKER.into())],
+        ));
+    }
+
+    let target = if rule.resolve_symlinks && path.is_symlink() {
+        match explorer::resolve_symlink_target(path, &config.symlink_bases) {
+            Some(target) => target,
+            None => return ExclusionOutcome::Skipped(tr("explain-skip-symlink", &[])),
+        }
+    } else {
+        path.to_path_buf()
+    };
+
+    if let Some(min_size_mb) = rule.min_size_mb {
+        let size = crate::size::directory_size(&target);
+        if size < min_size_mb * 1024 * 1024 {
+            return ExclusionOutcome::Skipped(tr(
+                "explain-skip-size",
+                &[
+                    ("size", crate::size::format_size(size).into()),
+                    ("min", min_size_mb.into()),
+                ],
+            ));
+        }
+    }
+
+    if explorer::is_kept(&target, &config.keep) {
+        return ExclusionOutcome::Skipped(tr("explain-skip-keep", &[]));
+    }
+    if explorer::is_kept(&target, &config.protected) {
+        return ExclusionOutcome::Skipped(tr("explain-skip-protected", &[]));
+    }
+
+    match rule.action {
+        RuleAction::Exclude => ExclusionOutcome::Exclude,
+        RuleAction::Warn => ExclusionOutcome::Warn,
+        RuleAction::Report => ExclusionOutcome::Report,
+    }
+}
+
+/// A configured source that excludes a path or one of its ancestors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Responsibility {
+    /// Rule name, or `exclude_paths` / `nobackup` for the built-in sources
+    pub source: String,
+    /// Folder where the rule matched
+    pub folder: PathBuf,
+    /// Path excluded by the source: the explained path or one of its ancestors
+    pub target: PathBuf,
+    pub outcome: ExclusionOutcome,
+}
+
+/// Finds the configured sources responsible for excluding `path`, by evaluating the
+/// enabled rules in the path and each of its ancestors, the `exclude_paths` entries
+/// and the `.nobackup` markers
+pub fn find_responsible(path: &Path, config: &Config, verbose: bool) -> Vec<Responsibility> {
+    let mut responsible = Vec::new();
+
+    for entry in &config.exclude_paths {
+        if let Ok(fixed) = config::expand_path(entry) {
+            if path.starts_with(&fixed) {
+                responsible.push(Responsibility {
+                    source: "exclude_paths".to_string(),
+                    folder: fixed.clone(),
+                    target: fixed,
+                    outcome: ExclusionOutcome::Exclude,
+                });
+            }
+        }
+    }
+
+    for folder in path.ancestors().filter(|folder| folder.is_dir()) {
+        if folder.join(explorer::NOBACKUP_MARKER).is_file() {
+            responsible.push(Responsibility {
+                source: "nobackup".to_string(),
+                folder: folder.to_path_buf(),
+                target: folder.to_path_buf(),
+                outcome: ExclusionOutcome::Exclude,
+            });
+        }
+
+        for rule in config.rules.iter().filter(|rule| rule.is_enabled()) {
+            let evaluation = evaluate_rule(folder, rule, config, verbose);
+            for (target, outcome) in evaluation.exclusions {
+                if path.starts_with(&target) {
+                    responsible.push(Responsibility {
+                        source: rule.name.clone(),
+                        folder: folder.to_path_buf(),
+                        target,
+                        outcome,
+                    });
+                }
+            }
+        }
+    }
+    responsible
+}
+
+/// Explains whether a path is excluded from backups according to `backend`, through which
+/// mechanism, and which configured rule (if any) is responsible
+pub fn explain_path(
+    config_path: Option<&str>,
+    path_str: &str,
+    backend: &dyn BackupExclusionBackend,
+    verbose: bool,
+) -> Result<()> {
+    let path = config::expand_tilde(path_str)?;
+    if !path.exists() {
+        return Err(anyhow::anyhow!("Path does not exist: {}", path.display()));
+    }
+    let path = fs::canonicalize(&path).unwrap_or(path);
+
+    let path_arg = || [("path", path.display().to_string().into())];
+    println!("{}", tr("explain-header", &path_arg()));
+    println!("------------------------------------");
+    match backend.exclusion_mechanism(&path) {
+        Some(mechanism) => println!(
+            "{}",
+            tr(
+                "explain-excluded",
+                &[("mechanism", mechanism.to_string().into())]
+            )
+        ),
+        None => println!("   {}", tr("explain-included", &[])),
+    }
+
+    let config = match config::load_config(config_path, verbose) {
+        Ok((config, _)) => config,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                tr("explain-config-warning", &[("error", e.to_string().into())])
+            );
+            return Ok(());
+        }
+    };
+
+    if explorer::is_kept(&path, &config.keep) {
+        println!("{}", tr("explain-keep-entry", &[]));
+    }
+    if explorer::is_kept(&path, &config.protected) {
+        println!("{}", tr("explain-protected-entry", &[]));
+    }
+    if let Some(marker_dir) = explorer::find_keep_marker(&path) {
+        println!(
+            "{}",
+            tr(
+                "explain-keep-marker",
+                &[
+                    ("marker", explorer::KEEP_MARKER.into()),
+                    ("folder", marker_dir.display().to_string().into()),
+                ]
+            )
+        );
+    }
+
+    let responsible = find_responsible(&path, &config, verbose);
+    if responsible.is_empty() {
+        println!("\n{}", tr("explain-no-rule", &[]));
+        return Ok(());
+    }
+
+    println!("\n{}", tr("explain-responsible", &[]));
+    for responsibility in &responsible {
+        let line = tr(
+            "explain-responsibility",
+            &[
+                ("source", responsibility.source.as_str().into()),
+                ("folder", responsibility.folder.display().to_string().into()),
+                ("target", responsibility.target.display().to_string().into()),
+                ("outcome", responsibility.outcome.to_string().into()),
+            ],
+        );
+        println!("  {}", line);
+    }
+
+    Ok(())
+}
+
+/// Returns true if a static exclusion entry of the rule, relative to `folder`, could
+/// name `path`, ignoring case. Rules without exclusions target the entries they match.
+fn could_target(rule: &Rule, folder: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(folder) else {
+        return false;
+    };
+    let relative = explorer::normalize_name(&relative.to_string_lossy()).to_lowercase();
+    if rule.exclusions.is_empty() {
+        return path.parent() == Some(folder) && explorer::file_match_matches(rule, path);
+    }
+    rule.exclusions
+        .iter()
+        .filter_map(|exclusion| exclusion.as_path())
+        .any(|entry| {
+            let entry = explorer::normalize_name(entry.trim_end_matches('/')).to_lowercase();
+            entry == relative
+                || glob::Pattern::new(&entry).is_ok_and(|pattern| pattern.matches(&relative))
+        })
+}
+
+/// Explains why a scan leaves a path included: where the traversal stops before reaching
+/// it (ignore patterns, excluded ancestors, `descend` limits) and why the rules that could
+/// target it do not. Returns one line per finding.
+pub fn why_not(
+    path: &Path,
+    config: &Config,
+    backend: &dyn BackupExclusionBackend,
+    verbose: bool,
+) -> Vec<String> {
+    let mut findings = Vec::new();
+    if backend.is_excluded(path) {
+        findings.push(tr("why-not-already-excluded", &[]));
+        return findings;
+    }
+    let Some(parent) = path.parent() else {
+        findings.push(tr("why-not-filesystem-root", &[]));
+        return findings;
+    };
+
+    // The scan has to reach the parent folder, walking down from a configured root
+    let root = config
+        .roots
+        .iter()
+        .filter_map(|root| config::expand_path(&root.path).ok())
+        .filter(|root| path.starts_with(root) && path != root)
+        .max_by_key(|root| root.components().count());
+    if root.is_none() {
+        let roots: Vec<&str> = config.roots.iter().map(|root| root.path.as_str()).collect();
+        findings.push(tr(
+            "why-not-outside-roots",
+            &[("roots", roots.join(", ").into())],
+        ));
+    }
+    let start = root.unwrap_or_else(|| parent.to_path_buf());
+    let mut chain: Vec<&Path> = path
+        .ancestors()
+        .take_while(|folder| folder.starts_with(&start))
+        .collect();
+    chain.reverse();
+
+    for folder in &chain {
+        let folder_arg = ("folder", folder.display().to_string().into());
+        if folder.starts_with(explorer::NIX_STORE) {
+            findings.push(tr("why-not-nix-store", &[folder_arg]));
+            break;
+        }
+        if let Some(pattern) = config
+            .ignore
+            .iter()
+            .find(|pattern| explorer::is_ignored(folder, std::slice::from_ref(*pattern)))
+        {
+            let id = if explorer::is_path_ignore(pattern) {
+                "why-not-ignored-path"
+            } else {
+                "why-not-ignored-name"
+            };
+            findings.push(tr(
+                id,
+                &[folder_arg.clone(), ("pattern", pattern.as_str().into())],
+            ));
+        }
+        if let Some(pattern) = explorer::root_ignore_pattern(folder, &config.roots) {
+            findings.push(tr(
+                "why-not-root-ignored",
+                &[folder_arg, ("pattern", pattern.to_string().into())],
+            ));
+        }
+    }
+
+    let rules: Vec<&Rule> = config
+        .rules
+        .iter()
+        .filter(|rule| rule.is_enabled())
+        .collect();
+    for folder in chain.iter().filter(|folder| **folder != path) {
+        let folder_arg = || ("folder", folder.display().to_string().into());
+        if *folder != start && backend.is_excluded(folder) {
+            findings.push(tr("why-not-excluded-ancestor", &[folder_arg()]));
+        }
+        if folder.join(explorer::NOBACKUP_MARKER).is_file() {
+            findings.push(tr(
+                "why-not-nobackup",
+                &[folder_arg(), ("marker", explorer::NOBACKUP_MARKER.into())],
+            ));
+        }
+
+        let levels_to_parent = parent.components().count() - folder.components().count();
+        for rule in &rules {
+            let evaluation = evaluate_rule(folder, rule, config, verbose);
+            if !evaluation.matches() {
+                continue;
+            }
+            for (target, _) in &evaluation.exclusions {
+                if path.starts_with(target) && target != path {
+                    findings.push(tr(
+                        "why-not-under-target",
+                        &[
+                            ("target", target.display().to_string().into()),
+                            ("rule", rule.name.as_str().into()),
+                            folder_arg(),
+                        ],
+                    ));
+                }
+            }
+            match evaluation.descend {
+                Descend::Stop if levels_to_parent > 0 => findings.push(tr(
+                    "why-not-descend-stop",
+                    &[("rule", rule.name.as_str().into()), folder_arg()],
+                )),
+                Descend::Depth(depth) if levels_to_parent > depth => findings.push(tr(
+                    "why-not-descend-depth",
+                    &[
+                        ("rule", rule.name.as_str().into()),
+                        folder_arg(),
+                        ("depth", depth.into()),
+                    ],
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    // Rules that could name the path from one of its ancestors
+    let mut by_priority: Vec<&Rule> = config.rules.iter().collect();
+    by_priority.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+    let mut candidates = 0;
+    for folder in chain.iter().rev().filter(|folder| **folder != path) {
+        for rule in by_priority
+            .iter()
+            .filter(|rule| could_target(rule, folder, path))
+        {
+            candidates += 1;
+            findings.push(rule_finding(rule, folder, path, config, verbose));
+        }
+    }
+    if candidates == 0 {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        findings.push(tr(
+            "why-not-no-candidate",
+            &[("name", name.to_string().into())],
+        ));
+    }
+    findings
+}
+
+/// Explains what a rule that could name `path` from `folder` does with it
+fn rule_finding(rule: &Rule, folder: &Path, path: &Path, config: &Config, verbose: bool) -> String {
+    let rule_arg = || ("rule", rule.name.as_str().into());
+    let folder_arg = || ("folder", folder.display().to_string().into());
+    if !rule.is_enabled() {
+        return tr("why-not-rule-disabled", &[rule_arg(), folder_arg()]);
+    }
+
+    let evaluation = evaluate_rule(folder, rule, config, verbose);
+    if !evaluation.applies {
+        return tr(
+            "why-not-rule-outside",
+            &[
+                rule_arg(),
+                folder_arg(),
+                ("applies_to", rule.applies_to.join(", ").into()),
+            ],
+        );
+    }
+    if evaluation.matched_entries.is_empty() {
+        // `name/` only matches directories
+        let only_files = rule.file_match.strip_suffix('/').is_some_and(|dir_match| {
+            let file_rule = Rule {
+                file_match: dir_match.to_string(),
+                ..rule.clone()
+            };
+            fs::read_dir(folder).is_ok_and(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .any(|entry| explorer::file_match_matches(&file_rule, &entry.path()))
+            })
+        });
+        let id = if only_files {
+            "why-not-rule-no-match-files"
+        } else {
+            "why-not-rule-no-match"
+        };
+        return tr(
+            id,
+            &[
+                rule_arg(),
+                folder_arg(),
+                ("file_match", rule.file_match.as_str().into()),
+            ],
+        );
+    }
+    if !evaluation.missing_requirements.is_empty() {
+        return tr(
+            "why-not-rule-missing",
+            &[
+                rule_arg(),
+                folder_arg(),
+                (
+                    "requires",
+                    evaluation.missing_requirements.join(", ").into(),
+                ),
+            ],
+        );
+    }
+
+    match evaluation
+        .exclusions
+        .iter()
+        .find(|(target, _)| target == path)
+        .map(|(_, outcome)| outcome)
+    {
+        Some(ExclusionOutcome::Exclude) => tr("why-not-rule-excludes", &[rule_arg(), folder_arg()]),
+        Some(outcome) => tr(
+            "why-not-rule-outcome",
+            &[
+                rule_arg(),
+                folder_arg(),
+                ("outcome", outcome.to_string().into()),
+            ],
+        ),
+        None => {
+            // The entry resolves on case-insensitive volumes only
+            let relative = path.strip_prefix(folder).unwrap_or(path).to_string_lossy();
+            let case_mismatch = rule
+                .exclusions
+                .iter()
+                .filter_map(|exclusion| exclusion.as_path())
+                .find(|entry| entry.eq_ignore_ascii_case(&relative) && *entry != relative);
+            match case_mismatch {
+                Some(entry) => tr(
+                    "why-not-rule-case",
+                    &[
+                        rule_arg(),
+                        ("entry", entry.into()),
+                        ("relative", relative.to_string().into()),
+                    ],
+                ),
+                None => tr("why-not-rule-unresolved", &[rule_arg(), folder_arg()]),
+            }
+        }
+    }
+}
+
+/// Explains why a path is not excluded by a scan
+pub fn why_not_path(config_path: Option<&str>, path_str: &str, verbose: bool) -> Result<()> {
+    let path = config::expand_tilde(path_str)?;
+    if !path.exists() {
+        return Err(anyhow::anyhow!("Path does not exist: {}", path.display()));
+    }
+    let path = fs::canonicalize(&path).unwrap_or(path);
+    let (config, _) = config::load_config(config_path, verbose)?;
+
+    println!(
+        "{}",
+        tr(
+            "why-not-header",
+            &[("path", path.display().to_string().into())]
+        )
+    );
+    println!("------------------------------------");
+    for finding in why_not(&path, &config, default_backend().as_ref(), verbose) {
+        println!("{}", finding);
+    }
+    Ok(())
+}
+
+/// Evaluates a configured rule against a directory and explains step by step whether it
+/// matches, which exclusions would result and how the traversal would be pruned
+pub fn test_rule(
+    config_path: Option<&str>,
+    rule_name: &str,
+    path_str: &str,
+    verbose: bool,
+) -> Result<()> {
+    let (config, _) = config::load_config(config_path, verbose)?;
+    let rule = config
+        .rules
+        .iter()
+        .find(|rule| rule.name == rule_name)
+        .ok_or_else(|| {
+            let names: Vec<&str> = config.rules.iter().map(|rule| rule.name.as_str()).collect();
+            anyhow::anyhow!(
+                "Unknown rule '{}'. Configured rules: {}",
+                rule_name,
+                names.join(", ")
+            )
+        })?;
+
+    let folder = config::expand_tilde(path_str)?;
+    if !folder.is_dir() {
+        return Err(anyhow::anyhow!("Not a directory: {}", folder.display()));
+    }
+
+    let folder_arg = || ("folder", folder.display().to_string().into());
+    let file_match_arg = || ("file_match", rule.file_match.as_str().into());
+    println!(
+        "{}",
+        tr(
+            "test-rule-header",
+            &[("rule", rule.name.as_str().into()), folder_arg()]
+        )
+    );
+    println!("------------------------------------");
+    if !rule.is_enabled() {
+        println!("{}", tr("test-rule-disabled", &[]));
+    }
+
+    let evaluation = evaluate_rule(&folder, rule, &config, verbose);
+
+    let applies_to = [("applies_to", rule.applies_to.join(", ").into())];
+    if rule.applies_to.is_empty() {
+        println!("{}", tr("test-rule-applies-everywhere", &[]));
+    } else if evaluation.applies {
+        println!("{}", tr("test-rule-applies", &applies_to));
+    } else {
+        println!("{}", tr("test-rule-outside", &applies_to));
+        return Ok(());
+    }
+
+    if evaluation.matched_entries.is_empty() {
+        println!("{}", tr("test-rule-no-match", &[file_match_arg()]));
+        return Ok(());
+    }
+    for entry in &evaluation.matched_entries {
+        let name = entry.file_name().unwrap_or_default().to_string_lossy();
+        println!(
+            "{}",
+            tr(
+                "test-rule-match",
+                &[file_match_arg(), ("entry", name.to_string().into())]
+            )
+        );
+    }
+
+    for required in &rule.requires {
+        let id = if evaluation.missing_requirements.contains(required) {
+            "test-rule-missing"
+        } else {
+            "test-rule-found"
+        };
+        println!("{}", tr(id, &[("required", required.as_str().into())]));
+    }
+    if !evaluation.matches() {
+        return Ok(());
+    }
+
+    if let Some(marker_dir) = &evaluation.keep_marker {
+        println!(
+            "{}",
+            tr(
+                "test-rule-keep-marker",
+                &[
+                    ("marker", explorer::KEEP_MARKER.into()),
+                    ("folder", marker_dir.display().to_string().into()),
+                ]
+            )
+        );
+    }
+
+    println!("\n{}", tr("test-rule-exclusions", &[]));
+    if evaluation.exclusions.is_empty() {
+        println!("  {}", tr("test-rule-no-exclusion", &[]));
+    }
+    for (path, outcome) in &evaluation.exclusions {
+        println!("  {} - {}", path.display(), outcome);
+    }
+
+    println!("\n{}", tr("test-rule-traversal", &[]));
+    let traversal = match evaluation.descend {
+        Descend::Stop => tr("test-rule-descend-stop", &[folder_arg()]),
+        Descend::Continue => tr("test-rule-descend-continue", &[]),
+        Descend::Depth(depth) => tr(
+            "test-rule-descend-depth",
+            &[("depth", depth.into()), folder_arg()],
+        ),
+    };
+    println!("  {}", traversal);
+    if rule.recursive {
+        println!("  {}", tr("test-rule-recursive", &[]));
+    }
+
+    Ok(())
+}
@@ -1,29 +1,130 @@
-use crate::config::Rule;
+use crate::backend::{backend_with_privilege_helper, default_backend, BackupExclusionBackend};
+use crate::cloud::SyncedFolders;
+use crate::config::{
+    Backend, Config, Descend, Exclusion, ExclusionType, GitignoreMode, MatchMode, Root, Rule,
+    RuleAction,
+};
+use crate::error::BackendErrorKind;
+use crate::events::{Event, EventHandler, ExclusionOutcome, MessageLevel, ScanStream};
+use crate::i18n::tr;
+use crate::output::{
+    ErrorKind, HumanReporter, JsonReporter, OutputFormat, Reporter, ScanError, SilentReporter,
+};
+use crate::queue::FolderQueue;
 use anyhow::Result;
 use glob::Pattern;
-use std::collections::{HashMap, HashSet};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::string::ToString;
 use std::sync::{Arc, OnceLock, RwLock};
 use std::thread;
+use std::time::{Duration, Instant};
+use unicode_normalization::UnicodeNormalization;
 
 pub struct State {
-    pub folder_queue: RwLock<Vec<PathBuf>>,
+    pub folder_queue: RwLock<FolderQueue>,
     pub exclusion_found: RwLock<i32>,
     pub processed_paths: RwLock<i32>,
     pub active_tasks: RwLock<usize>,
     pub processing_complete: RwLock<bool>,
     pub newly_excluded: RwLock<i32>,
+    pub reported: RwLock<i32>,
+    pub warnings: RwLock<i32>,
+    pub candidates: RwLock<i32>,
+    pub crowded: RwLock<i32>,
+    pub orphans: RwLock<i32>,
+    // Exclusions a cloud service keeps uploading, see `cloud::sync_warning`
+    pub cloud_synced: RwLock<i32>,
+    // Dataless directories left unscanned, see `icloud::is_dataless`
+    pub dataless: RwLock<i32>,
+    // Directories reported by the heuristics, so their subdirectories are not reported again
+    pub heuristic_candidates: RwLock<HashSet<PathBuf>>,
+    // Paths listed by report-only rules (rule name, path)
+    pub reported_paths: RwLock<Vec<(String, PathBuf)>>,
+    // Paths already warned about or reported this run, listed once whatever matched them
+    pub listed_paths: RwLock<HashSet<PathBuf>>,
     // Tracks exclusion paths we already attempted this run to avoid repeated tmutil calls
     pub seen_exclusion_paths: RwLock<HashSet<String>>,
     // Optional memoization for exclusion status checks (path -> is_excluded)
     pub exclusion_status_cache: RwLock<HashMap<String, bool>>,
+    // Paths printed by exclusion commands, run once per scan (command -> path)
+    pub command_outputs: RwLock<HashMap<String, Option<PathBuf>>>,
+    // Folders matched by a rule with `descend: depth(N)` (folder -> max depth below it)
+    pub descend_limits: RwLock<HashMap<PathBuf, usize>>,
+    // Folders matched by a `recursive` rule (folder -> rules applied in every subdirectory)
+    pub recursive_rules: RwLock<HashMap<PathBuf, Vec<Rule>>>,
+    // Backup system the exclusions are applied to (Time Machine through tmutil by default)
+    pub backend: Arc<dyn BackupExclusionBackend>,
+    // Answers of the interactive review, its lock also keeps prompts from interleaving
+    pub review: RwLock<ReviewState>,
+    // Directory each busy worker is processing and since when (worker index -> path)
+    pub worker_paths: RwLock<HashMap<usize, (PathBuf, Instant)>>,
+    // Errors met while scanning, in the order they occurred
+    pub errors: RwLock<Vec<ScanError>>,
+    // Canonical paths of the scanned directories when following symlinks, to break cycles
+    pub visited_dirs: RwLock<HashSet<PathBuf>>,
+    // Counters of each rule, by rule name
+    pub rule_stats: RwLock<BTreeMap<String, RuleStats>>,
+    // Counters of each root, by expanded root path
+    pub root_stats: RwLock<BTreeMap<PathBuf, RootStats>>,
+    // Total size of the exclusions found, measured when `Config::size_mode` is set
+    pub exclusion_bytes: RwLock<u64>,
+    // Receives the events of the scan, see `ExplorerBuilder::on_event`
+    pub on_event: Option<EventHandler>,
+    // Presents the scan, see `output::Reporter`
+    pub reporter: Arc<dyn Reporter>,
+    // Folders uploaded by a cloud service, see `State::synced_folders`
+    synced_folders: OnceLock<SyncedFolders>,
 }
 
-static THIS_FOLDER: OnceLock<String> = OnceLock::new();
-static PARENT_FOLDER: OnceLock<String> = OnceLock::new();
+/// Answer to the interactive review of a new exclusion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewAnswer {
+    Yes,
+    No,
+    /// Accept this and every later exclusion of the same rule in this run
+    Always,
+    /// Refuse and add the path to the `protected` entries of the config
+    Never,
+}
+
+impl ReviewAnswer {
+    /// Parses an answer to the review prompt; an empty answer is a no
+    pub fn parse(answer: &str) -> Option<Self> {
+        match answer.trim().to_lowercase().as_str() {
+            "y" | "yes" => Some(ReviewAnswer::Yes),
+            "" | "n" | "no" => Some(ReviewAnswer::No),
+            "a" | "always" => Some(ReviewAnswer::Always),
+            "v" | "never" => Some(ReviewAnswer::Never),
+            _ => None,
+        }
+    }
+}
+
+/// Answers collected during an interactive scan
+#[derive(Debug, Default)]
+pub struct ReviewState {
+    /// Rules whose exclusions are accepted without asking
+    pub always_rules: HashSet<String>,
+    /// Paths refused with `never`, to be added to the protected entries
+    pub never_paths: Vec<PathBuf>,
+}
+
+/// Marker file that opts a directory and everything below it out of exclusions
+pub const KEEP_MARKER: &str = ".asimeow-keep";
+
+/// Read-only Nix store: never scanned, as it is rebuildable and usually lives on its own volume
+pub const NIX_STORE: &str = "/nix/store";
+
+/// File that keeps Spotlight from indexing the directory containing it
+pub const SPOTLIGHT_MARKER: &str = ".metadata_never_index";
+
+/// Marker file that requests the exclusion of the directory containing it
+pub const NOBACKUP_MARKER: &str = ".nobackup";
 
 impl Default for State {
     fn default() -> Self {
@@ -33,23 +134,129 @@ impl Default for State {
 
 impl State {
     pub fn new() -> Self {
+        Self::with_backend(default_backend())
+    }
+
+    /// Creates a state applying exclusions with the given backend (e.g. a mock in tests)
+    pub fn with_backend(backend: Arc<dyn BackupExclusionBackend>) -> Self {
         State {
-            folder_queue: RwLock::new(Vec::new()),
+            folder_queue: RwLock::new(FolderQueue::default()),
             exclusion_found: RwLock::new(0),
             processed_paths: RwLock::new(0),
             active_tasks: RwLock::new(0),
             processing_complete: RwLock::new(false),
             newly_excluded: RwLock::new(0),
+            reported: RwLock::new(0),
+            warnings: RwLock::new(0),
+            candidates: RwLock::new(0),
+            crowded: RwLock::new(0),
+            orphans: RwLock::new(0),
+            cloud_synced: RwLock::new(0),
+            dataless: RwLock::new(0),
+            heuristic_candidates: RwLock::new(HashSet::new()),
+            reported_paths: RwLock::new(Vec::new()),
+            listed_paths: RwLock::new(HashSet::new()),
             seen_exclusion_paths: RwLock::new(HashSet::new()),
             exclusion_status_cache: RwLock::new(HashMap::new()),
+            descend_limits: RwLock::new(HashMap::new()),
+            recursive_rules: RwLock::new(HashMap::new()),
+            review: RwLock::new(ReviewState::default()),
+            command_outputs: RwLock::new(HashMap::new()),
+            worker_paths: RwLock::new(HashMap::new()),
+            errors: RwLock::new(Vec::new()),
+            visited_dirs: RwLock::new(HashSet::new()),
+            rule_stats: RwLock::new(BTreeMap::new()),
+            root_stats: RwLock::new(BTreeMap::new()),
+            exclusion_bytes: RwLock::new(0),
+            on_event: None,
+            reporter: Arc::new(SilentReporter),
+            synced_folders: OnceLock::new(),
+            backend,
         }
     }
+
+    /// Returns the folders uploaded by a cloud service, read from the home directory on
+    /// first use
+    pub fn synced_folders(&self) -> &SyncedFolders {
+        self.synced_folders.get_or_init(|| {
+            dirs::home_dir()
+                .map(|home| SyncedFolders::of(&home))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Passes an event to the reporter and to the handler, if any
+    pub fn emit(&self, event: impl FnOnce() -> Event) {
+        let event = event();
+        self.reporter.event(&event);
+        if let Some(handler) = &self.on_event {
+            handler(&event);
+        }
+    }
+
+    /// Passes a line of the human-readable output to the reporter
+    pub fn message(&self, level: MessageLevel, text: String) {
+        self.emit(|| Event::Message { level, text });
+    }
+}
+
+/// Environment variable overriding the `tmutil` executable, e.g. a shim in tests
+pub const TMUTIL_PATH_ENV: &str = "ASIMEOW_TMUTIL";
+
+/// Environment variable holding a command prefix for every `tmutil` call, e.g. `sudo -n`
+pub const TMUTIL_WRAPPER_ENV: &str = "ASIMEOW_TMUTIL_WRAPPER";
+
+// `tmutil_path` and `tmutil_wrapper` of the loaded config, used when the environment
+// variables are not set
+static TMUTIL_OVERRIDES: RwLock<(Option<String>, Option<String>)> = RwLock::new((None, None));
+
+/// Sets the `tmutil` executable and wrapper configured in the config file
+pub fn set_tmutil_overrides(tmutil_path: Option<String>, wrapper: Option<String>) {
+    *TMUTIL_OVERRIDES.write().unwrap() = (tmutil_path, wrapper);
+}
+
+/// Returns the program and arguments running `tmutil`: the wrapper words followed by the
+/// executable, `tmutil` from the PATH by default
+pub fn tmutil_command_line(tmutil_path: Option<&str>, wrapper: Option<&str>) -> Vec<String> {
+    let mut command_line: Vec<String> = wrapper
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    command_line.push(
+        tmutil_path
+            .filter(|path| !path.is_empty())
+            .unwrap_or("tmutil")
+            .to_string(),
+    );
+    command_line
+}
+
+/// Returns the program and arguments running `tmutil`, from the environment variables or
+/// else the config file
+fn tmutil_invocation() -> Vec<String> {
+    let overrides = TMUTIL_OVERRIDES.read().unwrap();
+    let tmutil_path = std::env::var(TMUTIL_PATH_ENV)
+        .ok()
+        .or_else(|| overrides.0.clone());
+    let wrapper = std::env::var(TMUTIL_WRAPPER_ENV)
+        .ok()
+        .or_else(|| overrides.1.clone());
+    tmutil_command_line(tmutil_path.as_deref(), wrapper.as_deref())
+}
+
+/// Returns a `Command` running `tmutil`, with the configured executable and wrapper
+pub fn tmutil_command() -> Command {
+    let invocation = tmutil_invocation();
+    let mut command = Command::new(&invocation[0]);
+    command.args(&invocation[1..]);
+    command
 }
 
 /// Checks if a path is excluded from Time Machine backups on macOS.
 /// Returns true if the path is excluded, false otherwise.
 pub fn is_excluded_from_timemachine(path: &Path) -> bool {
-    let check_output = Command::new("tmutil")
+    let check_output = tmutil_command()
         .args(["isexcluded", path.to_str().unwrap_or_default()])
         .output();
 
@@ -62,159 +269,1341 @@ pub fn is_excluded_from_timemachine(path: &Path) -> bool {
     }
 }
 
-/// Excludes a path from Time Machine backups on macOS.
-/// Returns true if the path was successfully excluded or false if it was already excluded.
-pub fn exclude_from_timemachine(path: &Path) -> bool {
-    // Check if the path is already excluded
+/// Extended attribute holding a sticky Time Machine exclusion
+pub const STICKY_EXCLUSION_XATTR: &str = "com.apple.metadata:com_apple_backup_excludeItem";
+
+/// Returns true if the path itself carries a sticky Time Machine exclusion attribute
+pub fn has_sticky_exclusion(path: &Path) -> bool {
+    Command::new("xattr")
+        .args([
+            "-p",
+            STICKY_EXCLUSION_XATTR,
+            path.to_str().unwrap_or_default(),
+        ])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Time Machine preferences, whose `SkipPaths` holds the fixed-path exclusions
+pub const TIME_MACHINE_PREFERENCES: &str = "/Library/Preferences/com.apple.TimeMachine.plist";
+
+/// Returns the fixed-path exclusions of the Time Machine preferences (`SkipPaths`),
+/// or an empty list if they cannot be read (e.g. not on macOS)
+pub fn fixed_path_exclusions() -> Vec<PathBuf> {
+    Command::new("plutil")
+        .args([
+            "-extract",
+            "SkipPaths",
+            "json",
+            "-o",
+            "-",
+            TIME_MACHINE_PREFERENCES,
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| parse_skip_paths(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parses `SkipPaths` as extracted to JSON by `plutil`. Entries in the home directory
+/// may be stored with a `~` prefix
+pub fn parse_skip_paths(json: &str) -> Vec<PathBuf> {
+    serde_yaml::from_str::<Vec<String>>(json)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| crate::config::expand_tilde(entry).ok())
+        .map(|path| {
+            let trimmed = path.to_string_lossy().trim_end_matches('/').to_string();
+            if trimmed.is_empty() {
+                path
+            } else {
+                PathBuf::from(trimmed)
+            }
+        })
+        .collect()
+}
+
+/// How a path ends up excluded from Time Machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExclusionMechanism {
+    /// Sticky exclusion attribute on the path itself
+    Sticky,
+    /// Fixed-path exclusion in the Time Machine preferences
+    FixedPath,
+    /// The path is below an excluded ancestor
+    Inherited(PathBuf),
+}
+
+impl std::fmt::Display for ExclusionMechanism {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExclusionMechanism::Sticky => f.write_str(&tr("explain-mechanism-sticky", &[])),
+            ExclusionMechanism::FixedPath => f.write_str(&tr("explain-mechanism-fixed-path", &[])),
+            ExclusionMechanism::Inherited(ancestor) => f.write_str(&tr(
+                "explain-mechanism-inherited",
+                &[("path", ancestor.display().to_string().into())],
+            )),
+        }
+    }
+}
+
+/// Returns how a path is excluded from Time Machine, walking its ancestors,
+/// or None if it is included.
+pub fn exclusion_mechanism(path: &Path) -> Option<ExclusionMechanism> {
+    let skip_paths = fixed_path_exclusions();
+    if has_sticky_exclusion(path) {
+        return Some(ExclusionMechanism::Sticky);
+    }
+    if skip_paths.iter().any(|skip_path| skip_path == path) {
+        return Some(ExclusionMechanism::FixedPath);
+    }
+    if let Some(ancestor) = path.ancestors().skip(1).find(|ancestor| {
+        skip_paths.iter().any(|skip_path| skip_path == ancestor)
+            || has_sticky_exclusion(ancestor)
+            || is_excluded_from_timemachine(ancestor)
+    }) {
+        return Some(ExclusionMechanism::Inherited(ancestor.to_path_buf()));
+    }
+    // Excluded without the attribute: a fixed path missing from the readable preferences
     if is_excluded_from_timemachine(path) {
-        return false; // Already excluded
+        return Some(ExclusionMechanism::FixedPath);
     }
+    None
+}
 
-    // Exclude the path
-    let exclude_result = Command::new("tmutil")
-        .args(["addexclusion", path.to_str().unwrap_or_default()])
-        .status();
+/// Excludes a path from Time Machine backups on macOS with a sticky exclusion.
+/// Returns true if the path was successfully excluded or false if it was already excluded.
+pub fn exclude_from_timemachine(path: &Path) -> bool {
+    exclude_from_timemachine_as(path, ExclusionType::Sticky)
+}
 
-    match exclude_result {
-        Ok(status) => status.success(),
-        Err(_) => false,
-    }
+/// Excludes a path from Time Machine backups on macOS with the given exclusion type.
+/// Returns true if the path was successfully excluded or false if it was already excluded.
+pub fn exclude_from_timemachine_as(path: &Path, exclusion_type: ExclusionType) -> bool {
+    default_backend().exclude(path, exclusion_type)
 }
 
-/// Removes a path from Time Machine exclusions on macOS.
+/// Removes a sticky path exclusion from Time Machine on macOS.
 /// Returns true if the path was successfully included or false if it was already included.
 pub fn include_in_timemachine(path: &Path) -> bool {
-    // Check if the path is already included (not excluded)
-    if !is_excluded_from_timemachine(path) {
-        return false; // Already included
+    include_in_timemachine_as(path, ExclusionType::Sticky)
+}
+
+/// Removes a Time Machine exclusion of the given type on macOS.
+/// Returns true if the path was successfully included or false if it was already included.
+pub fn include_in_timemachine_as(path: &Path, exclusion_type: ExclusionType) -> bool {
+    default_backend().include(path, exclusion_type)
+}
+
+/// Runs a `tmutil` exclusion verb (`addexclusion`, `removeexclusion`) on paths, in a
+/// single call, adding `-p` for fixed-path exclusions. Those need root: without it, the
+/// command is prefixed with the privilege helper when one is set.
+/// Returns true if the command succeeded for every path.
+pub fn run_tmutil(
+    verb: &str,
+    paths: &[PathBuf],
+    exclusion_type: ExclusionType,
+    privilege_helper: Option<&str>,
+) -> bool {
+    let mut args = tmutil_invocation();
+    args.push(verb.to_string());
+    if exclusion_type == ExclusionType::Fixed {
+        args.push("-p".to_string());
     }
+    args.extend(paths.iter().map(|path| path.to_string_lossy().to_string()));
 
-    // Include the path (remove exclusion)
-    let include_result = Command::new("tmutil")
-        .args(["removeexclusion", path.to_str().unwrap_or_default()])
-        .status();
+    if exclusion_type == ExclusionType::Fixed && !is_elevated() {
+        if let Some(helper) = privilege_helper {
+            let helper_args = helper.split_whitespace().map(String::from);
+            args.splice(0..0, helper_args);
+        }
+    }
 
-    match include_result {
+    match Command::new(&args[0]).args(&args[1..]).status() {
         Ok(status) => status.success(),
         Err(_) => false,
     }
 }
 
-fn process_exclusion(path: &Path, rule: &Rule, state: &Arc<State>, verbose: bool) {
-    // Print in the requested format: /path/to/excluded/dir - rule-name
-    for exclusion in &rule.exclusions {
-        let exclusion_path = path.join(exclusion);
+/// Returns true if the process runs as root
+pub fn is_elevated() -> bool {
+    Command::new("id")
+        .arg("-u")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "0")
+        .unwrap_or(false)
+}
+
+/// Excludes a directory from Spotlight indexing by creating a `.metadata_never_index` file.
+/// Returns true if the file was created, or false if it already existed or the path is a file.
+pub fn exclude_from_spotlight(path: &Path) -> bool {
+    if !path.is_dir() {
+        return false;
+    }
+
+    let marker = path.join(SPOTLIGHT_MARKER);
+    if marker.exists() {
+        return false; // Already excluded
+    }
+    if fs::File::create(&marker).is_err() {
+        return false;
+    }
+    match_parent_ownership(&marker);
+    true
+}
+
+/// Gives a file created by asimeow the owner of its directory, so files created by a
+/// root scan of another user's home (`--all-users`) stay theirs. Only root can change
+/// the owner, other processes already create files they own, so errors are ignored
+pub fn match_parent_ownership(path: &Path) {
+    use std::os::unix::fs::MetadataExt;
+
+    let Some(parent) = path.parent() else {
+        return;
+    };
+    let (Ok(parent_metadata), Ok(metadata)) = (fs::metadata(parent), fs::symlink_metadata(path))
+    else {
+        return;
+    };
+    if (metadata.uid(), metadata.gid()) != (parent_metadata.uid(), parent_metadata.gid()) {
+        let _ = std::os::unix::fs::lchown(
+            path,
+            Some(parent_metadata.uid()),
+            Some(parent_metadata.gid()),
+        );
+    }
+}
+
+/// Returns `name` in Unicode composed form (NFC), so that names and patterns compare
+/// equal however their accented characters are encoded. HFS+ stores file names
+/// decomposed (`e` + combining accent) while editors usually write composed characters.
+pub fn normalize_name(name: &str) -> String {
+    name.nfc().collect()
+}
+
+/// Returns true if an exclusion entry contains glob metacharacters.
+fn is_glob_exclusion(exclusion: &str) -> bool {
+    exclusion.contains(['*', '?', '['])
+}
+
+/// Expands a single exclusion path component against `base`.
+/// Literal components are joined to `base` and returned if they exist, while glob
+/// components (e.g. `cmake-build-*`) are matched against the children of `base`.
+/// When `dirs_only` is set, glob components only match child directories. Nothing is
+/// found under a dataless `base` (e.g. an evicted `target` for `target/debug`), as
+/// looking into it would download it.
+fn expand_component(base: &Path, component: &str, dirs_only: bool) -> Vec<PathBuf> {
+    if crate::icloud::is_dataless_path(base) {
+        return Vec::new();
+    }
+
+    let pattern = if is_glob_exclusion(component) {
+        Pattern::new(&normalize_name(component)).ok()
+    } else {
+        None
+    };
+
+    let Some(pattern) = pattern else {
+        let exclusion_path = base.join(component);
         if exclusion_path.exists() {
-            // Skip if we already processed this exact exclusion path in this run
-            let exclusion_str = exclusion_path.display().to_string();
-            {
-                let seen = state.seen_exclusion_paths.read().unwrap();
-                if seen.contains(&exclusion_str) {
-                    continue;
+            return vec![exclusion_path];
+        }
+        if component.is_ascii() {
+            return Vec::new();
+        }
+        // The file system may store the name in another Unicode form
+        let component = normalize_name(component);
+        return match fs::read_dir(base) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|entry_path| {
+                    (!dirs_only || entry_path.is_dir())
+                        && normalize_name(
+                            &entry_path.file_name().unwrap_or_default().to_string_lossy(),
+                        ) == component
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+    };
+
+    let mut matches: Vec<PathBuf> = match fs::read_dir(base) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|entry_path| {
+                (!dirs_only || entry_path.is_dir())
+                    && pattern.matches(&normalize_name(
+                        &entry_path.file_name().unwrap_or_default().to_string_lossy(),
+                    ))
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    matches.sort();
+    matches
+}
+
+/// Resolves an exclusion entry against the matched folder.
+/// Entries may contain path separators (e.g. `target/debug`, `.idea/caches`), in which
+/// case each component is resolved in turn and may itself be a glob pattern.
+/// Intermediate components only match directories, while the last one also matches
+/// files, so that entries like `*.o` or `build/*.ipa` exclude individual files.
+pub fn expand_exclusion(base: &Path, exclusion: &str) -> Vec<PathBuf> {
+    // Special entries: "." is the matched folder itself, ".." is kept as-is
+    if exclusion == "." {
+        return vec![base.to_path_buf()];
+    }
+    if exclusion == ".." {
+        return expand_component(base, exclusion, true);
+    }
+
+    let components: Vec<&str> = exclusion
+        .split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect();
+
+    // An entry made only of separators resolves to nothing
+    if components.is_empty() {
+        return Vec::new();
+    }
+
+    let mut current = vec![base.to_path_buf()];
+    for (index, component) in components.iter().enumerate() {
+        let dirs_only = index + 1 < components.len();
+        current = current
+            .iter()
+            .flat_map(|parent| expand_component(parent, component, dirs_only))
+            .collect();
+        if current.is_empty() {
+            break;
+        }
+    }
+
+    current
+}
+
+/// Runs a shell command, optionally from `cwd`, and returns the existing path printed on
+/// the first line of its output (with `~` expansion), or None if the command failed.
+pub fn run_exclusion_command(command: &str, cwd: Option<&Path>) -> Option<PathBuf> {
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]);
+    if let Some(cwd) = cwd {
+        shell.current_dir(cwd);
+    }
+    let output = shell.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first_line = stdout.lines().next()?.trim();
+    if first_line.is_empty() {
+        return None;
+    }
+
+    let path = crate::config::expand_tilde(first_line).ok()?;
+    path.exists().then_some(path)
+}
+
+/// Resolves a command exclusion, running the command only the first time it is seen in a
+/// scan, or the first time it is seen for a folder when `cwd` is given.
+fn resolve_command_exclusion(
+    command: &str,
+    cwd: Option<&Path>,
+    state: &Arc<State>,
+    verbose: bool,
+) -> Option<PathBuf> {
+    let cache_key = match cwd {
+        Some(cwd) => format!("{}: {}", cwd.display(), command),
+        None => command.to_string(),
+    };
+
+    // Hold the lock while running, so concurrent workers never run the same command twice
+    let mut outputs = state.command_outputs.write().unwrap();
+    if let Some(cached) = outputs.get(&cache_key) {
+        return cached.clone();
+    }
+
+    let resolved = run_exclusion_command(command, cwd);
+    match &resolved {
+        Some(resolved_path) if verbose => state.message(
+            MessageLevel::Verbose,
+            format!(
+                "Command '{}' resolved to: {}",
+                command,
+                resolved_path.display()
+            ),
+        ),
+        // Per-project tools are often not installed, only mention it in verbose mode
+        None if verbose || cwd.is_none() => state.message(
+            MessageLevel::Warning,
+            tr("scan-command-no-path", &[("command", command.into())]),
+        ),
+        _ => {}
+    }
+
+    outputs.insert(cache_key, resolved.clone());
+    resolved
+}
+
+/// Resolves the target of a symlink, returning it only if it is an existing directory
+/// under one of the `bases` (the home directory when `bases` is empty).
+pub fn resolve_symlink_target(link: &Path, bases: &[String]) -> Option<PathBuf> {
+    let target = fs::canonicalize(link).ok()?;
+    if !target.is_dir() {
+        return None;
+    }
+
+    let bases: Vec<PathBuf> = if bases.is_empty() {
+        dirs::home_dir().into_iter().collect()
+    } else {
+        bases
+            .iter()
+            .filter_map(|base| crate::config::expand_tilde(base).ok())
+            .map(|base| fs::canonicalize(&base).unwrap_or(base))
+            .collect()
+    };
+
+    bases
+        .iter()
+        .any(|base| target.starts_with(base))
+        .then_some(target)
+}
+
+/// Returns true if a directory entry matches the rule's `file_match` (case-insensitive,
+/// after Unicode normalization). A trailing slash restricts the match to directories
+/// (e.g. `Assets/`).
+pub fn file_match_matches(rule: &Rule, entry_path: &Path) -> bool {
+    let (file_match, dirs_only) = match rule.file_match.strip_suffix('/') {
+        Some(dir_match) => (dir_match, true),
+        None => (rule.file_match.as_str(), false),
+    };
+    let file_match = normalize_name(file_match);
+    // Invalid patterns are reported once per scan, see `warn_invalid_patterns`
+    let pattern = Pattern::new(&file_match.to_lowercase()).unwrap_or_else(|_| {
+        Pattern::new(&glob::Pattern::escape(&file_match.to_lowercase())).unwrap()
+    });
+
+    let file_name_lc =
+        normalize_name(&entry_path.file_name().unwrap_or_default().to_string_lossy())
+            .to_lowercase();
+    pattern.matches(&file_name_lc) && (!dirs_only || entry_path.is_dir())
+}
+
+/// Returns true if every `requires` entry of a rule exists in the matched folder,
+/// allowing compound conditions such as Unity's `Assets` plus `ProjectSettings`.
+pub fn requirements_met(folder: &Path, rule: &Rule) -> bool {
+    rule.requires
+        .iter()
+        .all(|required| !expand_exclusion(folder, required).is_empty())
+}
+
+/// Returns true if the rule may match in `folder`: its `applies_to` list is empty, or
+/// `folder` is below one of its literal entries or has an ancestor matching one of its globs.
+pub fn rule_applies_to(folder: &Path, rule: &Rule) -> bool {
+    rule.applies_to.is_empty()
+        || rule.applies_to.iter().any(|entry| {
+            let Ok(prefix) = crate::config::expand_tilde(entry) else {
+                return false;
+            };
+
+            if is_glob_exclusion(entry) {
+                match Pattern::new(&prefix.to_string_lossy()) {
+                    Ok(pattern) => folder
+                        .ancestors()
+                        .any(|ancestor| pattern.matches_path(ancestor)),
+                    Err(_) => false,
                 }
+            } else {
+                folder.starts_with(&prefix)
+            }
+        })
+}
+
+/// Returns true if `path` is protected by one of the `keep` entries.
+/// Literal entries protect the path itself, everything below it and every ancestor
+/// (excluding an ancestor would exclude the kept path too), while glob entries
+/// protect any path that has a matching ancestor.
+pub fn is_kept(path: &Path, keep: &[String]) -> bool {
+    keep.iter().any(|entry| {
+        let Ok(keep_path) = crate::config::expand_tilde(entry) else {
+            return false;
+        };
+
+        if is_glob_exclusion(entry) {
+            match Pattern::new(&keep_path.to_string_lossy()) {
+                Ok(pattern) => path
+                    .ancestors()
+                    .any(|ancestor| pattern.matches_path(ancestor)),
+                Err(_) => false,
+            }
+        } else {
+            path.starts_with(&keep_path) || keep_path.starts_with(path)
+        }
+    })
+}
+
+/// Returns the closest directory, starting from `path` and walking up its ancestors,
+/// that contains a `.asimeow-keep` marker file.
+pub fn find_keep_marker(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.join(KEEP_MARKER).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Returns the existing protected paths that are currently excluded from Time Machine.
+/// Glob entries are expanded against the file system.
+pub fn find_excluded_protected_paths(
+    protected: &[String],
+    backend: &dyn BackupExclusionBackend,
+) -> Vec<PathBuf> {
+    let mut excluded = Vec::new();
+    for entry in protected {
+        let Ok(protected_path) = crate::config::expand_tilde(entry) else {
+            continue;
+        };
+
+        let candidates: Vec<PathBuf> = if is_glob_exclusion(entry) {
+            match glob::glob(&protected_path.to_string_lossy()) {
+                Ok(paths) => paths.filter_map(|p| p.ok()).collect(),
+                Err(_) => Vec::new(),
             }
+        } else if protected_path.exists() {
+            vec![protected_path]
+        } else {
+            Vec::new()
+        };
+
+        for candidate in candidates {
+            if backend.is_excluded(&candidate) {
+                excluded.push(candidate);
+            }
+        }
+    }
+    excluded
+}
+
+/// Resolves every exclusion entry of a rule against the matched folder, running command
+/// entries as needed. Symlinks, size thresholds and keep entries are not applied yet.
+pub fn resolve_rule_exclusions(
+    path: &Path,
+    rule: &Rule,
+    state: &Arc<State>,
+    verbose: bool,
+) -> Vec<PathBuf> {
+    rule.exclusions
+        .iter()
+        .flat_map(|exclusion| match exclusion {
+            Exclusion::Path(relative) => expand_exclusion(path, relative),
+            Exclusion::Command {
+                command,
+                in_project,
+            } => resolve_command_exclusion(command, in_project.then_some(path), state, verbose)
+                .into_iter()
+                .collect(),
+        })
+        .collect()
+}
 
-            // Try to exclude from Time Machine
-            let excluded = exclude_from_timemachine(&exclusion_path);
+/// Applies the exclusions of a matched rule and returns the resolved exclusion paths,
+/// so that the caller can avoid descending into them.
+fn process_exclusion(
+    path: &Path,
+    rule: &Rule,
+    config: &Config,
+    state: &Arc<State>,
+    verbose: bool,
+) -> Vec<PathBuf> {
+    let mut resolved = Vec::new();
 
-            if excluded {
-                // Green tick for newly excluded paths
-                println!("✅ {} - {}", exclusion_path.display(), rule.name);
+    // A marker file in this folder or any ancestor opts it out of exclusions
+    if let Some(marker_dir) = find_keep_marker(path) {
+        if verbose {
+            state.message(
+                MessageLevel::Verbose,
+                format!(
+                    "Skipping rule '{}' at {} ({} found in {})",
+                    rule.name,
+                    path.display(),
+                    KEEP_MARKER,
+                    marker_dir.display()
+                ),
+            );
+        }
+        return resolved;
+    }
+
+    // Print in the requested format: /path/to/excluded/dir - rule-name
+    for exclusion_path in resolve_rule_exclusions(path, rule, state, verbose) {
+        // Symlinked entries are replaced by their target, the link itself is never traversed
+        let exclusion_path = if rule.resolve_symlinks && exclusion_path.is_symlink() {
+            resolved.push(exclusion_path.clone());
+            match resolve_symlink_target(&exclusion_path, &config.symlink_bases) {
+                Some(target) => {
+                    if verbose {
+                        state.message(
+                            MessageLevel::Verbose,
+                            format!(
+                                "Resolved symlink {} to: {}",
+                                exclusion_path.display(),
+                                target.display()
+                            ),
+                        );
+                    }
+                    target
+                }
+                None => {
+                    if verbose {
+                        state.message(MessageLevel::Verbose, format!(
+                            "Skipping symlink {} - {} (target missing or outside the allowed bases)",
+                            exclusion_path.display(),
+                            rule.name
+                        ));
+                    }
+                    continue;
+                }
+            }
+        } else {
+            exclusion_path
+        };
 
-                // Increment the newly_excluded counter
-                let mut newly_excluded = state.newly_excluded.write().unwrap();
-                *newly_excluded += 1;
+        // A confined scan never changes anything outside its directory, wherever a root,
+        // an `exclude_paths` entry or a symlink points
+        if let Some(dir) = &config.confine_to {
+            if !crate::users::is_within(&exclusion_path, dir) {
+                state.message(
+                    MessageLevel::Warning,
+                    tr(
+                        "scan-outside-confinement",
+                        &[
+                            ("path", exclusion_path.display().to_string().into()),
+                            ("rule", rule.name.as_str().into()),
+                            ("dir", dir.display().to_string().into()),
+                        ],
+                    ),
+                );
+                continue;
+            }
+        }
 
+        // Size thresholds only keep large enough paths (e.g. disk images over 1 GB)
+        if let Some(min_size_mb) = rule.min_size_mb {
+            let size = crate::size::directory_size(&exclusion_path);
+            if size < min_size_mb * 1024 * 1024 {
                 if verbose {
-                    println!(
-                        "  → Excluded from Time Machine: {}",
-                        exclusion_path.display()
+                    state.message(
+                        MessageLevel::Verbose,
+                        format!(
+                            "Skipping {} - {} ({} is below {} MB)",
+                            exclusion_path.display(),
+                            rule.name,
+                            crate::size::format_size(size),
+                            min_size_mb
+                        ),
                     );
                 }
-            } else {
-                // Yellow circle for already excluded paths
-                println!("🟡 {} - {}", exclusion_path.display(), rule.name);
+                continue;
+            }
+        }
 
-                if verbose {
-                    println!("  → Already excluded from Time Machine");
+        // Keep and protected entries take precedence over any rule
+        if is_kept(&exclusion_path, &config.keep) {
+            if verbose {
+                state.message(
+                    MessageLevel::Verbose,
+                    format!(
+                        "Keeping {} - {} (matches a keep entry)",
+                        exclusion_path.display(),
+                        rule.name
+                    ),
+                );
+            }
+            continue;
+        }
+        if is_kept(&exclusion_path, &config.protected) {
+            if verbose {
+                state.message(
+                    MessageLevel::Verbose,
+                    format!(
+                        "Keeping {} - {} (matches a protected entry)",
+                        exclusion_path.display(),
+                        rule.name
+                    ),
+                );
+            }
+            continue;
+        }
+
+        // Warn and report rules list their matches without excluding or pruning them
+        match rule.action {
+            RuleAction::Exclude => {}
+            RuleAction::Warn => {
+                // Several markers, recursive rules or `match_mode: all` reach a path again
+                if !state
+                    .listed_paths
+                    .write()
+                    .unwrap()
+                    .insert(exclusion_path.clone())
+                {
+                    continue;
                 }
+                state.message(
+                    MessageLevel::Warning,
+                    tr(
+                        "scan-warn",
+                        &[
+                            ("path", exclusion_path.display().to_string().into()),
+                            ("rule", rule.name.as_str().into()),
+                            ("severity", rule.severity.to_string().into()),
+                        ],
+                    ),
+                );
+                let mut warnings = state.warnings.write().unwrap();
+                *warnings += 1;
+                continue;
             }
+            RuleAction::Report => {
+                if !state
+                    .listed_paths
+                    .write()
+                    .unwrap()
+                    .insert(exclusion_path.clone())
+                {
+                    continue;
+                }
+                if !config.quiet {
+                    state.message(
+                        MessageLevel::Notice,
+                        tr(
+                            "scan-report-only",
+                            &[
+                                ("path", exclusion_path.display().to_string().into()),
+                                ("rule", rule.name.as_str().into()),
+                                ("severity", rule.severity.to_string().into()),
+                            ],
+                        ),
+                    );
+                }
+                let mut reported = state.reported.write().unwrap();
+                *reported += 1;
+                state
+                    .reported_paths
+                    .write()
+                    .unwrap()
+                    .push((rule.name.clone(), exclusion_path.clone()));
+                continue;
+            }
+        }
 
-            // Increment the exclusion_found counter
-            let mut counter = state.exclusion_found.write().unwrap();
-            *counter += 1;
+        resolved.push(exclusion_path.clone());
 
-            // Mark as seen to avoid repeated tmutil calls on the same path
+        // Skip if we already processed this exact exclusion path in this run. Marking it
+        // as seen right away avoids repeated tmutil calls when workers race on the same path
+        let exclusion_str = exclusion_path.display().to_string();
+        {
             let mut seen = state.seen_exclusion_paths.write().unwrap();
-            seen.insert(exclusion_str);
+            if !seen.insert(exclusion_str) {
+                continue;
+            }
+        }
+
+        let excluded = |outcome| {
+            state.emit(|| Event::Excluded {
+                rule: rule.name.clone(),
+                path: exclusion_path.clone(),
+                outcome,
+            })
+        };
+
+        // In a dry run the path is still pruned from the traversal, but left untouched
+        if config.dry_run {
+            excluded(ExclusionOutcome::DryRun);
+            count_exclusion(state, config, rule, &exclusion_path);
+            warn_cloud_synced(state, config, rule, &exclusion_path);
+            continue;
         }
+
+        if config.interactive
+            && !state.backend.is_excluded(&exclusion_path)
+            && !review_exclusion(&exclusion_path, rule, state)
+        {
+            continue;
+        }
+
+        // Try to exclude with each backend of the rule (Time Machine by default)
+        let mut newly_excluded_by = Vec::new();
+        let mut only_reported = false;
+        for backend in rule.backends() {
+            let newly_excluded = match backend {
+                Backend::TimeMachine => {
+                    if state.backend.reports_only() {
+                        only_reported = true;
+                        continue;
+                    } else if state.backend.is_excluded(&exclusion_path) {
+                        false
+                    } else if state.backend.add(&exclusion_path, rule.exclusion_type) {
+                        true
+                    } else {
+                        record_error(
+                            state,
+                            config,
+                            ScanError::new(
+                                ErrorKind::Exclusion,
+                                &exclusion_path,
+                                "Failed to add the Time Machine exclusion",
+                            ),
+                            verbose.then(|| {
+                                format!(
+                                    "⚠️  {} - failed to add the Time Machine exclusion",
+                                    exclusion_path.display()
+                                )
+                            }),
+                        );
+                        continue;
+                    }
+                }
+                Backend::Spotlight => exclude_from_spotlight(&exclusion_path),
+                Backend::Dropbox => {
+                    // Paths outside the Dropbox folders are not synced, nothing to ignore
+                    let root =
+                        crate::dropbox::sync_root(&exclusion_path, &state.synced_folders().dropbox);
+                    match root {
+                        Some(root) => crate::dropbox::ignore(&exclusion_path, &root),
+                        None => continue,
+                    }
+                }
+                Backend::ICloud => {
+                    if !state
+                        .synced_folders()
+                        .is_under_icloud_drive(&exclusion_path)
+                    {
+                        continue;
+                    }
+                    match crate::icloud::move_to_nosync(&exclusion_path) {
+                        Ok(moved) => moved,
+                        Err(e) => {
+                            record_error(
+                                state,
+                                config,
+                                ScanError::new(
+                                    ErrorKind::Exclusion,
+                                    &exclusion_path,
+                                    format!("not moved out of iCloud Drive: {}", e),
+                                ),
+                                verbose.then(|| {
+                                    format!(
+                                        "⚠️  {} - not moved out of iCloud Drive: {}",
+                                        exclusion_path.display(),
+                                        e
+                                    )
+                                }),
+                            );
+                            continue;
+                        }
+                    }
+                }
+            };
+            if newly_excluded {
+                newly_excluded_by.push(backend);
+            } else if verbose {
+                state.message(
+                    MessageLevel::Verbose,
+                    format!("  → Already excluded from {}", backend),
+                );
+            }
+        }
+
+        if !newly_excluded_by.is_empty() {
+            excluded(ExclusionOutcome::Excluded);
+
+            // Increment the newly_excluded counter, in total and for the rule
+            *state.newly_excluded.write().unwrap() += 1;
+            {
+                let mut rule_stats = state.rule_stats.write().unwrap();
+                rule_stats
+                    .entry(rule.name.clone())
+                    .or_default()
+                    .newly_excluded += 1;
+            }
+
+            if verbose {
+                for backend in newly_excluded_by {
+                    state.message(
+                        MessageLevel::Verbose,
+                        format!(
+                            "  → Excluded from {}: {}",
+                            backend,
+                            exclusion_path.display()
+                        ),
+                    );
+                }
+            }
+        } else if only_reported {
+            excluded(ExclusionOutcome::DryRun);
+        } else {
+            excluded(ExclusionOutcome::AlreadyExcluded);
+        }
+
+        count_exclusion(state, config, rule, &exclusion_path);
+        warn_cloud_synced(state, config, rule, &exclusion_path);
     }
+
+    resolved
 }
 
-pub fn process_path(
-    path: &Path,
-    state: Arc<State>,
-    rules: &[Rule],
-    verbose: bool,
-    ignore_patterns: &[String],
-) -> Result<()> {
+/// Warns when a cloud service keeps uploading an exclusion of `rule`, e.g. `node_modules`
+/// in a Documents folder synced with iCloud Drive
+fn warn_cloud_synced(state: &State, config: &Config, rule: &Rule, path: &Path) {
+    let Some(warning) = crate::cloud::sync_warning(path, rule, state.synced_folders()) else {
+        return;
+    };
+    if config.format == OutputFormat::Text {
+        state.message(MessageLevel::Warning, warning);
+    }
+    *state.cloud_synced.write().unwrap() += 1;
+}
+
+/// Counts an exclusion found by `rule`, in total, for the rule and for its root, and
+/// measures it when `Config::size_mode` is set
+fn count_exclusion(state: &State, config: &Config, rule: &Rule, path: &Path) {
+    {
+        let mut counter = state.exclusion_found.write().unwrap();
+        *counter += 1;
+    }
+    {
+        let mut rule_stats = state.rule_stats.write().unwrap();
+        rule_stats
+            .entry(rule.name.clone())
+            .or_default()
+            .exclusions_found += 1;
+    }
+    if let Some((_, root_path)) = closest_root(path, config) {
+        let mut root_stats = state.root_stats.write().unwrap();
+        root_stats.entry(root_path).or_default().exclusions_found += 1;
+    }
+    if let Some(mode) = config.size_mode {
+        let size = crate::size::directory_size_as(path, mode);
+        *state.exclusion_bytes.write().unwrap() += size;
+    }
+}
+
+/// Asks whether to apply a new exclusion, showing its path, rule and size, unless the
+/// rule was accepted with `always`. Returns true if the exclusion is accepted.
+fn review_exclusion(path: &Path, rule: &Rule, state: &Arc<State>) -> bool {
+    // Held during the prompt, so workers ask one question at a time
+    let mut review = state.review.write().unwrap();
+    if review.always_rules.contains(&rule.name) || crate::privileged::assume_yes() {
+        return true;
+    }
+
+    let size = crate::size::format_size(crate::size::directory_size(path));
+    state.message(
+        MessageLevel::Notice,
+        tr(
+            "scan-review",
+            &[
+                ("path", path.display().to_string().into()),
+                ("rule", rule.name.as_str().into()),
+                ("size", size.into()),
+            ],
+        ),
+    );
+    loop {
+        state.message(
+            MessageLevel::Prompt,
+            format!("   {}", tr("scan-review-prompt", &[])),
+        );
+        let mut answer = String::new();
+        // A closed stdin answers no to every question
+        if std::io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+            return false;
+        }
+        match ReviewAnswer::parse(&answer) {
+            Some(ReviewAnswer::Yes) => return true,
+            Some(ReviewAnswer::No) => return false,
+            Some(ReviewAnswer::Always) => {
+                review.always_rules.insert(rule.name.clone());
+                return true;
+            }
+            Some(ReviewAnswer::Never) => {
+                review.never_paths.push(path.to_path_buf());
+                return false;
+            }
+            None => continue,
+        }
+    }
+}
+
+/// Excludes the existing `exclude_paths` of the config, honoring keep and protected entries
+fn exclude_fixed_paths(config: &Config, state: &Arc<State>, verbose: bool) {
+    for entry in &config.exclude_paths {
+        let path = match crate::config::expand_path(entry) {
+            Ok(path) => path,
+            Err(e) => {
+                state.message(
+                    MessageLevel::Warning,
+                    tr(
+                        "scan-exclude-path-skipped",
+                        &[
+                            ("entry", entry.as_str().into()),
+                            ("error", e.to_string().into()),
+                        ],
+                    ),
+                );
+                continue;
+            }
+        };
+        if !path.exists() {
+            if verbose {
+                state.message(
+                    MessageLevel::Verbose,
+                    format!("Exclude path does not exist: {}", path.display()),
+                );
+            }
+            continue;
+        }
+
+        let fixed_rule = Rule {
+            name: "exclude_paths".to_string(),
+            file_match: entry.clone(),
+            exclusions: vec![".".into()],
+            ..Default::default()
+        };
+        process_exclusion(&path, &fixed_rule, config, state, verbose);
+    }
+}
+
+/// Returns true if an `ignore` entry is matched against the full path rather than the
+/// directory name: entries containing a `/` (e.g. `**/Library/Containers/**`)
+pub fn is_path_ignore(entry: &str) -> bool {
+    entry.contains('/')
+}
+
+/// Returns the location an `ignore` entry prunes when it is an absolute path without
+/// glob metacharacters (e.g. `~/Development/do-not-touch` or `$HOME/scratch`)
+pub fn absolute_ignore_path(entry: &str) -> Option<PathBuf> {
+    if !is_path_ignore(entry) || !entry.starts_with(['/', '~', '$']) || is_glob_exclusion(entry) {
+        return None;
+    }
+    let path = crate::config::expand_path(entry).ok()?;
+    let path = normalize_name(&path.to_string_lossy());
+    match path.trim_end_matches('/') {
+        "" => Some(PathBuf::from("/")),
+        trimmed => Some(PathBuf::from(trimmed)),
+    }
+}
+
+/// Checks whether a directory matches one of the `ignore` glob patterns. Plain entries
+/// (e.g. `.git`) match the directory name, absolute paths prune exactly that location,
+/// and other path entries match the full path: `~` is expanded and relative entries
+/// match at any depth, as if prefixed by `**/`.
+pub fn is_ignored(path: &Path, ignore: &[String]) -> bool {
+    let dir_name = match path.file_name() {
+        Some(dir_name) => normalize_name(&dir_name.to_string_lossy()),
+        None => return false,
+    };
+    let full_path = normalize_name(&path.to_string_lossy());
+    // `*` stays within a path component, while `**` spans several
+    let path_options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    ignore.iter().any(|pattern| {
+        if let Some(ignored) = absolute_ignore_path(pattern) {
+            return Path::new(&full_path) == ignored;
+        }
+        // Use glob pattern matching for ignore patterns
+        let path_pattern = is_path_ignore(pattern);
+        let pattern = &if path_pattern {
+            let Ok(expanded) = crate::config::expand_tilde(pattern) else {
+                return false;
+            };
+            let expanded = expanded.to_string_lossy();
+            let expanded = expanded.trim_end_matches('/');
+            if expanded.starts_with('/') {
+                normalize_name(expanded)
+            } else {
+                normalize_name(&format!("**/{}", expanded))
+            }
+        } else {
+            normalize_name(pattern)
+        };
+        let glob_pattern = Pattern::new(pattern)
+            .unwrap_or_else(|_| Pattern::new(&glob::Pattern::escape(pattern)).unwrap());
+        if path_pattern {
+            glob_pattern.matches_with(&full_path, path_options)
+        } else {
+            glob_pattern.matches(&dir_name)
+        }
+    })
+}
+
+/// Returns the `ignore` pattern of a root containing `path` that matches the path relative
+/// to that root (e.g. `experiments/*/sandbox` for root `~/Development`), if any. `*` stays
+/// within a path component, while `**` spans several.
+pub fn root_ignore_pattern<'a>(path: &Path, roots: &'a [Root]) -> Option<&'a str> {
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    roots
+        .iter()
+        .filter(|root| !root.ignore.is_empty())
+        .find_map(|root| {
+            let root_path = crate::config::expand_tilde(&root.path).ok()?;
+            let relative = path.strip_prefix(&root_path).ok()?;
+            if relative.as_os_str().is_empty() {
+                return None;
+            }
+            let relative = normalize_name(&relative.to_string_lossy());
+            root.ignore
+                .iter()
+                .find(|pattern| {
+                    let pattern = normalize_name(pattern.trim_matches('/'));
+                    Pattern::new(&pattern)
+                        .unwrap_or_else(|_| Pattern::new(&Pattern::escape(&pattern)).unwrap())
+                        .matches_with(&relative, options)
+                })
+                .map(String::as_str)
+        })
+}
+
+/// Returns the configured root containing `path`, the closest one when roots are nested,
+/// with its expanded path
+fn closest_root<'a>(path: &Path, config: &'a Config) -> Option<(&'a Root, PathBuf)> {
+    config
+        .roots
+        .iter()
+        .filter_map(|root| Some((root, crate::config::expand_tilde(&root.path).ok()?)))
+        .filter(|(_, root_path)| path.starts_with(root_path))
+        .max_by_key(|(_, root_path)| root_path.components().count())
+}
+
+/// Returns the maximum scan depth of the root containing `path` (the closest one, with
+/// its own `max_depth` or else the global one) and the depth of `path` below that root
+pub fn depth_below_root(path: &Path, config: &Config) -> Option<(usize, usize)> {
+    let (root, root_path) = closest_root(path, config)?;
+    let max_depth = root.max_depth.or(config.max_depth)?;
+    let depth = path.components().count() - root_path.components().count();
+    Some((max_depth, depth))
+}
+
+/// Checks whether the traversal skips a directory: it matches the global `ignore`
+/// patterns or the `ignore` patterns of its root
+pub fn is_ignored_in(path: &Path, config: &Config) -> bool {
+    is_ignored(path, &config.ignore) || root_ignore_pattern(path, &config.roots).is_some()
+}
+
+/// Reports the `file_match` and `ignore` patterns that are not valid globs, which the
+/// matchers compare literally
+fn warn_invalid_patterns(state: &State, config: &Config) {
+    for rule in &config.rules {
+        let file_match = rule.file_match.trim_end_matches('/');
+        if Pattern::new(&normalize_name(file_match)).is_err() {
+            state.message(
+                MessageLevel::Warning,
+                format!(
+                    "Warning: Invalid pattern '{}' in rule '{}', using literal match",
+                    rule.file_match, rule.name
+                ),
+            );
+        }
+    }
+    for pattern in &config.ignore {
+        if Pattern::new(&normalize_name(pattern)).is_err() {
+            state.message(
+                MessageLevel::Warning,
+                format!(
+                    "Warning: Invalid ignore pattern '{}', using literal match",
+                    pattern
+                ),
+            );
+        }
+    }
+}
+
+/// Records a scan error, reported with the summary at the end of the scan and passed to
+/// the event handler. In the text format, `message` (given in verbose mode) is also
+/// printed on stderr right away
+fn record_error(state: &State, config: &Config, error: ScanError, message: Option<String>) {
+    if config.format == OutputFormat::Text {
+        if let Some(message) = message {
+            state.message(MessageLevel::Warning, message);
+        }
+    }
+    state.emit(|| Event::Error(error.clone()));
+    state.errors.write().unwrap().push(error);
+}
+
+pub fn process_path(path: &Path, state: Arc<State>, config: &Config, verbose: bool) -> Result<()> {
     // Skip if path doesn't exist or is not a directory
     if !path.exists() {
-        if verbose {
-            eprintln!("Error: Path does not exist: {}", path.display());
-        }
+        record_error(
+            &state,
+            config,
+            ScanError::new(ErrorKind::NotFound, path, "Path does not exist"),
+            verbose.then(|| format!("Error: Path does not exist: {}", path.display())),
+        );
         return Ok(());
     }
 
     if !path.is_dir() {
+        record_error(
+            &state,
+            config,
+            ScanError::new(ErrorKind::NotADirectory, path, "Not a directory"),
+            verbose.then(|| format!("Error: Not a directory: {}", path.display())),
+        );
+        return Ok(());
+    }
+
+    // The Nix store is reachable through `result` links and roots such as `/`
+    if path.starts_with(NIX_STORE) {
+        if path == Path::new(NIX_STORE) {
+            // Sizing the whole store takes longer than most scans, so only verbose runs do it
+            if verbose {
+                let size = crate::size::format_size(crate::size::directory_size(path));
+                state.message(
+                    MessageLevel::Notice,
+                    tr(
+                        "scan-nix-store-size",
+                        &[
+                            ("path", path.display().to_string().into()),
+                            ("size", size.into()),
+                        ],
+                    ),
+                );
+            } else if !config.quiet {
+                state.message(
+                    MessageLevel::Notice,
+                    tr(
+                        "scan-nix-store",
+                        &[("path", path.display().to_string().into())],
+                    ),
+                );
+            }
+        } else if verbose {
+            state.message(
+                MessageLevel::Verbose,
+                format!("Skipping path in the Nix store: {}", path.display()),
+            );
+        }
+        return Ok(());
+    }
+
+    // Check if this directory should be ignored based on its name or path
+    if is_ignored_in(path, config) {
         if verbose {
-            eprintln!("Error: Not a directory: {}", path.display());
+            state.message(
+                MessageLevel::Verbose,
+                format!("Skipping ignored directory: {}", path.display()),
+            );
         }
         return Ok(());
     }
 
-    // Check if this directory should be ignored based on its name
-    if let Some(dir_name) = path.file_name() {
-        let dir_name_str = dir_name.to_string_lossy().to_string();
-        for pattern in ignore_patterns {
-            // Use glob pattern matching for ignore patterns
-            let glob_pattern = match Pattern::new(pattern) {
-                Ok(p) => p,
-                Err(_) => {
-                    if verbose {
-                        eprintln!(
-                            "Warning: Invalid ignore pattern '{}', using literal match",
-                            pattern
-                        );
+    // Skip if a rule matched on an ancestor limits the traversal depth below it
+    {
+        let limits = state.descend_limits.read().unwrap();
+        if !limits.is_empty() {
+            let depth = path.components().count();
+            for ancestor in path.ancestors().skip(1) {
+                if let Some(max_depth) = limits.get(ancestor) {
+                    if depth - ancestor.components().count() > *max_depth {
+                        if verbose {
+                            state.message(
+                                MessageLevel::Verbose,
+                                format!(
+                                    "Skipping directory beyond descend depth: {}",
+                                    path.display()
+                                ),
+                            );
+                        }
+                        return Ok(());
                     }
-                    Pattern::new(&glob::Pattern::escape(pattern)).unwrap()
                 }
-            };
+            }
+        }
+    }
 
-            if glob_pattern.matches(&dir_name_str) {
+    // Skip directories deeper than the `max_depth` of their root
+    if config.max_depth.is_some() || config.roots.iter().any(|root| root.max_depth.is_some()) {
+        if let Some((max_depth, depth)) = depth_below_root(path, config) {
+            if depth > max_depth {
                 if verbose {
-                    println!("Skipping ignored directory: {}", path.display());
+                    state.message(
+                        MessageLevel::Verbose,
+                        format!("Skipping directory beyond max depth: {}", path.display()),
+                    );
                 }
                 return Ok(());
             }
         }
     }
 
-    // Increment the processed_paths counter
+    // Symlinks may lead back to a scanned directory, or reach a directory twice
+    if config.follows_symlinks() {
+        let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if !state.visited_dirs.write().unwrap().insert(canonical) {
+            if verbose {
+                state.message(
+                    MessageLevel::Verbose,
+                    format!("Skipping directory already scanned: {}", path.display()),
+                );
+            }
+            return Ok(());
+        }
+    }
+
+    // Listing a directory whose content only lives in the cloud would download it
+    if crate::icloud::is_dataless_path(path) {
+        if verbose {
+            state.message(
+                MessageLevel::Verbose,
+                format!(
+                    "Skipping dataless directory (not downloaded): {}",
+                    path.display()
+                ),
+            );
+        }
+        *state.dataless.write().unwrap() += 1;
+        return Ok(());
+    }
+
+    // Increment the processed_paths counter, in total and for the root
     {
         let mut counter = state.processed_paths.write().unwrap();
         *counter += 1;
     }
+    if let Some((_, root_path)) = closest_root(path, config) {
+        let mut root_stats = state.root_stats.write().unwrap();
+        root_stats.entry(root_path).or_default().processed_paths += 1;
+    }
+    state.emit(|| Event::DirScanned {
+        path: path.to_path_buf(),
+    });
 
     if verbose {
-        println!("Processing path: {}", path.display());
+        state.message(
+            MessageLevel::Verbose,
+            format!("Processing path: {}", path.display()),
+        );
     }
 
     // Read all entries once
     let read_dir_iter = match fs::read_dir(path) {
         Ok(entries) => entries,
         Err(e) => {
-            eprintln!("Failed to read directory {}: {}", path.display(), e);
+            record_error(
+                &state,
+                config,
+                ScanError::from_io(path, &e),
+                verbose.then(|| format!("Failed to read directory {}: {}", path.display(), e)),
+            );
             return Ok(());
         }
     };
@@ -224,80 +1613,300 @@ pub fn process_path(
     for entry_result in read_dir_iter {
         match entry_result {
             Ok(entry) => entries.push(entry),
-            Err(err) => {
-                if verbose {
-                    eprintln!("Error accessing entry: {}", err);
-                }
-            }
+            Err(err) => record_error(
+                &state,
+                config,
+                ScanError::from_io(path, &err),
+                verbose.then(|| format!("Error accessing entry: {}", err)),
+            ),
+        }
+    }
+
+    // Opt-in: directories with a huge number of entries wreck scan times and are
+    // usually exclusion candidates themselves
+    let mut stop_descending = false;
+    if let Some(entry_limit) = &config.entry_limit {
+        if entries.len() > entry_limit.max_entries {
+            let id = if entry_limit.stop {
+                "scan-crowded-stop"
+            } else {
+                "scan-crowded-directory"
+            };
+            state.message(
+                MessageLevel::Warning,
+                tr(
+                    id,
+                    &[
+                        ("path", path.display().to_string().into()),
+                        ("count", entries.len().into()),
+                        ("max", entry_limit.max_entries.into()),
+                    ],
+                ),
+            );
+            *state.crowded.write().unwrap() += 1;
+            stop_descending = entry_limit.stop;
+        }
+    }
+
+    // A .nobackup marker excludes the whole folder, so there is no need to descend
+    let has_nobackup_marker = entries
+        .iter()
+        .any(|entry| entry.file_name() == NOBACKUP_MARKER && entry.path().is_file());
+    if has_nobackup_marker {
+        let marker_rule = Rule {
+            name: "nobackup".to_string(),
+            file_match: NOBACKUP_MARKER.to_string(),
+            exclusions: vec![".".into()],
+            ..Default::default()
+        };
+        if verbose {
+            state.message(
+                MessageLevel::Verbose,
+                format!("Found {} marker at: {}", NOBACKUP_MARKER, path.display()),
+            );
+        }
+        if !process_exclusion(path, &marker_rule, config, &state, verbose).is_empty() {
+            return Ok(());
         }
     }
 
     // Phase 1: evaluate rule matches and compute directories to ignore
-    let mut directory_to_ignore: Vec<String> = Vec::new();
+    let mut directory_to_ignore: Vec<PathBuf> = Vec::new();
+
+    // Recursive rules matched on an ancestor apply their exclusions here too, as the
+    // traversal reaches each subdirectory of the matched folder
+    let inherited_rules: Vec<Rule> = {
+        let recursive_rules = state.recursive_rules.read().unwrap();
+        if recursive_rules.is_empty() {
+            Vec::new()
+        } else {
+            path.ancestors()
+                .skip(1)
+                .filter_map(|ancestor| recursive_rules.get(ancestor))
+                .flatten()
+                .cloned()
+                .collect()
+        }
+    };
+    for rule in &inherited_rules {
+        directory_to_ignore.extend(process_exclusion(path, rule, config, &state, verbose));
+    }
+
+    let mut any_rule_matched = false;
     for entry in &entries {
         let entry_path = entry.path();
-        let file_name_lc = entry_path
-            .file_name()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_lowercase();
 
-        for rule in rules {
-            let pattern = match Pattern::new(&rule.file_match.to_lowercase()) {
-                Ok(p) => p,
-                Err(_) => {
-                    if verbose {
-                        eprintln!(
-                            "Warning: Invalid pattern '{}' in rule '{}', using literal match",
-                            rule.file_match, rule.name
-                        );
-                    }
-                    Pattern::new(&glob::Pattern::escape(&rule.file_match.to_lowercase())).unwrap()
-                }
-            };
+        for rule in &config.rules {
+            if !rule_applies_to(path, rule) {
+                continue;
+            }
 
-            if pattern.matches(&file_name_lc) {
+            if file_match_matches(rule, &entry_path) && requirements_met(path, rule) {
                 if verbose {
-                    println!(
-                        "Found match for rule '{}' at: {}",
-                        rule.name,
-                        entry_path.display()
+                    state.message(
+                        MessageLevel::Verbose,
+                        format!(
+                            "Found match for rule '{}' at: {}",
+                            rule.name,
+                            entry_path.display()
+                        ),
                     );
                 }
-                process_exclusion(path, rule, &state, verbose);
-
-                // If special entries are present, do not descend further from current folder
-                if rule
-                    .exclusions
-                    .contains(THIS_FOLDER.get_or_init(|| ".".to_string()))
-                    || rule
-                        .exclusions
-                        .contains(PARENT_FOLDER.get_or_init(|| "..".to_string()))
+                any_rule_matched = true;
                 {
-                    return Ok(());
+                    let mut rule_stats = state.rule_stats.write().unwrap();
+                    rule_stats.entry(rule.name.clone()).or_default().matches += 1;
+                }
+                state.emit(|| Event::RuleMatched {
+                    rule: rule.name.clone(),
+                    folder: path.to_path_buf(),
+                    entry: entry_path.clone(),
+                });
+                // Without exclusions, the rule targets the matched entry itself (e.g. `*.iso`)
+                let entry_rule;
+                let rule = if rule.exclusions.is_empty() {
+                    entry_rule = Rule {
+                        exclusions: vec![glob::Pattern::escape(
+                            &entry_path.file_name().unwrap_or_default().to_string_lossy(),
+                        )
+                        .into()],
+                        ..rule.clone()
+                    };
+                    &entry_rule
+                } else {
+                    rule
+                };
+                let resolved = process_exclusion(path, rule, config, &state, verbose);
+                if rule.recursive {
+                    state
+                        .recursive_rules
+                        .write()
+                        .unwrap()
+                        .entry(path.to_path_buf())
+                        .or_default()
+                        .push(rule.clone());
+                }
+
+                match rule.descend_behavior() {
+                    // The subdirectories of a recursive rule are reached by the traversal
+                    Descend::Stop if !rule.recursive => return Ok(()),
+                    Descend::Stop | Descend::Continue => {}
+                    Descend::Depth(max_depth) => {
+                        let mut limits = state.descend_limits.write().unwrap();
+                        let limit = limits.entry(path.to_path_buf()).or_insert(max_depth);
+                        *limit = (*limit).min(max_depth);
+                    }
+                }
+
+                directory_to_ignore.extend(resolved);
+
+                // In first-match mode there is no need to check other rules for this same entry
+                if config.match_mode == MatchMode::First {
+                    break;
                 }
+            }
+        }
+    }
 
-                for exclusion in &rule.exclusions {
-                    directory_to_ignore.push(exclusion.clone());
+    // Opt-in: large directories ignored by git in a repository root are candidates too
+    if let Some(gitignore_config) = &config.gitignore {
+        let min_size = gitignore_config.min_size_mb * 1024 * 1024;
+        let candidates: Vec<(PathBuf, u64)> = crate::gitignore::ignored_directories(path)
+            .into_iter()
+            .filter(|candidate| !directory_to_ignore.contains(candidate))
+            .map(|candidate| {
+                let size = crate::size::directory_size(&candidate);
+                (candidate, size)
+            })
+            .filter(|(_, size)| *size >= min_size)
+            .collect();
+
+        if !candidates.is_empty() {
+            match gitignore_config.mode {
+                GitignoreMode::Propose => {
+                    for (candidate, size) in &candidates {
+                        state.message(
+                            MessageLevel::Notice,
+                            tr(
+                                "scan-gitignore-candidate",
+                                &[
+                                    ("path", candidate.display().to_string().into()),
+                                    ("size", crate::size::format_size(*size).into()),
+                                ],
+                            ),
+                        );
+                    }
                 }
+                GitignoreMode::Exclude => {
+                    let gitignore_rule = Rule {
+                        name: "gitignore".to_string(),
+                        file_match: ".gitignore".to_string(),
+                        exclusions: candidates
+                            .iter()
+                            .filter_map(|(candidate, _)| candidate.file_name())
+                            .map(|name| glob::Pattern::escape(&name.to_string_lossy()).into())
+                            .collect(),
+                        ..Default::default()
+                    };
+                    let resolved =
+                        process_exclusion(path, &gitignore_rule, config, &state, verbose);
+                    directory_to_ignore.extend(resolved);
+                }
+            }
+        }
+    }
+
+    // Opt-in: build outputs in a folder that no rule matched lost their project marker.
+    // They are reported but not traversed, as their contents would be reported too
+    if let Some(orphans_config) = config.orphans.as_ref().filter(|_| !any_rule_matched) {
+        for entry in &entries {
+            let entry_path = entry.path();
+            if directory_to_ignore.contains(&entry_path)
+                || is_ignored_in(&entry_path, config)
+                || is_kept(&entry_path, &config.keep)
+            {
+                continue;
+            }
+            if let Some(size) = crate::heuristics::evaluate_orphan(&entry_path, orphans_config) {
+                state.message(
+                    MessageLevel::Notice,
+                    tr(
+                        "scan-orphan",
+                        &[
+                            ("path", entry_path.display().to_string().into()),
+                            ("size", crate::size::format_size(size).into()),
+                        ],
+                    ),
+                );
+                *state.orphans.write().unwrap() += 1;
+                directory_to_ignore.push(entry_path);
+            }
+        }
+    }
 
-                break; // no need to check other rules for this same entry
+    // Opt-in: report cache-like directories that no rule matched
+    if let Some(heuristics_config) = &config.heuristics {
+        let inside_candidate = {
+            let candidates = state.heuristic_candidates.read().unwrap();
+            path.ancestors()
+                .any(|ancestor| candidates.contains(ancestor))
+        };
+        if !inside_candidate {
+            for entry in &entries {
+                let entry_path = entry.path();
+                if directory_to_ignore.contains(&entry_path)
+                    || is_ignored_in(&entry_path, config)
+                    || is_kept(&entry_path, &config.keep)
+                {
+                    continue;
+                }
+                if let Some((stats, reasons)) =
+                    crate::heuristics::evaluate(&entry_path, heuristics_config)
+                {
+                    state.message(
+                        MessageLevel::Notice,
+                        tr(
+                            "scan-cache-candidate",
+                            &[
+                                ("path", entry_path.display().to_string().into()),
+                                ("size", crate::size::format_size(stats.size).into()),
+                                ("reasons", reasons.join(", ").into()),
+                            ],
+                        ),
+                    );
+                    state
+                        .heuristic_candidates
+                        .write()
+                        .unwrap()
+                        .insert(entry_path);
+                    *state.candidates.write().unwrap() += 1;
+                }
             }
         }
     }
 
-    // Phase 2: enqueue subdirectories excluding those we just excluded
-    if !entries.is_empty() {
+    // Phase 2: enqueue subdirectories excluding those we just excluded, as well as
+    // those excluded by a nested exclusion entry (e.g. `target/debug`) of an ancestor
+    if !entries.is_empty() && !stop_descending {
+        let seen = state.seen_exclusion_paths.read().unwrap();
         let mut queue = state.folder_queue.write().unwrap();
         for entry in entries {
             let entry_path = entry.path();
             if entry_path.is_dir() {
-                let name = entry_path
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string();
-                if directory_to_ignore.iter().any(|n| n == &name) {
+                // Directories moved out of iCloud Drive are reached through their symlink
+                if directory_to_ignore.contains(&entry_path)
+                    || seen.contains(&entry_path.display().to_string())
+                    || crate::icloud::is_nosync_target(&entry_path)
+                {
+                    continue;
+                }
+                if !config.follows_symlinks() && entry.file_type().is_ok_and(|t| t.is_symlink()) {
+                    if verbose {
+                        state.message(
+                            MessageLevel::Verbose,
+                            format!("Skipping symlinked directory: {}", entry_path.display()),
+                        );
+                    }
                     continue;
                 }
 
@@ -311,16 +1920,14 @@ pub fn process_path(
 
 pub fn run_workers(
     state: Arc<State>,
-    rules: Arc<Vec<Rule>>,
+    config: Arc<Config>,
     thread_count: usize,
     verbose: bool,
-    ignore_patterns: Arc<Vec<String>>,
 ) -> Result<()> {
     // Spawn worker threads to process the queue
-    for _ in 0..thread_count {
+    for worker in 0..thread_count {
         let state_clone = Arc::clone(&state);
-        let rules_clone = Arc::clone(&rules);
-        let ignore_patterns_clone = Arc::clone(&ignore_patterns);
+        let config_clone = Arc::clone(&config);
         let verbose_clone = verbose;
 
         thread::spawn(move || {
@@ -338,23 +1945,37 @@ pub fn run_workers(
                         let mut active = state_clone.active_tasks.write().unwrap();
                         *active += 1;
 
-                        Some(queue.remove(0))
+                        queue.pop()
                     } else {
                         None
                     }
                 };
 
-                if let Some(next_path) = next_path_option {
+                if let Some((root, next_path)) = next_path_option {
+                    state_clone
+                        .worker_paths
+                        .write()
+                        .unwrap()
+                        .insert(worker, (next_path.clone(), Instant::now()));
+
                     // Process the path
                     if let Err(e) = process_path(
                         &next_path,
                         Arc::clone(&state_clone),
-                        &rules_clone,
+                        &config_clone,
                         verbose_clone,
-                        &ignore_patterns_clone,
                     ) {
-                        eprintln!("Error processing path {}: {}", next_path.display(), e);
+                        record_error(
+                            &state_clone,
+                            &config_clone,
+                            ScanError::new(ErrorKind::Other, &next_path, e.to_string()),
+                            verbose_clone.then(|| {
+                                format!("Error processing path {}: {}", next_path.display(), e)
+                            }),
+                        );
                     }
+                    state_clone.worker_paths.write().unwrap().remove(&worker);
+                    state_clone.folder_queue.write().unwrap().done(&root);
 
                     // Decrement active tasks counter
                     let mut active = state_clone.active_tasks.write().unwrap();
@@ -390,17 +2011,58 @@ pub fn run_workers(
     Ok(())
 }
 
-/// Lists the exclusion status of files and directories in a given path
-pub fn list_exclusions(path_str: Option<&str>) -> Result<()> {
+/// Returns the sizes of the listed entries through the size cache, which is then saved
+fn entry_sizes(
+    paths: &[PathBuf],
+    mode: crate::size::SizeMode,
+    refresh: bool,
+    thread_count: usize,
+) -> Vec<u64> {
+    let mut cache = crate::size::SizeCache::load_default();
+    let max_age = if refresh {
+        std::time::Duration::ZERO
+    } else {
+        crate::size::SIZE_CACHE_MAX_AGE
+    };
+    let sizes = cache.sizes(paths, thread_count, mode, max_age);
+    if let Err(e) = cache.save() {
+        eprintln!(
+            "{}",
+            tr("size-cache-save-failed", &[("error", e.to_string().into())])
+        );
+    }
+    sizes
+}
+
+/// Right-aligned size column of the listings, empty when sizes are not shown
+fn size_column(size: Option<u64>) -> String {
+    size.map(|size| format!("{:>10}  ", crate::size::format_size(size)))
+        .unwrap_or_default()
+}
+
+/// Lists the exclusion status of files and directories in a given path. With `sizes`, the
+/// size of each entry (measured as `sizes` says) is shown too, largest first, computed with
+/// `thread_count` threads and cached between runs unless `refresh` is set. Directory
+/// listings are limited to the requested page.
+pub fn list_exclusions(
+    path_str: Option<&str>,
+    sizes: Option<crate::size::SizeMode>,
+    refresh: bool,
+    thread_count: usize,
+    pagination: &crate::pagination::Pagination,
+) -> crate::error::Result<()> {
     // If no path is provided, use the current directory
     let path = if let Some(p) = path_str {
         crate::config::expand_tilde(p)?
     } else {
-        std::env::current_dir()?
+        std::env::current_dir().map_err(|source| crate::error::Error::Io {
+            path: PathBuf::from("."),
+            source,
+        })?
     };
 
     if !path.exists() {
-        return Err(anyhow::anyhow!("Path does not exist: {}", path.display()));
+        return Err(not_found_error(&path, &path.display().to_string()));
     }
 
     // Check if we're listing a directory with all contents or just a single file/directory
@@ -408,58 +2070,106 @@ pub fn list_exclusions(path_str: Option<&str>) -> Result<()> {
 
     if is_directory_listing {
         // List all entries in the directory
-        println!("Listing contents of: {}", path.display());
+        println!(
+            "{}",
+            tr(
+                "list-contents",
+                &[("path", path.display().to_string().into())]
+            )
+        );
         println!("------------------------------------");
 
-        let entries = match fs::read_dir(&path) {
-            Ok(entries) => entries,
-            Err(e) => return Err(anyhow::anyhow!("Failed to read directory: {}", e)),
-        };
+        let entries = fs::read_dir(&path).map_err(|source| crate::error::Error::Io {
+            path: path.clone(),
+            source,
+        })?;
 
+        let backend = default_backend();
+        let skip_paths = fixed_path_exclusions();
         let mut has_entries = false;
+        let mut entry_paths = Vec::new();
         for entry_result in entries {
             has_entries = true;
-            let entry = match entry_result {
-                Ok(entry) => entry,
-                Err(e) => {
-                    eprintln!("Error accessing entry: {}", e);
-                    continue;
-                }
-            };
+            match entry_result {
+                Ok(entry) => entry_paths.push(entry.path()),
+                Err(e) => eprintln!(
+                    "{}",
+                    tr("list-entry-error", &[("error", e.to_string().into())])
+                ),
+            }
+        }
+        // Pages need a stable order
+        if pagination.max_results.is_some() {
+            entry_paths.sort();
+        }
 
-            let entry_path = entry.path();
-            let is_excluded = is_excluded_from_timemachine(&entry_path);
+        let entry_sizes = sizes.map(|mode| entry_sizes(&entry_paths, mode, refresh, thread_count));
+        let mut listed: Vec<(PathBuf, Option<u64>)> = entry_paths
+            .into_iter()
+            .enumerate()
+            .map(|(i, path)| (path, entry_sizes.as_ref().map(|sizes| sizes[i])))
+            .collect();
+        if sizes.is_some() {
+            listed.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        }
+
+        for (entry_path, size) in pagination.apply(&listed) {
+            let is_fixed = skip_paths.contains(entry_path);
+            let is_excluded = is_fixed || backend.is_excluded(entry_path);
 
             // Format the output with appropriate indicators
             let indicator = if is_excluded { "🟡" } else { "  " };
             let type_indicator = if entry_path.is_dir() { "/" } else { "" };
+            let fixed_indicator = if is_fixed {
+                format!(" {}", tr("list-fixed-path", &[]))
+            } else {
+                String::new()
+            };
 
             println!(
-                "{} {}{}",
+                "{} {}{}{}{}",
                 indicator,
+                size_column(*size),
                 entry_path.file_name().unwrap_or_default().to_string_lossy(),
-                type_indicator
+                type_indicator,
+                fixed_indicator
             );
         }
 
         if !has_entries {
-            println!("  (empty directory)");
+            println!("  {}", tr("list-empty", &[]));
+        }
+        if let Some(footer) = pagination.footer(listed.len()) {
+            println!("\n{}", footer);
         }
 
         // Add a legend
-        println!("\nLegend:");
-        println!("🟡 - Excluded from Time Machine");
-        println!("  - Included in Time Machine");
-        println!("/ - Directory");
+        println!("\n{}", tr("list-legend", &[]));
+        println!("{}", tr("list-legend-excluded", &[]));
+        println!("  {}", tr("list-legend-included", &[]));
+        println!("{}", tr("list-legend-directory", &[]));
+        println!("{}", tr("list-legend-fixed-path", &[]));
     } else {
         // Just check the status of the specific path but format it like the directory listing
-        let item_type = if path.is_dir() { "directory" } else { "file" };
-        println!("Status of {}: {}", item_type, path.display());
+        let id = if path.is_dir() {
+            "list-status-directory"
+        } else {
+            "list-status-file"
+        };
+        println!("{}", tr(id, &[("path", path.display().to_string().into())]));
         println!("------------------------------------");
 
-        let is_excluded = is_excluded_from_timemachine(&path);
+        let is_fixed = fixed_path_exclusions().contains(&path);
+        let is_excluded = is_fixed || default_backend().is_excluded(&path);
         let indicator = if is_excluded { "🟡" } else { "  " };
         let type_indicator = if path.is_dir() { "/" } else { "" };
+        let fixed_indicator = if is_fixed {
+            format!(" {}", tr("list-fixed-path", &[]))
+        } else {
+            String::new()
+        };
+        let size = sizes
+            .map(|mode| entry_sizes(std::slice::from_ref(&path), mode, refresh, thread_count)[0]);
 
         // Use the filename if available, otherwise use the full path
         let display_name = path
@@ -467,77 +2177,227 @@ pub fn list_exclusions(path_str: Option<&str>) -> Result<()> {
             .map(|name| name.to_string_lossy().to_string())
             .unwrap_or_else(|| path.display().to_string());
 
-        println!("{} {}{}", indicator, display_name, type_indicator);
+        println!(
+            "{} {}{}{}{}",
+            indicator,
+            size_column(size),
+            display_name,
+            type_indicator,
+            fixed_indicator
+        );
 
         // Add a legend
-        println!("\nLegend:");
-        println!("🟡 - Excluded from Time Machine");
-        println!("  - Included in Time Machine");
+        println!("\n{}", tr("list-legend", &[]));
+        println!("{}", tr("list-legend-excluded", &[]));
+        println!("  {}", tr("list-legend-included", &[]));
         if path.is_dir() {
-            println!("/ - Directory");
+            println!("{}", tr("list-legend-directory", &[]));
+        }
+        if is_fixed {
+            println!("{}", tr("list-legend-fixed-path", &[]));
         }
     }
 
     Ok(())
 }
 
-/// Explicitly excludes a single file or folder from Time Machine backups
-pub fn exclude_path(path_str: &str, verbose: bool) -> Result<()> {
-    // Expand the path if it contains a tilde
-    let path = crate::config::expand_tilde(path_str)?;
+/// Error returned when fixed-path exclusions, stored in the Time Machine preferences, are
+/// requested without root privileges, naming the rules creating them, if any
+pub(crate) fn elevation_required_error(fixed_rules: &[&str]) -> crate::error::Error {
+    let message = if fixed_rules.is_empty() {
+        tr("elevation-required-fixed-paths", &[])
+    } else {
+        tr(
+            "elevation-required-fixed-rules",
+            &[("rules", fixed_rules.join(", ").into())],
+        )
+    };
+    crate::error::Error::Backend {
+        path: PathBuf::from(TIME_MACHINE_PREFERENCES),
+        kind: BackendErrorKind::ElevationRequired,
+        message,
+    }
+}
+
+/// Splits a list of paths read from stdin: NUL delimited when it holds a NUL character
+/// (e.g. `find -print0`), newline delimited otherwise. Empty entries are skipped.
+pub fn parse_path_list(input: &str) -> Vec<String> {
+    let delimiter = if input.contains('\0') { '\0' } else { '\n' };
+    input
+        .split(delimiter)
+        .map(|entry| entry.strip_suffix('\r').unwrap_or(entry))
+        .filter(|entry| !entry.trim().is_empty())
+        .map(String::from)
+        .collect()
+}
 
-    if !path.exists() {
-        return Err(anyhow::anyhow!("Path does not exist: {}", path.display()));
+/// Error for paths that do not exist, `path` being the first of the `names` listed
+fn not_found_error(path: &Path, names: &str) -> crate::error::Error {
+    crate::error::Error::Io {
+        path: path.to_path_buf(),
+        source: std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Path does not exist: {}", names),
+        ),
     }
+}
 
-    let item_type = if path.is_dir() { "directory" } else { "file" };
+/// Expands the paths of the `exclude` and `include` commands: `~` and glob patterns
+/// (e.g. `~/Development/*/node_modules`, quoted so asimeow expands them rather than the
+/// shell), failing before any change if a path does not exist or a pattern matches nothing
+pub fn expand_path_args(path_strs: &[String]) -> crate::error::Result<Vec<PathBuf>> {
+    if path_strs.is_empty() {
+        return Err(anyhow::anyhow!("No path given").into());
+    }
+    let mut paths = Vec::new();
+    let mut missing: Vec<(PathBuf, String)> = Vec::new();
+    for path_str in path_strs {
+        // Expand the path if it contains a tilde
+        let path = crate::config::expand_tilde(path_str)?;
+        if path.exists() {
+            paths.push(path);
+            continue;
+        }
 
-    if verbose {
-        println!(
-            "Excluding {} from Time Machine: {}",
-            item_type,
-            path.display()
-        );
+        let pattern = path.to_string_lossy();
+        if !pattern.contains(['*', '?', '[']) {
+            missing.push((path.clone(), path.display().to_string()));
+            continue;
+        }
+        let mut matches: Vec<PathBuf> = glob::glob(&pattern)
+            .map_err(|e| anyhow::anyhow!("Invalid pattern {}: {}", pattern, e))?
+            .filter_map(|entry| entry.ok())
+            .collect();
+        if matches.is_empty() {
+            missing.push((path.clone(), format!("{} (no match)", pattern)));
+        }
+        matches.sort();
+        paths.extend(matches);
+    }
+    if let Some((first, _)) = missing.first() {
+        let names: Vec<&str> = missing.iter().map(|(_, name)| name.as_str()).collect();
+        return Err(not_found_error(first, &names.join(", ")));
+    }
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Argument of the messages about a single path
+fn path_arg(path: &Path) -> [(&'static str, fluent_bundle::FluentValue<'static>); 1] {
+    [("path", path.display().to_string().into())]
+}
+
+/// Explicitly excludes files or folders from Time Machine backups, with a single `tmutil`
+/// call for the paths not excluded yet
+pub fn exclude_paths(
+    path_strs: &[String],
+    exclusion_type: ExclusionType,
+    privilege_helper: Option<String>,
+    verbose: bool,
+) -> crate::error::Result<()> {
+    let paths = expand_path_args(path_strs)?;
+
+    let backend = backend_with_privilege_helper(privilege_helper);
+    if backend.requires_elevation(exclusion_type) {
+        return Err(elevation_required_error(&[]));
     }
 
-    let excluded = exclude_from_timemachine(&path);
+    let (already_excluded, pending): (Vec<PathBuf>, Vec<PathBuf>) = paths
+        .into_iter()
+        .partition(|path| backend.is_excluded(path));
+    if backend.reports_only() {
+        for path in &pending {
+            println!("{}", tr("exclude-would", &path_arg(path)));
+        }
+        return Ok(());
+    }
+    if verbose {
+        for path in &pending {
+            let item_type = if path.is_dir() { "directory" } else { "file" };
+            println!(
+                "Excluding {} from Time Machine: {}",
+                item_type,
+                path.display()
+            );
+        }
+    }
+    let all_added = pending.is_empty() || backend.add_all(&pending, exclusion_type);
 
-    if excluded {
-        println!("✅ Successfully excluded: {}", path.display());
-    } else {
-        println!("🟡 Already excluded: {}", path.display());
+    for path in &already_excluded {
+        println!("{}", tr("exclude-already", &path_arg(path)));
+    }
+    let mut failed = Vec::new();
+    for path in &pending {
+        // A failed batch may still have excluded some of the paths
+        if all_added || backend.is_excluded(path) {
+            println!("{}", tr("exclude-done", &path_arg(path)));
+        } else {
+            eprintln!("{}", tr("exclude-failed", &path_arg(path)));
+            failed.push(path);
+        }
     }
 
+    if let Some(first) = failed.first() {
+        return Err(crate::error::Error::Backend {
+            path: first.to_path_buf(),
+            kind: BackendErrorKind::ExclusionFailed,
+            message: tr("exclude-failed-count", &[("count", failed.len().into())]),
+        });
+    }
     Ok(())
 }
 
-/// Explicitly includes a single file or folder in Time Machine backups (removes exclusion)
-pub fn include_path(path_str: &str, verbose: bool) -> Result<()> {
-    // Expand the path if it contains a tilde
-    let path = crate::config::expand_tilde(path_str)?;
+/// Explicitly includes files or folders in Time Machine backups (removes exclusions), with
+/// a single `tmutil` call for the paths still excluded
+pub fn include_paths(
+    path_strs: &[String],
+    exclusion_type: ExclusionType,
+    privilege_helper: Option<String>,
+    verbose: bool,
+) -> crate::error::Result<()> {
+    let paths = expand_path_args(path_strs)?;
 
-    if !path.exists() {
-        return Err(anyhow::anyhow!("Path does not exist: {}", path.display()));
+    let backend = backend_with_privilege_helper(privilege_helper);
+    if backend.requires_elevation(exclusion_type) {
+        return Err(elevation_required_error(&[]));
     }
 
-    let item_type = if path.is_dir() { "directory" } else { "file" };
-
+    let (pending, already_included): (Vec<PathBuf>, Vec<PathBuf>) = paths
+        .into_iter()
+        .partition(|path| backend.is_excluded(path));
     if verbose {
-        println!(
-            "Including {} in Time Machine: {}",
-            item_type,
-            path.display()
-        );
+        for path in &pending {
+            let item_type = if path.is_dir() { "directory" } else { "file" };
+            println!(
+                "Including {} in Time Machine: {}",
+                item_type,
+                path.display()
+            );
+        }
     }
+    let all_removed = pending.is_empty() || backend.remove_all(&pending, exclusion_type);
 
-    let included = include_in_timemachine(&path);
-
-    if included {
-        println!("✅ Successfully included: {}", path.display());
-    } else {
-        println!("  Already included: {}", path.display());
+    for path in &already_included {
+        println!("  {}", tr("include-already", &path_arg(path)));
+    }
+    let mut failed = Vec::new();
+    for path in &pending {
+        // A failed batch may still have included some of the paths
+        if all_removed || !backend.is_excluded(path) {
+            println!("{}", tr("include-done", &path_arg(path)));
+        } else {
+            eprintln!("{}", tr("include-failed", &path_arg(path)));
+            failed.push(path);
+        }
     }
 
+    if let Some(first) = failed.first() {
+        return Err(crate::error::Error::Backend {
+            path: first.to_path_buf(),
+            kind: BackendErrorKind::ExclusionFailed,
+            message: tr("include-failed-count", &[("count", failed.len().into())]),
+        });
+    }
     Ok(())
 }
 
@@ -545,15 +2405,101 @@ pub fn run_explorer(
     config: crate::config::Config,
     thread_count: usize,
     verbose: bool,
-) -> Result<()> {
-    let _ = run_explorer_with_stats(config, thread_count, verbose)?;
-    Ok(())
+) -> crate::error::Result<ExplorerStats> {
+    run_explorer_with_stats(config, thread_count, verbose)
+}
+
+/// Runs the rules against `roots` instead of the configured ones, in report mode: nothing
+/// is excluded and the configured `exclude_paths` are ignored
+pub fn run_simulation(
+    mut config: crate::config::Config,
+    roots: &[String],
+    thread_count: usize,
+    verbose: bool,
+) -> crate::error::Result<ExplorerStats> {
+    config.dry_run = true;
+    run_on_paths(config, roots, thread_count, verbose)
+}
+
+/// Applies the configured rules recursively under the given paths instead of the
+/// configured roots (e.g. a repository cloned outside of them). The `exclude_paths`
+/// entries are left alone.
+pub fn run_on_paths(
+    mut config: crate::config::Config,
+    paths: &[String],
+    thread_count: usize,
+    verbose: bool,
+) -> crate::error::Result<ExplorerStats> {
+    config.roots = paths
+        .iter()
+        .map(|path| crate::config::Root {
+            path: path.clone(),
+            ..Default::default()
+        })
+        .collect();
+    config.exclude_paths.clear();
+    run_explorer_with_stats(config, thread_count, verbose)
 }
 
+/// Counters of a scan, returned by every run entry point. Serialized as the summary of
+/// the JSON formats
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct ExplorerStats {
     pub processed_paths: i32,
     pub exclusions_found: i32,
     pub newly_excluded: i32,
+    pub reported: i32,
+    pub warnings: i32,
+    pub candidates: i32,
+    pub crowded: i32,
+    pub orphans: i32,
+    /// Exclusions a cloud service keeps uploading, see `cloud::sync_warning`
+    pub cloud_synced: i32,
+    /// Dataless directories left unscanned, as listing them would download them
+    pub dataless: i32,
+    /// Errors met while scanning, see `State::errors`
+    #[serde(rename = "error_count")]
+    pub errors: i32,
+    /// Errors by kind
+    pub errors_by_kind: BTreeMap<ErrorKind, i32>,
+    /// Counters of each enabled rule, by rule name
+    pub rules: BTreeMap<String, RuleStats>,
+    /// Counters of each root, by expanded root path
+    pub roots: BTreeMap<PathBuf, RootStats>,
+    /// Time taken by the scan, serialized in seconds
+    #[serde(rename = "duration_secs", serialize_with = "serialize_secs")]
+    pub duration: Duration,
+    /// Total size of the exclusions found in bytes, when `Config::size_mode` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+}
+
+/// Counters of a rule during a scan, telling the rules doing the work from the dead weight
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RuleStats {
+    /// Entries the rule matched
+    pub matches: i32,
+    /// Exclusions resolved from its matches, see `ExplorerStats::exclusions_found`
+    pub exclusions_found: i32,
+    pub newly_excluded: i32,
+}
+
+/// Counters of a root during a scan
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RootStats {
+    pub processed_paths: i32,
+    pub exclusions_found: i32,
+    /// Time taken by the root, scanned side by side with the others (see
+    /// `queue::FolderQueue`), serialized in seconds
+    #[serde(rename = "duration_secs", serialize_with = "serialize_secs")]
+    pub duration: Duration,
+}
+
+fn serialize_secs<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
 }
 
 /// Same as run_explorer but returns stats for testing/inspection
@@ -561,40 +2507,297 @@ pub fn run_explorer_with_stats(
     config: crate::config::Config,
     thread_count: usize,
     verbose: bool,
-) -> Result<ExplorerStats> {
+) -> crate::error::Result<ExplorerStats> {
+    let (stats, _) = run_explorer_with_state(config, thread_count, verbose)?;
+    Ok(stats)
+}
+
+/// Same as run_explorer_with_stats but also returns the final scan state
+pub fn run_explorer_with_state(
+    config: crate::config::Config,
+    thread_count: usize,
+    verbose: bool,
+) -> crate::error::Result<(ExplorerStats, Arc<State>)> {
+    let backend = backend_with_privilege_helper(config.privilege_helper.clone());
+    run_explorer_with_backend(config, backend, thread_count, verbose)
+}
+
+/// Same as run_explorer_with_state, applying the exclusions with the given backend
+pub fn run_explorer_with_backend(
+    config: crate::config::Config,
+    backend: Arc<dyn BackupExclusionBackend>,
+    thread_count: usize,
+    verbose: bool,
+) -> crate::error::Result<(ExplorerStats, Arc<State>)> {
+    let mut state = State::with_backend(backend);
+    state.reporter = default_reporter(&config, verbose);
+    run_scan(config, state, thread_count, verbose)
+}
+
+/// Returns the reporter of the output format of the config: JSON, or human-readable
+/// without the per-path lines under the dashboard and without output on stdout when quiet
+pub fn default_reporter(config: &Config, verbose: bool) -> Arc<dyn Reporter> {
+    if config.format != OutputFormat::Text {
+        return Arc::new(JsonReporter {
+            format: config.format,
+        });
+    }
+    let watch_progress = config.watch_progress && std::io::stderr().is_terminal();
+    Arc::new(HumanReporter {
+        paths: !config.quiet && !watch_progress,
+        summary: !config.quiet,
+        ..HumanReporter::new(verbose)
+    })
+}
+
+/// Scans the configured roots with a fresh `state`
+fn run_scan(
+    mut config: crate::config::Config,
+    state: State,
+    thread_count: usize,
+    verbose: bool,
+) -> crate::error::Result<(ExplorerStats, Arc<State>)> {
+    let started = Instant::now();
+
     // Create shared state
-    let state = Arc::new(State::new());
+    let state = Arc::new(state);
 
     // Process each root path and add to initial queue
+    let mut root_paths = Vec::new();
     for root in &config.roots {
         let expanded_path = crate::config::expand_tilde(&root.path)?;
 
         // Add root paths to the queue
         let mut queue = state.folder_queue.write().unwrap();
-        queue.push(expanded_path);
+        queue.add_root(expanded_path.clone());
+        state
+            .root_stats
+            .write()
+            .unwrap()
+            .entry(expanded_path.clone())
+            .or_default();
+        root_paths.push(expanded_path);
+    }
+
+    // Warn about protected paths that are currently excluded
+    for protected_path in find_excluded_protected_paths(&config.protected, state.backend.as_ref()) {
+        state.message(
+            MessageLevel::Warning,
+            tr(
+                "scan-protected-excluded",
+                &[("path", protected_path.display().to_string().into())],
+            ),
+        );
+    }
+
+    // Drop disabled rules and evaluate higher priority rules first
+    config.rules.retain(|rule| rule.is_enabled());
+    config.sort_rules_by_priority();
+    if verbose {
+        warn_invalid_patterns(&state, &config);
+    }
+
+    // Every rule is listed in the stats, including those that never match
+    {
+        let mut rule_stats = state.rule_stats.write().unwrap();
+        for rule in &config.rules {
+            rule_stats.entry(rule.name.clone()).or_default();
+        }
+    }
+
+    // Fail before scanning when exclusions cannot be applied at all
+    if !config.dry_run {
+        state.backend.check_available()?;
+        if state.backend.reports_only() && !config.quiet {
+            state.message(MessageLevel::Warning, tr("scan-report-only-backend", &[]));
+        }
+    }
+
+    // Fail before scanning rather than on each fixed-path exclusion
+    let fixed_rules: Vec<&str> = config
+        .rules
+        .iter()
+        .filter(|rule| {
+            rule.action == RuleAction::Exclude
+                && rule.exclusion_type == ExclusionType::Fixed
+                && rule.backends().contains(&Backend::TimeMachine)
+        })
+        .map(|rule| rule.name.as_str())
+        .collect();
+    if !config.dry_run
+        && !fixed_rules.is_empty()
+        && state.backend.requires_elevation(ExclusionType::Fixed)
+    {
+        return Err(elevation_required_error(&fixed_rules));
     }
 
-    // Create Arc-wrapped rules and ignore patterns for sharing
-    let rules = Arc::new(config.rules);
-    let ignore_patterns = Arc::new(config.ignore);
+    // The dashboard and the JSON formats replace the rest of the per-path output
+    let watch_progress = config.watch_progress && std::io::stderr().is_terminal();
+    if watch_progress || config.format != OutputFormat::Text {
+        config.quiet = true;
+    }
+
+    // Create Arc-wrapped config for sharing
+    let config = Arc::new(config);
+
+    // Fixed paths are excluded up front; marking them as seen also prunes them from the scan
+    exclude_fixed_paths(&config, &state, verbose);
 
     // Run worker threads
-    run_workers(state.clone(), rules, thread_count, verbose, ignore_patterns)?;
+    let dashboard = watch_progress.then(|| {
+        let state = state.clone();
+        thread::spawn(move || crate::progress::watch(&state, thread_count))
+    });
+    let size_mode = config.size_mode;
+    run_workers(state.clone(), config, thread_count, verbose)?;
+    if let Some(dashboard) = dashboard {
+        let _ = dashboard.join();
+    }
 
     // Gather stats
     let exclusions_count = *state.exclusion_found.read().unwrap();
     let processed_count = *state.processed_paths.read().unwrap();
     let newly_excluded_count = *state.newly_excluded.read().unwrap();
-
-    if verbose || exclusions_count > 0 {
-        println!("\nTotal paths processed: {}", processed_count);
-        println!("Total exclusions found: {}", exclusions_count);
-        println!("Newly excluded from Time Machine: {}", newly_excluded_count);
+    let reported_count = *state.reported.read().unwrap();
+    let warnings_count = *state.warnings.read().unwrap();
+    let candidates_count = *state.candidates.read().unwrap();
+    let crowded_count = *state.crowded.read().unwrap();
+    let orphans_count = *state.orphans.read().unwrap();
+    // Each root is timed on its own, as the roots are scanned side by side
+    let mut roots = state.root_stats.read().unwrap().clone();
+    for queue in state.folder_queue.read().unwrap().roots() {
+        if let Some(root_stats) = roots.get_mut(&queue.root) {
+            root_stats.duration = queue.elapsed();
+        }
+    }
+    let mut errors_by_kind = BTreeMap::new();
+    for error in state.errors.read().unwrap().iter() {
+        *errors_by_kind.entry(error.kind).or_insert(0) += 1;
     }
 
-    Ok(ExplorerStats {
+    let stats = ExplorerStats {
         processed_paths: processed_count,
         exclusions_found: exclusions_count,
         newly_excluded: newly_excluded_count,
-    })
+        reported: reported_count,
+        warnings: warnings_count,
+        candidates: candidates_count,
+        crowded: crowded_count,
+        orphans: orphans_count,
+        cloud_synced: *state.cloud_synced.read().unwrap(),
+        dataless: *state.dataless.read().unwrap(),
+        errors: state.errors.read().unwrap().len() as i32,
+        errors_by_kind,
+        rules: state.rule_stats.read().unwrap().clone(),
+        roots,
+        duration: started.elapsed(),
+        bytes: size_mode.map(|_| *state.exclusion_bytes.read().unwrap()),
+    };
+    state.reporter.finish(&stats, &state, &root_paths);
+    Ok((stats, state))
+}
+
+/// Entry point of the library API, e.g.
+/// `Explorer::builder().config(cfg).threads(8).on_event(|e| ...).run()`
+pub struct Explorer;
+
+impl Explorer {
+    pub fn builder() -> ExplorerBuilder {
+        ExplorerBuilder::default()
+    }
+}
+
+/// Configures and runs a scan, see `Explorer::builder`
+#[derive(Default)]
+pub struct ExplorerBuilder {
+    config: Config,
+    threads: Option<usize>,
+    verbose: bool,
+    backend: Option<Arc<dyn BackupExclusionBackend>>,
+    on_event: Option<EventHandler>,
+    reporter: Option<Arc<dyn Reporter>>,
+}
+
+impl ExplorerBuilder {
+    /// Number of worker threads used when none is set, as on the command line
+    pub const DEFAULT_THREADS: usize = 4;
+
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Applies the exclusions with `backend` instead of the one of the config
+    pub fn backend(mut self, backend: Arc<dyn BackupExclusionBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Presents the scan with `reporter` instead of the output format of the config
+    pub fn reporter(mut self, reporter: impl Reporter + 'static) -> Self {
+        self.reporter = Some(Arc::new(reporter));
+        self
+    }
+
+    /// Passes the events of the scan to `handler`, called from the worker threads. Unless
+    /// a reporter is set, the events replace the output, as with `Config::quiet`
+    pub fn on_event(mut self, handler: impl Fn(&Event) + Send + Sync + 'static) -> Self {
+        self.on_event = Some(Arc::new(handler));
+        self
+    }
+
+    /// Runs the scan on a background thread, returning its events as an iterator, e.g. to
+    /// render, filter or store the findings as they come. The handler of `on_event`, if
+    /// any, still receives them
+    pub fn stream(mut self) -> ScanStream {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let handler = self.on_event.take();
+        let builder = self.on_event(move |event| {
+            if let Some(handler) = &handler {
+                handler(event);
+            }
+            // The receiver is gone when the stream is finished early
+            let _ = sender.send(event.clone());
+        });
+        ScanStream::new(receiver, thread::spawn(move || builder.run()))
+    }
+
+    /// Runs the scan, returning its counters
+    pub fn run(self) -> crate::error::Result<ExplorerStats> {
+        let (stats, _) = self.run_with_state()?;
+        Ok(stats)
+    }
+
+    /// Runs the scan, returning its counters and its final state
+    pub fn run_with_state(mut self) -> crate::error::Result<(ExplorerStats, Arc<State>)> {
+        let backend = self
+            .backend
+            .unwrap_or_else(|| backend_with_privilege_helper(self.config.privilege_helper.clone()));
+        let mut state = State::with_backend(backend);
+        state.reporter = match self.reporter {
+            Some(reporter) => reporter,
+            None if self.on_event.is_some() => {
+                self.config.quiet = true;
+                Arc::new(SilentReporter)
+            }
+            None => default_reporter(&self.config, self.verbose),
+        };
+        state.on_event = self.on_event;
+        run_scan(
+            self.config,
+            state,
+            self.threads.unwrap_or(Self::DEFAULT_THREADS),
+            self.verbose,
+        )
+    }
 }
@@ -0,0 +1,340 @@
+use crate::config::Config;
+use crate::explorer;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Backup tool an exclusion set is exported for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// restic exclude file, for `restic backup --exclude-file`
+    Restic,
+    /// Borg patterns file, for `borg create --patterns-from`
+    Borg,
+    /// borgmatic configuration snippet setting `exclude_patterns`
+    Borgmatic,
+    /// rsync patterns relative to each root, for `rsync --exclude-from`
+    Rsync,
+    /// Carbon Copy Cloner task filter, as a property list to import in a task's filter
+    Ccc,
+    /// Arq exclusion patterns, grouped by backed-up folder
+    Arq,
+    /// Backblaze `bzdirfilter` entries, for the `do_backup` section of `bzinfo.xml`
+    Backblaze,
+    /// `kopia policy set --add-ignore` commands, one per root
+    Kopia,
+}
+
+/// Runs a dry-run scan of the configured roots and `exclude_paths` and returns the paths
+/// it would exclude, sorted, leaving out those inside another excluded path
+pub fn collect_exclusions(
+    mut config: Config,
+    thread_count: usize,
+    verbose: bool,
+) -> Result<Vec<PathBuf>> {
+    config.dry_run = true;
+    config.quiet = true;
+    // Report-only findings are not part of the exclusion set
+    config.heuristics = None;
+    config.orphans = None;
+
+    let (_, state) = explorer::run_explorer_with_state(config, thread_count, verbose)?;
+    let mut exclusions: Vec<PathBuf> = state
+        .seen_exclusion_paths
+        .read()
+        .unwrap()
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    exclusions.sort();
+    exclusions.dedup_by(|path, previous| path.starts_with(previous));
+    Ok(exclusions)
+}
+
+/// Renders an exclusion set in the given format. Formats relative to the scanned
+/// directories (rsync) use `roots`, the others ignore it
+pub fn render(format: ExportFormat, exclusions: &[PathBuf], roots: &[PathBuf]) -> String {
+    match format {
+        ExportFormat::Restic => render_restic(exclusions),
+        ExportFormat::Borg => render_borg(exclusions),
+        ExportFormat::Borgmatic => render_borgmatic(exclusions),
+        ExportFormat::Rsync => render_rsync(exclusions, roots),
+        ExportFormat::Ccc => render_ccc(exclusions),
+        ExportFormat::Arq => render_arq(exclusions, roots),
+        ExportFormat::Backblaze => render_backblaze(exclusions),
+        ExportFormat::Kopia => render_kopia(exclusions, roots),
+    }
+}
+
+/// One absolute path per line. restic reads the lines as glob patterns and expands
+/// environment variables in them, so wildcards and `$` are escaped
+fn render_restic(exclusions: &[PathBuf]) -> String {
+    let mut content = String::from("# restic exclude file generated by asimeow\n");
+    for path in exclusions {
+        let mut pattern = String::new();
+        for c in path.to_string_lossy().chars() {
+            match c {
+                '*' | '?' | '[' | ']' | '\\' => {
+                    pattern.push('\\');
+                    pattern.push(c);
+                }
+                '$' => pattern.push_str("$$"),
+                _ => pattern.push(c),
+            }
+        }
+        content.push_str(&pattern);
+        content.push('\n');
+    }
+    content
+}
+
+/// Borg path prefix pattern (`pp:`), matching the path and everything below it
+/// without interpreting wildcards
+fn borg_pattern(path: &Path) -> String {
+    format!("pp:{}", path.display())
+}
+
+/// One exclude line (`- pp:/path`) per path
+fn render_borg(exclusions: &[PathBuf]) -> String {
+    let mut content = String::from("# borg patterns file generated by asimeow\n");
+    for path in exclusions {
+        content.push_str(&format!("- {}\n", borg_pattern(path)));
+    }
+    content
+}
+
+#[derive(Serialize)]
+struct BorgmaticSnippet {
+    exclude_patterns: Vec<String>,
+}
+
+/// `exclude_patterns` option of a borgmatic configuration, to merge into it
+fn render_borgmatic(exclusions: &[PathBuf]) -> String {
+    let snippet = BorgmaticSnippet {
+        exclude_patterns: exclusions.iter().map(|path| borg_pattern(path)).collect(),
+    };
+    let yaml = serde_yaml::to_string(&snippet).unwrap_or_default();
+    format!("# borgmatic exclusions generated by asimeow\n{}", yaml)
+}
+
+/// Patterns anchored to the transfer root (`/app/node_modules/`), grouped by root, for
+/// transfers whose source is the root itself. Paths outside every root are left out
+fn render_rsync(exclusions: &[PathBuf], roots: &[PathBuf]) -> String {
+    let mut content = String::from("# rsync exclude patterns generated by asimeow\n");
+    for root in roots {
+        let patterns: Vec<String> = exclusions
+            .iter()
+            .filter(|path| innermost_root(path, roots) == Some(root) && *path != root)
+            .map(|path| {
+                let relative = path.strip_prefix(root).unwrap_or(path);
+                let trailing_slash = if path.is_dir() { "/" } else { "" };
+                format!(
+                    "/{}{}",
+                    rsync_escape(&relative.to_string_lossy()),
+                    trailing_slash
+                )
+            })
+            .collect();
+        if patterns.is_empty() {
+            continue;
+        }
+
+        content.push_str(&format!("\n# root: {}\n", root.display()));
+        for pattern in patterns {
+            content.push_str(&pattern);
+            content.push('\n');
+        }
+    }
+    content
+}
+
+/// Returns the innermost root containing the path
+fn innermost_root<'a>(path: &Path, roots: &'a [PathBuf]) -> Option<&'a PathBuf> {
+    roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count())
+}
+
+/// rsync only treats backslashes as escapes in patterns containing a wildcard
+fn rsync_escape(relative: &str) -> String {
+    if !relative.contains(['*', '?', '[']) {
+        return relative.to_string();
+    }
+    let mut pattern = String::new();
+    for c in relative.chars() {
+        if matches!(c, '*' | '?' | '[' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern
+}
+
+/// Property list with one exclude rule per path, matching the path and its contents
+fn render_ccc(exclusions: &[PathBuf]) -> String {
+    let mut content = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>Description</key>
+	<string>Exclusions generated by asimeow</string>
+	<key>Rules</key>
+	<array>
+"#,
+    );
+    for path in exclusions {
+        content.push_str(&format!(
+            "\t\t<dict>\n\
+             \t\t\t<key>Action</key>\n\
+             \t\t\t<string>exclude</string>\n\
+             \t\t\t<key>Path</key>\n\
+             \t\t\t<string>{}</string>\n\
+             \t\t</dict>\n",
+            xml_escape(&path.to_string_lossy())
+        ));
+    }
+    content.push_str("\t</array>\n</dict>\n</plist>\n");
+    content
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// One folder filter per excluded directory, with the trailing slash Backblaze uses.
+/// Backblaze cannot exclude single files, only extensions, so files are left as comments
+fn render_backblaze(exclusions: &[PathBuf]) -> String {
+    let mut content = String::from(
+        "<!-- Backblaze exclusions generated by asimeow, for the do_backup section of bzinfo.xml -->\n",
+    );
+    for path in exclusions {
+        let path_str = xml_escape(&path.to_string_lossy());
+        if path.is_dir() {
+            content.push_str(&format!(
+                "<bzdirfilter dir=\"{}/\" whattodo=\"exclude\" filetype=\"*\" />\n",
+                path_str.trim_end_matches('/')
+            ));
+        } else {
+            content.push_str(&format!(
+                "<!-- not excluded, Backblaze only excludes folders: {} -->\n",
+                path_str.replace("--", "&#45;&#45;")
+            ));
+        }
+    }
+    content
+}
+
+/// Absolute paths grouped by root, each group being the exclusions of the Arq backup
+/// plan folder of the same path. Paths outside every root come last
+fn render_arq(exclusions: &[PathBuf], roots: &[PathBuf]) -> String {
+    let mut content = String::from("# Arq exclusions generated by asimeow\n");
+    let groups = roots
+        .iter()
+        .map(|root| (format!("folder: {}", root.display()), Some(root)))
+        .chain(std::iter::once(("outside the roots".to_string(), None)));
+    for (title, root) in groups {
+        let paths: Vec<&PathBuf> = exclusions
+            .iter()
+            .filter(|path| innermost_root(path, roots) == root)
+            .collect();
+        if paths.is_empty() {
+            continue;
+        }
+
+        content.push_str(&format!("\n# {}\n", title));
+        for path in paths {
+            content.push_str(&format!("{}\n", path.display()));
+        }
+    }
+    content
+}
+
+/// Shell commands adding the exclusions to the ignore rules of the Kopia policy of their
+/// root, as anchored gitignore-style patterns. Paths outside every root are added to the
+/// policy of their parent directory
+fn render_kopia(exclusions: &[PathBuf], roots: &[PathBuf]) -> String {
+    let mut targets: Vec<(&Path, Vec<String>)> = Vec::new();
+    for path in exclusions {
+        let target = match innermost_root(path, roots) {
+            Some(root) if root != path => root.as_path(),
+            _ => match path.parent() {
+                Some(parent) => parent,
+                None => continue,
+            },
+        };
+        let relative = path.strip_prefix(target).unwrap_or(path);
+        let mut pattern = String::from("/");
+        for c in relative.to_string_lossy().chars() {
+            if matches!(c, '*' | '?' | '[' | '\\') {
+                pattern.push('\\');
+            }
+            pattern.push(c);
+        }
+        if path.is_dir() {
+            pattern.push('/');
+        }
+
+        match targets.iter_mut().find(|(existing, _)| *existing == target) {
+            Some((_, patterns)) => patterns.push(pattern),
+            None => targets.push((target, vec![pattern])),
+        }
+    }
+
+    let mut content = String::from("# Kopia ignore rules generated by asimeow\n");
+    for (target, patterns) in targets {
+        content.push_str(&format!(
+            "kopia policy set {}",
+            shell_quote(&target.to_string_lossy())
+        ));
+        for pattern in patterns {
+            content.push_str(&format!(" \\\n    --add-ignore {}", shell_quote(&pattern)));
+        }
+        content.push('\n');
+    }
+    content
+}
+
+/// Quotes a shell argument with single quotes
+pub fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// Exports the exclusion set of a scan of the configured roots, to a file or to stdout
+pub fn run_export(
+    config: Config,
+    format: ExportFormat,
+    output: Option<&str>,
+    thread_count: usize,
+    verbose: bool,
+) -> Result<()> {
+    let roots = config
+        .roots
+        .iter()
+        .map(|root| crate::config::expand_tilde(&root.path))
+        .collect::<Result<Vec<_>>>()?;
+    // Verbose scan output would end up in an export written to stdout
+    let exclusions = collect_exclusions(config, thread_count, verbose && output.is_some())?;
+    let content = render(format, &exclusions, &roots);
+
+    match output {
+        Some(output) => {
+            fs::write(output, content)
+                .with_context(|| format!("Failed to write export file: {}", output))?;
+            eprintln!(
+                "{}",
+                crate::i18n::tr(
+                    "export-done",
+                    &[("count", exclusions.len().into()), ("path", output.into()),],
+                )
+            );
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use crate::backend::{backend_with_privilege_helper, BackupExclusionBackend};
+use crate::config::{Config, RuleFilter};
+use crate::export;
+use crate::i18n::tr;
+use crate::size::{directory_sizes, format_size, SizeMode};
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Finds the paths currently excluded from Time Machine that a rule targets, by scanning
+/// the configured roots with that rule alone (nothing is changed). Disabled rules are
+/// evaluated too, as their earlier exclusions may still be in place.
+pub fn find_rule_exclusions(
+    config: &Config,
+    rule_name: &str,
+    backend: &dyn BackupExclusionBackend,
+    thread_count: usize,
+    verbose: bool,
+) -> Result<Vec<PathBuf>> {
+    let mut config = config.clone();
+    for rule in config
+        .rules
+        .iter_mut()
+        .filter(|rule| rule.name == rule_name)
+    {
+        rule.enabled = Some(true);
+    }
+    config.apply_rule_filter(&RuleFilter {
+        rule_names: vec![rule_name.to_string()],
+        ..Default::default()
+    })?;
+    // The paths of `exclude_paths` are not attributed to any rule
+    config.exclude_paths.clear();
+
+    let exclusions = export::collect_exclusions(config, thread_count, verbose)?;
+    Ok(exclusions
+        .into_iter()
+        .filter(|path| backend.is_excluded(path))
+        .collect())
+}
+
+/// Lists, for each rule, the paths it excluded that are still excluded, with their sizes,
+/// to audit the footprint of a single ecosystem
+pub fn run_find(
+    config: Config,
+    rule_names: &[String],
+    thread_count: usize,
+    size_mode: SizeMode,
+    verbose: bool,
+) -> Result<()> {
+    let backend = backend_with_privilege_helper(config.privilege_helper.clone());
+    for (i, rule_name) in rule_names.iter().enumerate() {
+        let paths =
+            find_rule_exclusions(&config, rule_name, backend.as_ref(), thread_count, verbose)?;
+        let sizes = directory_sizes(&paths, thread_count, size_mode);
+
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "{}",
+            tr("find-header", &[("rule", rule_name.as_str().into())])
+        );
+        println!("------------------------------------");
+        for (path, size) in paths.iter().zip(&sizes) {
+            println!("🟡 {:>10}  {}", format_size(*size), path.display());
+        }
+        println!(
+            "{}",
+            tr(
+                "find-total",
+                &[
+                    ("count", paths.len().into()),
+                    ("size", format_size(sizes.iter().sum()).into()),
+                ],
+            )
+        );
+    }
+    Ok(())
+}
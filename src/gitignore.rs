@@ -0,0 +1,58 @@
+use glob::Pattern;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extracts the patterns of a `.gitignore` file that can match top-level entries.
+/// Comments, negations and patterns targeting nested paths are skipped, while
+/// anchoring and trailing slashes are stripped (e.g. `/build/` becomes `build`).
+pub fn parse_gitignore(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .map(|line| line.trim_start_matches('/').trim_end_matches('/'))
+        .filter(|line| !line.is_empty() && !line.contains('/') && !line.contains("**"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Returns the top-level directories of a git repository that are ignored by its
+/// `.gitignore`, sorted by path. Returns nothing if `repo` has no `.git` folder.
+pub fn ignored_directories(repo: &Path) -> Vec<PathBuf> {
+    if !repo.join(".git").exists() {
+        return Vec::new();
+    }
+
+    let content = match fs::read_to_string(repo.join(".gitignore")) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+
+    let patterns: Vec<Pattern> = parse_gitignore(&content)
+        .iter()
+        .filter_map(|pattern| Pattern::new(pattern).ok())
+        .collect();
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ignored: Vec<PathBuf> = match fs::read_dir(repo) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|entry_path| {
+                let name = entry_path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+                entry_path.is_dir()
+                    && name != ".git"
+                    && patterns.iter().any(|pattern| pattern.matches(&name))
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    ignored.sort();
+    ignored
+}
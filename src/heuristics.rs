@@ -0,0 +1,125 @@
+use crate::config::{HeuristicsConfig, OrphansConfig};
+use glob::Pattern;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// Size, file count and average file size of a directory tree
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DirectoryStats {
+    pub size: u64,
+    pub files: u64,
+}
+
+impl DirectoryStats {
+    /// Average size of the files in the tree, in bytes
+    pub fn average_file_size(&self) -> u64 {
+        self.size.checked_div(self.files).unwrap_or(0)
+    }
+}
+
+/// Walks a directory tree without following symlinks, skipping unreadable entries and the
+/// contents of dataless directories
+pub fn directory_stats(path: &Path) -> DirectoryStats {
+    let mut stats = DirectoryStats::default();
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return stats,
+    };
+
+    if !metadata.is_dir() {
+        stats.size = metadata.len();
+        stats.files = 1;
+        return stats;
+    }
+    if crate::icloud::is_dataless(&metadata) {
+        return stats;
+    }
+
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let child = directory_stats(&entry.path());
+            stats.size += child.size;
+            stats.files += child.files;
+        }
+    }
+    stats
+}
+
+/// Whether a directory name looks like a cache or build output (case-insensitive)
+pub fn has_cache_like_name(path: &Path, config: &HeuristicsConfig) -> bool {
+    name_matches(path, &config.names)
+}
+
+fn name_matches(path: &Path, patterns: &[String]) -> bool {
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy().to_lowercase(),
+        None => return false,
+    };
+    patterns.iter().any(|pattern| {
+        Pattern::new(&pattern.to_lowercase())
+            .map(|pattern| pattern.matches(&name))
+            .unwrap_or(false)
+    })
+}
+
+/// Evaluates a directory against the heuristics and returns its stats and the reasons
+/// it looks like a rebuildable cache, or `None` if it does not.
+/// A candidate needs a cache-like name, at least `min_size_mb`, and either many small
+/// files or a recent regeneration (created or modified in the last `recent_days`).
+pub fn evaluate(path: &Path, config: &HeuristicsConfig) -> Option<(DirectoryStats, Vec<String>)> {
+    if !path.is_dir() || !has_cache_like_name(path, config) {
+        return None;
+    }
+
+    let stats = directory_stats(path);
+    if stats.size < config.min_size_mb * 1024 * 1024 {
+        return None;
+    }
+
+    let mut reasons = Vec::new();
+    if stats.files >= config.min_files
+        && stats.average_file_size() <= config.max_average_file_kb * 1024
+    {
+        reasons.push(format!("{} small files", stats.files));
+    }
+    if is_recently_regenerated(path, config.recent_days) {
+        reasons.push(format!(
+            "regenerated in the last {} days",
+            config.recent_days
+        ));
+    }
+
+    if reasons.is_empty() {
+        None
+    } else {
+        Some((stats, reasons))
+    }
+}
+
+/// Evaluates a directory whose folder no rule matched, returning its size if it looks
+/// like an orphaned build output: a build output name and at least `min_size_mb`.
+pub fn evaluate_orphan(path: &Path, config: &OrphansConfig) -> Option<u64> {
+    if !path.is_dir() || !name_matches(path, &config.names) {
+        return None;
+    }
+
+    let size = crate::size::directory_size(path);
+    (size >= config.min_size_mb * 1024 * 1024).then_some(size)
+}
+
+fn is_recently_regenerated(path: &Path, recent_days: u64) -> bool {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return false,
+    };
+    let regenerated = match metadata.created().or_else(|_| metadata.modified()) {
+        Ok(time) => time,
+        Err(_) => return false,
+    };
+    match SystemTime::now().duration_since(regenerated) {
+        Ok(age) => age <= Duration::from_secs(recent_days * 24 * 60 * 60),
+        // Timestamps in the future count as recent
+        Err(_) => true,
+    }
+}
@@ -0,0 +1,114 @@
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use unic_langid::LanguageIdentifier;
+
+/// Language of the user-facing messages, kept as Fluent catalogs in `src/locales`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum Lang {
+    #[default]
+    En,
+    It,
+}
+
+impl Lang {
+    fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::It => "it",
+        }
+    }
+
+    fn catalog(self) -> &'static str {
+        match self {
+            Lang::En => include_str!("locales/en.ftl"),
+            Lang::It => include_str!("locales/it.ftl"),
+        }
+    }
+
+    /// Returns the language of a locale such as `it_IT.UTF-8`, if it has a catalog
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let language = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match language.as_str() {
+            "en" => Some(Lang::En),
+            "it" => Some(Lang::It),
+            _ => None,
+        }
+    }
+}
+
+// Set by `--lang` or detected from the environment
+static LANG: RwLock<Lang> = RwLock::new(Lang::En);
+
+/// Sets the language of the later messages
+pub fn set_lang(lang: Lang) {
+    *LANG.write().unwrap() = lang;
+}
+
+/// Returns the language of the messages
+pub fn lang() -> Lang {
+    *LANG.read().unwrap()
+}
+
+/// Returns the language of the environment, from the first locale variable set among
+/// `LC_ALL`, `LC_MESSAGES` and `LANG`, or English
+pub fn detect() -> Lang {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|locale| !locale.is_empty())
+        .and_then(|locale| Lang::from_locale(&locale))
+        .unwrap_or_default()
+}
+
+thread_local! {
+    // Parsed catalogs, built on first use by each thread
+    static BUNDLES: RefCell<HashMap<Lang, FluentBundle<FluentResource>>> =
+        RefCell::new(HashMap::new());
+}
+
+fn bundle(lang: Lang) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = lang.code().parse().expect("valid language code");
+    let mut bundle = FluentBundle::new(vec![id]);
+    // Isolation marks would end up in terminals and logs
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(lang.catalog().to_string())
+        .unwrap_or_else(|(_, errors)| panic!("invalid {} catalog: {:?}", lang.code(), errors));
+    bundle
+        .add_resource(resource)
+        .unwrap_or_else(|errors| panic!("invalid {} catalog: {:?}", lang.code(), errors));
+    bundle
+}
+
+/// Formats the message `id` of the catalog of `lang` with named arguments, falling back
+/// to English when the catalog lacks it, and to the id when no catalog has it
+pub fn translate(lang: Lang, id: &str, args: &[(&str, FluentValue)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+
+    BUNDLES.with(|bundles| {
+        let mut bundles = bundles.borrow_mut();
+        for lang in [lang, Lang::En] {
+            let bundle = bundles.entry(lang).or_insert_with(|| bundle(lang));
+            if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) {
+                let mut errors = Vec::new();
+                return bundle
+                    .format_pattern(pattern, Some(&fluent_args), &mut errors)
+                    .to_string();
+            }
+        }
+        id.to_string()
+    })
+}
+
+/// Formats the message `id` in the current language, see `translate`
+pub fn tr(id: &str, args: &[(&str, FluentValue)]) -> String {
+    translate(lang(), id, args)
+}
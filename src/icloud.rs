@@ -0,0 +1,112 @@
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Suffix of the items iCloud Drive never uploads
+pub const NOSYNC_SUFFIX: &str = ".nosync";
+
+/// Location of iCloud Drive in the home directory
+const CLOUD_DOCS: &str = "Library/Mobile Documents/com~apple~CloudDocs";
+
+/// File flag of the items whose content only lives in the cloud (`SF_DATALESS`): iCloud
+/// Drive items evicted to save space, and File Provider placeholders (Dropbox, OneDrive).
+/// Reading such a file, or listing such a directory, downloads it
+pub const SF_DATALESS: u32 = 0x4000_0000;
+
+/// Returns true if the metadata, read with `symlink_metadata` which downloads nothing, is
+/// the one of a dataless item. Always false outside macOS
+pub fn is_dataless(metadata: &fs::Metadata) -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        use std::os::macos::fs::MetadataExt;
+        metadata.st_flags() & SF_DATALESS != 0
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = metadata;
+        false
+    }
+}
+
+/// Returns true if the path is a dataless item, see `is_dataless`
+pub fn is_dataless_path(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| is_dataless(&metadata))
+}
+
+/// Returns the folders synchronized with iCloud Drive: iCloud Drive itself, plus the
+/// Desktop and Documents folders when "Desktop & Documents Folders" is enabled
+pub fn icloud_drive_folders(home: &Path) -> Vec<PathBuf> {
+    let cloud_docs = home.join(CLOUD_DOCS);
+    if !cloud_docs.is_dir() {
+        return Vec::new();
+    }
+
+    let mut folders = vec![cloud_docs.clone()];
+    for synced in ["Desktop", "Documents"] {
+        if cloud_docs.join(synced).is_dir() {
+            folders.push(home.join(synced));
+        }
+    }
+    folders
+}
+
+/// Returns true if the path is synchronized with iCloud Drive
+pub fn is_under_icloud_drive(path: &Path, home: &Path) -> bool {
+    icloud_drive_folders(home)
+        .iter()
+        .any(|folder| path.starts_with(folder))
+}
+
+/// Returns the `.nosync` counterpart of a path, e.g. `node_modules.nosync`
+pub fn nosync_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(NOSYNC_SUFFIX);
+    path.with_file_name(name)
+}
+
+/// Returns true if the path is a `.nosync` directory that a sibling symlink points to,
+/// i.e. a directory moved out of sync by `move_to_nosync`
+pub fn is_nosync_target(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let Some(stem) = name.strip_suffix(NOSYNC_SUFFIX) else {
+        return false;
+    };
+    let link = path.with_file_name(stem);
+    link.is_symlink()
+        && fs::read_link(&link)
+            .map(|target| link.with_file_name(target) == path)
+            .unwrap_or(false)
+}
+
+/// Stops iCloud Drive from uploading a directory by renaming it with the `.nosync` suffix
+/// and leaving a symlink under the original name, so tools keep finding it.
+/// Returns true if the directory was moved, or false if it was already moved.
+pub fn move_to_nosync(path: &Path) -> Result<bool> {
+    let nosync = nosync_path(path);
+
+    if path.is_symlink() {
+        if is_nosync_target(&nosync) {
+            return Ok(false); // Already moved
+        }
+        return Err(anyhow::anyhow!("is a symlink, left untouched"));
+    }
+    if !path.is_dir() {
+        return Err(anyhow::anyhow!("only directories can be moved to .nosync"));
+    }
+    if nosync.exists() || nosync.is_symlink() {
+        return Err(anyhow::anyhow!("{} already exists", nosync.display()));
+    }
+
+    fs::rename(path, &nosync)?;
+    // A relative target keeps the link valid when the parent folder is moved
+    let target = nosync.file_name().unwrap_or_default();
+    if let Err(e) = std::os::unix::fs::symlink(target, path) {
+        // Restore the original layout rather than leaving the directory renamed
+        fs::rename(&nosync, path)?;
+        return Err(e.into());
+    }
+    crate::explorer::match_parent_ownership(path);
+    Ok(true)
+}
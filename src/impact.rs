@@ -0,0 +1,97 @@
+use crate::explorer;
+use crate::size::SizeMode;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+/// Firmlinked location of the data volume, under which backups store user data
+const DATA_VOLUME_PREFIX: &str = "/System/Volumes/Data";
+
+/// Space an excluded item occupies on disk and in the newest backup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Impact {
+    pub on_disk: u64,
+    /// Space only the newest backup holds for the item (`tmutil uniquesize`), None when
+    /// the item is not in that backup
+    pub in_backup: Option<u64>,
+}
+
+/// Returns the newest completed backup (`tmutil latestbackup`), if the backup disk is
+/// reachable
+pub fn latest_backup() -> Option<PathBuf> {
+    let output = explorer::tmutil_command()
+        .arg("latestbackup")
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let latest = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !latest.is_empty()).then(|| PathBuf::from(latest))
+}
+
+/// Returns where a backup holds `path`: backups store each volume under its own name
+/// (e.g. `Macintosh HD - Data`), so every volume folder of the backup is tried
+pub fn backup_path(backup: &Path, path: &Path) -> Option<PathBuf> {
+    let relative = path
+        .strip_prefix(DATA_VOLUME_PREFIX)
+        .or_else(|_| path.strip_prefix("/"))
+        .ok()?;
+    let mut volumes: Vec<PathBuf> = fs::read_dir(backup)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|volume| volume.is_dir())
+        .collect();
+    volumes.sort();
+    volumes
+        .into_iter()
+        .map(|volume| volume.join(relative))
+        .find(|candidate| candidate.symlink_metadata().is_ok())
+}
+
+/// Parses the size printed by `tmutil uniquesize` (e.g. `1.5G /Volumes/...`) into bytes
+pub fn parse_uniquesize(output: &str) -> Option<u64> {
+    let size = output.split_whitespace().next()?;
+    let split = size
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier = match unit
+        .trim_end_matches(['B', 'b'])
+        .to_ascii_uppercase()
+        .as_str()
+    {
+        "" => 1u64,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        "T" => 1 << 40,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as u64)
+}
+
+/// Returns the space only `backup_path` holds in its backup (`tmutil uniquesize`)
+pub fn unique_size(backup_path: &Path) -> Option<u64> {
+    let output = explorer::tmutil_command()
+        .arg("uniquesize")
+        .arg(backup_path)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_uniquesize(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Estimates the space an excluded item takes on disk and in the newest backup, i.e. what
+/// its exclusion saves once that backup ages out
+pub fn impact(path: &Path, latest_backup: Option<&Path>, mode: SizeMode) -> Impact {
+    Impact {
+        on_disk: crate::size::directory_size_as(path, mode),
+        in_backup: latest_backup
+            .and_then(|backup| backup_path(backup, path))
+            .and_then(|backup_path| unique_size(&backup_path)),
+    }
+}
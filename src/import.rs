@@ -0,0 +1,336 @@
+use crate::config::{self, Config, GitignoreConfig, GitignoreMode, Root, Rule};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Tool a configuration is imported from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportSource {
+    /// Asimov (stevegrunwell/asimov), reading its script and launchd setup
+    Asimov,
+    /// tmignore (samuelmeuli/tmignore), reading its JSON config
+    Tmignore,
+}
+
+/// Where the Asimov script is installed by Homebrew or by hand
+pub const ASIMOV_SCRIPT_PATHS: &[&str] = &[
+    "/opt/homebrew/bin/asimov",
+    "/usr/local/bin/asimov",
+    "~/bin/asimov",
+];
+
+/// launchd jobs installed by Asimov to run it daily
+pub const ASIMOV_LAUNCHD_PLISTS: &[&str] = &[
+    "/Library/LaunchDaemons/com.stevegrunwell.asimov.plist",
+    "~/Library/LaunchAgents/com.stevegrunwell.asimov.plist",
+];
+
+/// Arrays of `"<directory> <sentinel>"` pairs, by Asimov version
+const SENTINEL_ARRAYS: &[&str] = &["ASIMOV_VENDOR_DIR_SENTINELS", "FILEPATHS"];
+
+/// Array of the paths Asimov never searches
+const SKIP_PATHS_ARRAY: &str = "ASIMOV_SKIP_PATHS";
+
+/// Config file of tmignore
+pub const TMIGNORE_CONFIG_PATH: &str = "~/.config/tmignore/config.json";
+
+/// Paths excluded by tmignore, recorded to undo them when they leave the `.gitignore`
+pub const TMIGNORE_CACHE_PATH: &str = "~/.config/tmignore/cache.json";
+
+/// An imported configuration, with what could not be converted exactly
+#[derive(Debug)]
+pub struct Import {
+    pub config: Config,
+    pub warnings: Vec<String>,
+}
+
+/// Returns the entries of a bash array assignment (`NAME=(` ... `)`) of a script,
+/// with quotes and comments removed, or None if the array is not defined
+fn bash_array(script: &str, name: &str) -> Option<Vec<String>> {
+    let mut lines = script.lines().skip_while(|line| {
+        !line
+            .trim_start()
+            .trim_start_matches("readonly ")
+            .starts_with(&format!("{}=(", name))
+    });
+    let first = lines.next()?;
+    let mut body = first[first.find('(')? + 1..].to_string();
+    if !body.contains(')') {
+        for line in lines {
+            body.push('\n');
+            body.push_str(line);
+            if line.trim_start().starts_with(')') {
+                break;
+            }
+        }
+    }
+
+    Some(
+        body.lines()
+            .map(|line| line.split(" #").next().unwrap_or_default().trim())
+            .map(|line| line.split(')').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.trim_matches(|c| c == '\'' || c == '"').to_string())
+            .collect(),
+    )
+}
+
+/// Builds a rule name from a sentinel file name, e.g. `asimov-package-json`
+fn rule_name(sentinel: &str) -> String {
+    let slug: String = sentinel
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("asimov-{}", slug.trim_matches('-'))
+}
+
+/// Converts the definitions of an Asimov script: each sentinel becomes a rule excluding
+/// its directories, the home directory becomes the root, and skip paths become `ignore`
+/// entries matching their directory name
+pub fn parse_asimov_script(script: &str) -> Result<Import> {
+    let pairs = SENTINEL_ARRAYS
+        .iter()
+        .find_map(|name| bash_array(script, name))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No Asimov definitions found (expected one of: {})",
+                SENTINEL_ARRAYS.join(", ")
+            )
+        })?;
+
+    let mut warnings = Vec::new();
+    let mut rules: Vec<Rule> = Vec::new();
+    for pair in pairs {
+        let parts: Vec<&str> = pair.split_whitespace().collect();
+        let [directory, sentinel] = parts[..] else {
+            warnings.push(format!(
+                "Unconvertible entry '{}': expected '<directory> <sentinel>'",
+                pair
+            ));
+            continue;
+        };
+        match rules.iter_mut().find(|rule| rule.file_match == sentinel) {
+            Some(rule) => rule.exclusions.push(directory.into()),
+            None => rules.push(Rule {
+                name: rule_name(sentinel),
+                file_match: sentinel.to_string(),
+                exclusions: vec![directory.into()],
+                ..Default::default()
+            }),
+        }
+    }
+
+    let skip_paths = bash_array(script, SKIP_PATHS_ARRAY).unwrap_or_default();
+    let ignore = ignore_entries(&skip_paths, &mut warnings);
+
+    Ok(Import {
+        config: Config {
+            // Asimov searches the whole home directory
+            roots: vec![Root {
+                path: "~/".to_string(),
+                ..Default::default()
+            }],
+            ignore,
+            rules,
+            ..Default::default()
+        },
+        warnings,
+    })
+}
+
+/// Converts paths a tool never searches into `ignore` entries, which match directory
+/// names anywhere, flagging the approximation
+fn ignore_entries(skip_paths: &[String], warnings: &mut Vec<String>) -> Vec<String> {
+    let mut ignore = Vec::new();
+    for skip_path in skip_paths {
+        match Path::new(skip_path).file_name() {
+            Some(name) => {
+                let name = name.to_string_lossy().to_string();
+                warnings.push(format!(
+                    "Skip path '{}' converted to the ignore entry '{}', which skips every directory with that name",
+                    skip_path, name
+                ));
+                ignore.push(name);
+            }
+            None => warnings.push(format!("Unconvertible skip path '{}'", skip_path)),
+        }
+    }
+    ignore
+}
+
+/// tmignore config, with the defaults tmignore uses for missing keys
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TmignoreConfig {
+    #[serde(default = "default_tmignore_search_paths")]
+    search_paths: Vec<String>,
+    #[serde(default = "default_tmignore_ignored_paths")]
+    ignored_paths: Vec<String>,
+    #[serde(default)]
+    whitelist: Vec<String>,
+}
+
+fn default_tmignore_search_paths() -> Vec<String> {
+    vec!["~".to_string()]
+}
+
+fn default_tmignore_ignored_paths() -> Vec<String> {
+    [
+        "~/.Trash",
+        "~/Applications",
+        "~/Downloads",
+        "~/Library",
+        "~/Music/iTunes",
+        "~/Music/Music",
+        "~/Pictures/Photos Library.photoslibrary",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect()
+}
+
+/// Converts a tmignore config: search paths become roots, the whitelist becomes `keep`
+/// entries and the `.gitignore`-driven exclusions the `gitignore` option in exclude mode
+/// without a size threshold, as tmignore excludes every ignored path
+pub fn parse_tmignore_config(json: &str) -> Result<Import> {
+    let tmignore: TmignoreConfig =
+        serde_yaml::from_str(json).context("Failed to parse the tmignore config")?;
+
+    let mut warnings = vec![
+        "tmignore excludes ignored files too, while the gitignore option only considers directories"
+            .to_string(),
+    ];
+    let ignore = ignore_entries(&tmignore.ignored_paths, &mut warnings);
+
+    Ok(Import {
+        config: Config {
+            roots: tmignore
+                .search_paths
+                .into_iter()
+                .map(|path| Root {
+                    path,
+                    ..Default::default()
+                })
+                .collect(),
+            ignore,
+            keep: tmignore.whitelist,
+            gitignore: Some(GitignoreConfig {
+                mode: GitignoreMode::Exclude,
+                min_size_mb: 0,
+            }),
+            ..Default::default()
+        },
+        warnings,
+    })
+}
+
+/// Finds the Asimov script: the given path, or the first of the usual install locations
+fn find_asimov_script(script_path: Option<&str>) -> Result<PathBuf> {
+    if let Some(script_path) = script_path {
+        return config::expand_tilde(script_path);
+    }
+    ASIMOV_SCRIPT_PATHS
+        .iter()
+        .filter_map(|path| config::expand_tilde(path).ok())
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Asimov script not found in {}; pass its path with --file",
+                ASIMOV_SCRIPT_PATHS.join(", ")
+            )
+        })
+}
+
+/// Imports the configuration of another tool, writing it to a file or to stdout and
+/// flagging on stderr anything that was not converted exactly
+pub fn run_import(
+    source: ImportSource,
+    source_path: Option<&str>,
+    output: Option<&str>,
+) -> Result<()> {
+    let (source_path, import) = match source {
+        ImportSource::Asimov => import_asimov(source_path)?,
+        ImportSource::Tmignore => import_tmignore(source_path)?,
+    };
+
+    let yaml = format!(
+        "# Converted from {} by asimeow import\n{}",
+        source_path,
+        serde_yaml::to_string(&import.config)?
+    );
+    match output {
+        Some(output) => {
+            if Path::new(output).exists() {
+                return Err(anyhow::anyhow!("Config file already exists at: {}", output));
+            }
+            fs::write(output, yaml)
+                .with_context(|| format!("Failed to write config file: {}", output))?;
+            eprintln!(
+                "✅ Imported {} rules into {}",
+                import.config.rules.len(),
+                output
+            );
+        }
+        None => print!("{}", yaml),
+    }
+    for warning in &import.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    Ok(())
+}
+
+/// Reads the Asimov script and flags its launchd job, still running alongside asimeow
+fn import_asimov(script_path: Option<&str>) -> Result<(String, Import)> {
+    let script_path = find_asimov_script(script_path)?;
+    let script = fs::read_to_string(&script_path)
+        .with_context(|| format!("Failed to read Asimov script: {}", script_path.display()))?;
+    let mut import = parse_asimov_script(&script)?;
+
+    for plist in ASIMOV_LAUNCHD_PLISTS {
+        let plist = config::expand_tilde(plist)?;
+        if plist.exists() {
+            import.warnings.push(format!(
+                "Asimov is scheduled by launchd: once asimeow is set up, stop it with `sudo launchctl unload -w {}`",
+                plist.display()
+            ));
+        }
+    }
+    Ok((script_path.display().to_string(), import))
+}
+
+/// Reads the tmignore config, using the tmignore defaults when there is none, and flags
+/// the paths tmignore already excluded
+fn import_tmignore(config_path: Option<&str>) -> Result<(String, Import)> {
+    let config_path = config::expand_tilde(config_path.unwrap_or(TMIGNORE_CONFIG_PATH))?;
+    let (source, mut import) = if config_path.exists() {
+        let json = fs::read_to_string(&config_path).with_context(|| {
+            format!("Failed to read tmignore config: {}", config_path.display())
+        })?;
+        (
+            config_path.display().to_string(),
+            parse_tmignore_config(&json)?,
+        )
+    } else {
+        let mut import = parse_tmignore_config("{}")?;
+        import.warnings.push(format!(
+            "No tmignore config at {}, the tmignore defaults were converted",
+            config_path.display()
+        ));
+        ("the tmignore defaults".to_string(), import)
+    };
+
+    let cache_path = config::expand_tilde(TMIGNORE_CACHE_PATH)?;
+    if let Ok(cache) = fs::read_to_string(&cache_path) {
+        let excluded = serde_yaml::from_str::<Vec<String>>(&cache).unwrap_or_default();
+        if !excluded.is_empty() {
+            import.warnings.push(format!(
+                "{} paths excluded by tmignore ({}) keep their exclusions; the gitignore option finds them again on the next scan",
+                excluded.len(),
+                cache_path.display()
+            ));
+        }
+    }
+    Ok((source, import))
+}
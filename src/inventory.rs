@@ -0,0 +1,326 @@
+use crate::backend::backend_with_privilege_helper;
+use crate::config::{self, ExclusionType};
+use crate::explorer::{self, ExclusionMechanism};
+use crate::i18n::tr;
+use crate::impact;
+use crate::pagination::Pagination;
+use crate::privileged;
+use crate::size::{format_size, SizeMode};
+use crate::snapshots;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// An item excluded from Time Machine, whoever created the exclusion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryEntry {
+    pub path: PathBuf,
+    /// `Sticky` or `FixedPath`
+    pub mechanism: ExclusionMechanism,
+}
+
+/// Finds the items carrying the sticky exclusion attribute under `root`, with a single
+/// `find -xattrname` walk (macOS) that stays on the volume of `root` and does not descend
+/// into excluded directories. Returns an empty list where `find` lacks `-xattrname`.
+pub fn find_sticky_exclusions(root: &Path) -> Vec<PathBuf> {
+    let output = Command::new("find")
+        .arg("-x")
+        .arg(root)
+        .args([
+            "-xattrname",
+            explorer::STICKY_EXCLUSION_XATTR,
+            "-print",
+            "-prune",
+        ])
+        .stderr(Stdio::null())
+        .output();
+
+    match output {
+        // Unreadable directories make find fail while still printing the matches
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Lists every exclusion under `root`: the sticky exclusion attributes found on disk and
+/// the fixed-path exclusions (`skip_paths`) within `root`, sorted by path
+pub fn inventory(root: &Path, skip_paths: &[PathBuf]) -> Vec<InventoryEntry> {
+    let mut entries: Vec<InventoryEntry> = find_sticky_exclusions(root)
+        .into_iter()
+        .map(|path| InventoryEntry {
+            path,
+            mechanism: ExclusionMechanism::Sticky,
+        })
+        .collect();
+    entries.extend(
+        skip_paths
+            .iter()
+            .filter(|path| path.starts_with(root))
+            .map(|path| InventoryEntry {
+                path: path.clone(),
+                mechanism: ExclusionMechanism::FixedPath,
+            }),
+    );
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries
+}
+
+/// Prints every item excluded from Time Machine under a path (the whole startup volume
+/// by default), including exclusions asimeow did not create. With `impact`, the space each
+/// item takes on disk and in the newest backup is estimated. Only the entries of the
+/// requested page are listed and measured.
+pub fn run_inventory(
+    root: Option<&str>,
+    impact: bool,
+    size_mode: SizeMode,
+    pagination: &Pagination,
+    verbose: bool,
+) -> Result<()> {
+    let root = config::expand_tilde(root.unwrap_or("/"))?;
+    if !root.is_dir() {
+        return Err(anyhow::anyhow!("Not a directory: {}", root.display()));
+    }
+
+    println!("{}", tr("inventory-header", &path_arg(&root)));
+    println!("------------------------------------");
+    let entries = inventory(&root, &explorer::fixed_path_exclusions());
+    let latest_backup = if impact {
+        let latest_backup = impact::latest_backup();
+        if latest_backup.is_none() {
+            eprintln!("{}", tr("inventory-no-backup", &[]));
+        }
+        latest_backup
+    } else {
+        None
+    };
+    let (mut total_on_disk, mut total_in_backup) = (0, 0);
+    let shown = pagination.apply(&entries);
+    for entry in shown {
+        let id = if entry.path.exists() {
+            "inventory-entry"
+        } else {
+            "inventory-entry-missing"
+        };
+        println!("{}", tr(id, &entry_args(entry)));
+        if impact && entry.path.exists() {
+            let impact = impact::impact(&entry.path, latest_backup.as_deref(), size_mode);
+            total_on_disk += impact.on_disk;
+            total_in_backup += impact.in_backup.unwrap_or(0);
+            let on_disk = ("size", format_size(impact.on_disk).into());
+            let line = match impact.in_backup {
+                Some(size) => tr(
+                    "inventory-impact",
+                    &[on_disk, ("backup", format_size(size).into())],
+                ),
+                None => tr("inventory-impact-not-in-backup", &[on_disk]),
+            };
+            println!("  {}", line);
+        } else if verbose && entry.path.exists() {
+            println!(
+                "  → {}",
+                format_size(crate::size::directory_size_as(&entry.path, size_mode))
+            );
+        }
+    }
+
+    let fixed = entries
+        .iter()
+        .filter(|entry| entry.mechanism == ExclusionMechanism::FixedPath)
+        .count();
+    if let Some(footer) = pagination.footer(entries.len()) {
+        println!("\n{}", footer);
+    }
+    println!(
+        "\n{}",
+        tr(
+            "inventory-total",
+            &[
+                ("count", entries.len().into()),
+                ("sticky", (entries.len() - fixed).into()),
+                ("fixed", fixed.into()),
+            ],
+        )
+    );
+    if impact {
+        let id = if shown.len() < entries.len() {
+            "inventory-space-listed"
+        } else {
+            "inventory-space"
+        };
+        println!(
+            "{}",
+            tr(
+                id,
+                &[
+                    ("size", format_size(total_on_disk).into()),
+                    ("backup", format_size(total_in_backup).into()),
+                ],
+            )
+        );
+        let snapshots = snapshots::list_local_snapshots(Path::new("/"));
+        if !snapshots.is_empty() {
+            let paths: Vec<PathBuf> = entries.iter().map(|entry| entry.path.clone()).collect();
+            println!(
+                "{}",
+                tr(
+                    "inventory-snapshots",
+                    &[
+                        ("count", snapshots.len().into()),
+                        (
+                            "size",
+                            format_size(snapshots::excluded_churn(&paths, &snapshots)).into(),
+                        ),
+                    ],
+                )
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the fixed-path exclusions pointing at paths that no longer exist (e.g. deleted
+/// projects), which otherwise linger in the Time Machine preferences forever
+pub fn stale_fixed_paths(skip_paths: &[PathBuf]) -> Vec<PathBuf> {
+    skip_paths
+        .iter()
+        .filter(|path| !path.exists() && !path.is_symlink())
+        .cloned()
+        .collect()
+}
+
+/// Removes the stale fixed-path exclusions after confirmation (skipped with `yes`), or
+/// re-runs itself through sudo when root privileges are needed, with a single question
+pub fn run_clean(privilege_helper: Option<String>, yes: bool) -> Result<()> {
+    let stale = stale_fixed_paths(&explorer::fixed_path_exclusions());
+    if stale.is_empty() {
+        println!("{}", tr("clean-none", &[]));
+        return Ok(());
+    }
+
+    for path in &stale {
+        println!("{}", tr("clean-entry", &path_arg(path)));
+    }
+    // The elevated run shows the command and asks once, instead of a second question
+    let backend = backend_with_privilege_helper(privilege_helper);
+    if backend.requires_elevation(ExclusionType::Fixed) {
+        return privileged::run_elevated(
+            &["clean".to_string(), "--yes".to_string()],
+            &tr("elevated-fixed-path-reason", &[]),
+        );
+    }
+    let question = tr("clean-question", &[("count", stale.len().into())]);
+    if !yes && !privileged::confirm(&question)? {
+        println!("{}", tr("clean-unchanged", &[]));
+        return Ok(());
+    }
+
+    let mut removed = 0;
+    for path in &stale {
+        // Not `include`: the missing path cannot be checked with `tmutil isexcluded`
+        if backend.remove(path, ExclusionType::Fixed) {
+            removed += 1;
+        } else {
+            eprintln!("{}", tr("inventory-remove-failed", &path_arg(path)));
+        }
+    }
+    println!("{}", tr("clean-removed", &[("count", removed.into())]));
+    Ok(())
+}
+
+/// Removes every exclusion under the given paths (sticky and fixed-path, whoever created
+/// them), e.g. when archiving a project whose build artifacts should now be backed up.
+/// With `dry_run`, only lists them; otherwise asks for confirmation (skipped with `yes`),
+/// or re-runs itself through sudo when fixed-path exclusions need root privileges, with
+/// the confirmation of the elevated command as the single question.
+pub fn run_include_recursive(
+    path_strs: &[String],
+    privilege_helper: Option<String>,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    let skip_paths = explorer::fixed_path_exclusions();
+    let mut roots = Vec::new();
+    let mut entries = Vec::new();
+    for root in explorer::expand_path_args(path_strs)? {
+        entries.extend(inventory(&root, &skip_paths));
+        roots.push(root);
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries.dedup();
+
+    if entries.is_empty() {
+        println!("{}", tr("include-recursive-none", &[]));
+        return Ok(());
+    }
+    for entry in &entries {
+        println!("{}", tr("inventory-entry", &entry_args(entry)));
+    }
+    if dry_run {
+        println!(
+            "\n{}",
+            tr(
+                "include-recursive-dry-run",
+                &[("count", entries.len().into())]
+            )
+        );
+        return Ok(());
+    }
+    let backend = backend_with_privilege_helper(privilege_helper);
+    let has_fixed = entries
+        .iter()
+        .any(|entry| entry.mechanism == ExclusionMechanism::FixedPath);
+    if has_fixed && backend.requires_elevation(ExclusionType::Fixed) {
+        let mut args = vec![
+            "include".to_string(),
+            "--recursive".to_string(),
+            "--yes".to_string(),
+        ];
+        // Absolute paths keep `~` and relative paths independent of root's environment
+        args.extend(roots.iter().map(|root| root.display().to_string()));
+        // The elevated run shows the command and asks once, instead of a second question
+        return privileged::run_elevated(&args, &tr("elevated-fixed-path-reason", &[]));
+    }
+    let question = tr(
+        "include-recursive-question",
+        &[("count", entries.len().into())],
+    );
+    if !yes && !privileged::confirm(&question)? {
+        println!("{}", tr("include-recursive-unchanged", &[]));
+        return Ok(());
+    }
+
+    let mut removed = 0;
+    for entry in &entries {
+        let exclusion_type = match entry.mechanism {
+            ExclusionMechanism::FixedPath => ExclusionType::Fixed,
+            _ => ExclusionType::Sticky,
+        };
+        if backend.remove(&entry.path, exclusion_type) {
+            removed += 1;
+        } else {
+            eprintln!("{}", tr("inventory-remove-failed", &path_arg(&entry.path)));
+        }
+    }
+    println!(
+        "{}",
+        tr("include-recursive-removed", &[("count", removed.into())])
+    );
+    Ok(())
+}
+
+/// Argument of the messages about a single path
+fn path_arg(path: &Path) -> [(&'static str, fluent_bundle::FluentValue<'static>); 1] {
+    [("path", path.display().to_string().into())]
+}
+
+/// Arguments of the messages listing an exclusion
+fn entry_args(entry: &InventoryEntry) -> [(&'static str, fluent_bundle::FluentValue<'static>); 2] {
+    [
+        ("path", entry.path.display().to_string().into()),
+        ("mechanism", entry.mechanism.to_string().into()),
+    ]
+}
@@ -1,2 +1,33 @@
+pub mod backend;
+pub mod cloud;
 pub mod config;
+pub mod dropbox;
+pub mod error;
+pub mod estimate;
+pub mod events;
+pub mod exit;
+pub mod explain;
 pub mod explorer;
+pub mod export;
+pub mod find;
+pub mod gitignore;
+pub mod heuristics;
+pub mod i18n;
+pub mod icloud;
+pub mod impact;
+pub mod import;
+pub mod inventory;
+pub mod onboarding;
+pub mod output;
+pub mod pagination;
+pub mod presets;
+pub mod privileged;
+pub mod progress;
+pub mod queue;
+pub mod remote;
+pub mod rules;
+pub mod size;
+pub mod snapshots;
+pub mod suggest;
+pub mod tui;
+pub mod users;
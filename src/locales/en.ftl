@@ -0,0 +1,255 @@
+# User-facing messages of asimeow, in Fluent syntax (https://projectfluent.org).
+# Every message must exist here; other languages fall back to English.
+
+## Configuration
+
+config-using = Using configuration: { $path }
+config-created = ✅ Created default config file at: { $path }
+config-edit-roots = You may want to edit the file to customize the root paths for your system.
+config-exists = Config file already exists at: { $path }
+config-not-found = Specified config file not found: { $path }
+config-none = No configuration file found. Run 'asimeow init' to create one in ~/.config/asimeow/ or 'asimeow init --local' for the current directory.
+config-no-roots = No root paths or exclude_paths defined in config file
+config-read-failed = Failed to read config file: { $path }
+config-parse-failed = Failed to parse config file: { $path }
+config-unknown-rule = Unknown rule '{ $rule }'. Configured rules: { $rules }
+config-unknown-root = Unknown root '{ $root }'. Configured roots: { $roots }
+config-no-rules-selected = Warning: no enabled rules match the selected filters
+config-protect-question = Add { $count } paths to the protected entries of { $path }? Comments in the file will be lost
+config-protected-added = 🛡️  Added to the protected entries: { $entry }
+config-unchanged = Config left unchanged
+
+## Scan summary
+
+scan-processed = Total paths processed: { $count }
+scan-exclusions-found = Total exclusions found: { $count }
+scan-newly-excluded = Newly excluded from Time Machine: { $count }
+scan-reported = Reported only (not excluded): { $count }
+scan-warnings = Warnings: { $count }
+scan-candidates = Cache-like candidates (not excluded): { $count }
+scan-crowded = Directories with too many entries: { $count }
+scan-orphans = Orphaned build outputs (not excluded): { $count }
+scan-cloud-synced = Exclusions still uploaded by a cloud service: { $count }
+scan-dataless = Cloud-only directories skipped (not downloaded): { $count }
+scan-total-size = Total size of the exclusions: { $size }
+scan-duration = Scan time: { $duration }
+scan-roots-header = Roots:
+scan-root = { $root }: { $processed } paths, { $exclusions } exclusions in { $duration }
+scan-rules-header = Rules:
+scan-rule = { $rule }: { $matches ->
+    [one] 1 match
+   *[other] { $matches } matches
+    }, { $exclusions } exclusions ({ $newly_excluded } new)
+
+## Scan errors
+
+errors-header = ⚠️  { $count ->
+    [one] 1 error
+   *[other] { $count } errors
+    } while scanning:
+errors-group = { $kind }: { $count } in { $directories }
+errors-more = and { $count } more
+errors-verbose-hint = Run with -v to see each error as it occurs
+error-kind-permission-denied = Permission denied
+error-kind-not-found = Not found
+error-kind-not-a-directory = Not a directory
+error-kind-io = I/O error
+error-kind-exclusion = Exclusion failed
+error-kind-other = Other error
+
+## Scan output
+
+scan-excluded = ✅ { $path } - { $rule }
+scan-already-excluded = 🟡 { $path } - { $rule }
+scan-dry-run = 📋 { $path } - { $rule } (dry run)
+scan-warn = ⚠️  { $path } - { $rule } [{ $severity }]
+scan-report-only = 📋 { $path } - { $rule } [{ $severity }] (report only)
+scan-review = 🔎 { $path } - { $rule } ({ $size })
+scan-review-prompt = Exclude it? [y]es / [N]o / [a]lways for this rule / ne[v]er:
+scan-nix-store = ℹ️  Skipping the Nix store: { $path }
+scan-nix-store-size = ℹ️  Skipping the Nix store: { $path } ({ $size })
+scan-crowded-directory = 🚧 { $path } - { $count } entries (more than { $max })
+scan-crowded-stop = 🚧 { $path } - { $count } entries (more than { $max }), not descending
+scan-gitignore-candidate = 💡 { $path } - gitignore ({ $size })
+scan-orphan = 👻 { $path } - orphaned build output ({ $size }, no project marker found)
+scan-cache-candidate = 🔎 { $path } - looks like a cache ({ $size }, { $reasons })
+scan-cloud-add-backend = ⚠️  { $path } - { $rule }: still uploaded by { $service }, as Time Machine exclusions do not stop cloud sync (add `{ $backend }` to the backends of the rule)
+scan-cloud-move-out = ⚠️  { $path } - { $rule }: still uploaded by { $service }, as Time Machine exclusions do not stop cloud sync (move it out of the { $service } folder)
+scan-protected-excluded = ⚠️  Protected path is excluded from Time Machine: { $path } (run 'asimeow include' to restore it)
+scan-report-only-backend = ℹ️  Time Machine is not available on this system: exclusions are only reported, as with --dry-run
+scan-command-no-path = Warning: exclusion command '{ $command }' did not print an existing path
+scan-exclude-path-skipped = Warning: skipping exclude path '{ $entry }': { $error }
+scan-outside-confinement = Warning: skipping { $path } - { $rule } (outside { $dir })
+
+## Listing
+
+list-contents = Listing contents of: { $path }
+list-status-directory = Status of directory: { $path }
+list-status-file = Status of file: { $path }
+list-fixed-path = (fixed path)
+list-empty = (empty directory)
+list-legend = Legend:
+list-legend-excluded = 🟡 - Excluded from Time Machine
+list-legend-included = - Included in Time Machine
+list-legend-directory = / - Directory
+list-legend-fixed-path = { list-fixed-path } - Fixed-path exclusion of the Time Machine preferences
+list-entry-error = Error accessing entry: { $error }
+
+## Exclude and include
+
+exclude-would = 📋 Would exclude: { $path }
+exclude-already = 🟡 Already excluded: { $path }
+exclude-done = ✅ Successfully excluded: { $path }
+exclude-failed = ❌ Failed to exclude: { $path }
+exclude-failed-count = Failed to exclude { $count } paths
+include-already = Already included: { $path }
+include-done = ✅ Successfully included: { $path }
+include-failed = ❌ Failed to include: { $path }
+include-failed-count = Failed to include { $count } paths
+
+## Explain
+
+explain-outcome-exclude = would be excluded
+explain-outcome-warn = would be flagged with a warning
+explain-outcome-report = would be reported only
+explain-outcome-skipped = skipped ({ $reason })
+explain-skip-marker = { $marker } marker
+explain-skip-symlink = symlink target missing or outside the allowed bases
+explain-skip-size = { $size } is below { $min } MB
+explain-skip-keep = matches a keep entry
+explain-skip-protected = matches a protected entry
+explain-mechanism-sticky = sticky exclusion attribute
+explain-mechanism-fixed-path = fixed-path exclusion
+explain-mechanism-inherited = inherited from excluded ancestor { $path }
+explain-header = Explaining: { $path }
+explain-excluded = 🟡 Excluded from Time Machine ({ $mechanism })
+explain-included = Included in Time Machine
+explain-config-warning = Warning: { $error }; configured rules were not checked
+explain-keep-entry = 🛡️  Matches a keep entry: no rule may exclude it
+explain-protected-entry = 🛡️  Matches a protected entry: no rule may exclude it
+explain-keep-marker = 🛡️  { $marker } found in { $folder }: no rule may exclude it
+explain-no-rule = No configured rule targets this path or its ancestors
+explain-responsible = Responsible:
+explain-responsibility = - { $source } (matched in { $folder }): { $target } { $outcome }
+why-not-header = Why not excluded: { $path }
+why-not-already-excluded = 🟡 The path is already excluded from Time Machine
+why-not-filesystem-root = ❌ The root of the file system is never excluded
+why-not-outside-roots = ❌ Not under any configured root ({ $roots }): scans never reach it
+why-not-nix-store = 🛑 { $folder } is in the Nix store, which scans skip
+why-not-ignored-path = 🛑 { $folder } is skipped: its path matches the ignore pattern '{ $pattern }'
+why-not-ignored-name = 🛑 { $folder } is skipped: its name matches the ignore pattern '{ $pattern }'
+why-not-root-ignored = 🛑 { $folder } is skipped: its path within its root matches the root ignore pattern '{ $pattern }'
+why-not-excluded-ancestor = 🟡 { $folder } is excluded from Time Machine, and so is everything below it
+why-not-nobackup = 🟡 { $folder } has a { $marker } marker: it is excluded as a whole
+why-not-under-target = 🟡 It is under { $target }, targeted by rule '{ $rule }' matched in { $folder }: scans do not descend into it
+why-not-descend-stop = 🛑 Rule '{ $rule }' matched in { $folder } with descend: stop, so its subdirectories are not scanned
+why-not-descend-depth = 🛑 Rule '{ $rule }' matched in { $folder } with descend: depth({ $depth }), so folders deeper than { $depth } levels are not scanned
+why-not-no-candidate = ❌ No configured rule has an exclusion entry naming '{ $name }'
+why-not-rule-disabled = ⚪ Rule '{ $rule }' could target it from { $folder } but is disabled
+why-not-rule-outside = ❌ Rule '{ $rule }': { $folder } is outside its applies_to ({ $applies_to })
+why-not-rule-no-match = ❌ Rule '{ $rule }': no entry of { $folder } matches file_match '{ $file_match }'
+why-not-rule-no-match-files = ❌ Rule '{ $rule }': no entry of { $folder } matches file_match '{ $file_match }' (the trailing / only matches directories, a file has that name)
+why-not-rule-missing = ❌ Rule '{ $rule }': { $folder } lacks the required { $requires }
+why-not-rule-excludes = ✅ Rule '{ $rule }' matches in { $folder } and targets it: the next scan excludes it, unless the traversal stops before
+why-not-rule-outcome = 🛑 Rule '{ $rule }' matches in { $folder } and targets it, but it { $outcome }
+why-not-rule-case = ❌ Rule '{ $rule }': its exclusion entry '{ $entry }' differs from '{ $relative }' by case
+why-not-rule-unresolved = ❌ Rule '{ $rule }' matches in { $folder } but none of its exclusions resolves to the path
+test-rule-header = Testing rule '{ $rule }' against: { $folder }
+test-rule-disabled = ⚠️  The rule is disabled, a scan would not run it
+test-rule-applies-everywhere = ✅ applies_to: the rule applies everywhere
+test-rule-applies = ✅ applies_to: the folder is within { $applies_to }
+test-rule-outside = ❌ applies_to: the folder is outside { $applies_to }
+test-rule-no-match = ❌ file_match '{ $file_match }': no entry matches
+test-rule-match = ✅ file_match '{ $file_match }': matches { $entry }
+test-rule-missing = ❌ requires: { $required } is missing
+test-rule-found = ✅ requires: { $required } found
+test-rule-keep-marker = 🛑 { $marker } found in { $folder }: no exclusions would be made
+test-rule-exclusions = Exclusions:
+test-rule-no-exclusion = (no exclusion entry resolves to an existing path)
+test-rule-traversal = Traversal:
+test-rule-descend-stop = No subdirectory of { $folder } would be explored
+test-rule-descend-continue = Excluded paths would not be explored, other subdirectories would
+test-rule-descend-depth = Subdirectories would be explored at most { $depth } levels below { $folder }
+test-rule-recursive = The exclusions would also be applied in every subdirectory (recursive)
+
+## Remote commands
+
+remote-summary = Summary of { $host }:
+remote-errors = Errors: { $count }
+
+## Exit status
+
+exit-partial-failure = The scan completed with { $count ->
+    [one] 1 error
+   *[other] { $count } errors
+    }
+exit-changes = { $count ->
+    [one] 1 path is
+   *[other] { $count } paths are
+    } not excluded yet
+
+## Onboarding
+
+onboarding-welcome = 👋 Welcome to asimeow! No configuration file was found.
+onboarding-intro = asimeow excludes dependency and build folders (node_modules, target, .venv, ...) from Time Machine backups.
+onboarding-create = Create a default configuration at { $path }?
+onboarding-init-later = Run 'asimeow init' to create one later.
+onboarding-created = ✅ Created default config file at: { $path }
+onboarding-scanning = 🔎 Looking for folders to exclude (read-only, nothing is changed yet)...
+onboarding-nothing = Nothing new to exclude: your backups are already lean.
+onboarding-more = ... and { $count } more
+onboarding-savings = Estimated savings: { $count } paths, { $size }
+onboarding-apply = Apply these exclusions now?
+onboarding-apply-later = Nothing was excluded. Run 'asimeow' to apply them later.
+onboarding-excluded = ✅ Excluded { $count } paths from Time Machine backups
+
+## Root privileges
+
+elevated-fixed-path-reason = Fixed-path exclusions are stored in the Time Machine preferences, which only root can change
+elevated-question = Run this command as root?
+elevated-cancelled = Cancelled: the operation requires root privileges
+elevated-failed = Privileged command failed ({ $status })
+elevation-required-fixed-paths = Fixed-path exclusions require root privileges: run with sudo, or set `privilege_helper` in the config (e.g. `sudo -n`)
+elevation-required-fixed-rules = Rules { $rules } create fixed-path exclusions, which require root privileges: run with sudo, or set `privilege_helper` in the config (e.g. `sudo -n`)
+
+## Inventory, clean and recursive include
+
+inventory-header = Exclusions under: { $path }
+inventory-no-backup = ⚠️  No backup found (is the backup disk connected?): only sizes on disk are shown
+inventory-entry = 🟡 { $path } ({ $mechanism })
+inventory-entry-missing = 🟡 { $path } ({ $mechanism }, missing)
+inventory-impact = → { $size } on disk, { $backup } in the newest backup
+inventory-impact-not-in-backup = → { $size } on disk, not in the newest backup
+inventory-total = Total exclusions: { $count } ({ $sticky } sticky, { $fixed } fixed-path)
+inventory-space = Space excluded: { $size } on disk, { $backup } still held by the newest backup
+inventory-space-listed = Space excluded by the listed entries: { $size } on disk, { $backup } still held by the newest backup
+inventory-snapshots = Local snapshots: { $count }, keeping ~{ $size } of excluded churn
+inventory-remove-failed = ⚠️  Failed to remove the exclusion of { $path }
+clean-none = No stale fixed-path exclusions
+clean-entry = 🧹 { $path } (fixed-path exclusion, missing)
+clean-question = Remove { $count } stale fixed-path exclusions?
+clean-unchanged = Time Machine preferences left unchanged
+clean-removed = Removed { $count } stale fixed-path exclusions
+include-recursive-none = No exclusions found
+include-recursive-dry-run = Dry run: { $count } exclusions would be removed
+include-recursive-question = Remove { $count } exclusions?
+include-recursive-unchanged = Time Machine settings left unchanged
+include-recursive-removed = Removed { $count } exclusions
+
+## Find, estimate and export
+
+find-header = Excluded by rule '{ $rule }':
+find-total = Total: { $count } paths, { $size }
+estimate-header = Estimate (read-only, nothing was changed)
+estimate-new = Would exclude: { $count } paths, { $size }
+estimate-already = Already excluded: { $count } paths, { $size }
+export-done = ✅ Exported { $count } exclusions to { $path }
+size-cache-save-failed = Warning: could not save the size cache: { $error }
+
+## Rule suggestions
+
+suggest-none = No new rules to suggest
+suggest-header = 💡 Suggested rules ({ $count }):
+suggest-found-in = # found in: { $paths }
+suggest-question = Append { $count } rules to { $path }? Comments in the file will be lost
+suggest-appended = Appended { $count } rules to { $path }
@@ -0,0 +1,255 @@
+# Messaggi di asimeow in italiano, in sintassi Fluent (https://projectfluent.org).
+# I messaggi mancanti sono mostrati in inglese.
+
+## Configurazione
+
+config-using = Configurazione in uso: { $path }
+config-created = ✅ File di configurazione predefinito creato in: { $path }
+config-edit-roots = Puoi modificare il file per adattare le cartelle radice al tuo sistema.
+config-exists = Il file di configurazione esiste già in: { $path }
+config-not-found = File di configurazione indicato non trovato: { $path }
+config-none = Nessun file di configurazione trovato. Esegui 'asimeow init' per crearne uno in ~/.config/asimeow/ o 'asimeow init --local' per la cartella corrente.
+config-no-roots = Nessuna cartella radice né exclude_paths definiti nel file di configurazione
+config-read-failed = Lettura del file di configurazione non riuscita: { $path }
+config-parse-failed = Analisi del file di configurazione non riuscita: { $path }
+config-unknown-rule = Regola '{ $rule }' sconosciuta. Regole configurate: { $rules }
+config-unknown-root = Radice '{ $root }' sconosciuta. Radici configurate: { $roots }
+config-no-rules-selected = Avviso: nessuna regola attiva corrisponde ai filtri selezionati
+config-protect-question = Aggiungere { $count } percorsi alle voci protette di { $path }? I commenti del file andranno persi
+config-protected-added = 🛡️  Aggiunto alle voci protette: { $entry }
+config-unchanged = Configurazione non modificata
+
+## Riepilogo della scansione
+
+scan-processed = Percorsi analizzati: { $count }
+scan-exclusions-found = Esclusioni trovate: { $count }
+scan-newly-excluded = Nuove esclusioni da Time Machine: { $count }
+scan-reported = Solo segnalati (non esclusi): { $count }
+scan-warnings = Avvisi: { $count }
+scan-candidates = Candidati simili a cache (non esclusi): { $count }
+scan-crowded = Cartelle con troppi elementi: { $count }
+scan-orphans = Output di build orfani (non esclusi): { $count }
+scan-cloud-synced = Esclusioni ancora caricate da un servizio cloud: { $count }
+scan-dataless = Cartelle solo nel cloud saltate (non scaricate): { $count }
+scan-total-size = Dimensione totale delle esclusioni: { $size }
+scan-duration = Durata della scansione: { $duration }
+scan-roots-header = Radici:
+scan-root = { $root }: { $processed } percorsi, { $exclusions } esclusioni in { $duration }
+scan-rules-header = Regole:
+scan-rule = { $rule }: { $matches ->
+    [one] 1 corrispondenza
+   *[other] { $matches } corrispondenze
+    }, { $exclusions } esclusioni ({ $newly_excluded } nuove)
+
+## Errori della scansione
+
+errors-header = ⚠️  { $count ->
+    [one] 1 errore
+   *[other] { $count } errori
+    } durante la scansione:
+errors-group = { $kind }: { $count } in { $directories }
+errors-more = e altri { $count }
+errors-verbose-hint = Esegui con -v per vedere ogni errore quando si verifica
+error-kind-permission-denied = Permesso negato
+error-kind-not-found = Non trovato
+error-kind-not-a-directory = Non è una cartella
+error-kind-io = Errore di I/O
+error-kind-exclusion = Esclusione non riuscita
+error-kind-other = Altro errore
+
+## Output della scansione
+
+scan-excluded = ✅ { $path } - { $rule }
+scan-already-excluded = 🟡 { $path } - { $rule }
+scan-dry-run = 📋 { $path } - { $rule } (prova)
+scan-warn = ⚠️  { $path } - { $rule } [{ $severity }]
+scan-report-only = 📋 { $path } - { $rule } [{ $severity }] (solo segnalato)
+scan-review = 🔎 { $path } - { $rule } ({ $size })
+scan-review-prompt = Escluderlo? [y] sì / [N] no / [a] sempre per questa regola / [v] mai:
+scan-nix-store = ℹ️  Lo store di Nix viene saltato: { $path }
+scan-nix-store-size = ℹ️  Lo store di Nix viene saltato: { $path } ({ $size })
+scan-crowded-directory = 🚧 { $path } - { $count } elementi (più di { $max })
+scan-crowded-stop = 🚧 { $path } - { $count } elementi (più di { $max }), senza scendere
+scan-gitignore-candidate = 💡 { $path } - gitignore ({ $size })
+scan-orphan = 👻 { $path } - output di build orfano ({ $size }, nessun marcatore di progetto trovato)
+scan-cache-candidate = 🔎 { $path } - sembra una cache ({ $size }, { $reasons })
+scan-cloud-add-backend = ⚠️  { $path } - { $rule }: ancora caricato da { $service }, perché le esclusioni di Time Machine non fermano la sincronizzazione cloud (aggiungi `{ $backend }` ai backend della regola)
+scan-cloud-move-out = ⚠️  { $path } - { $rule }: ancora caricato da { $service }, perché le esclusioni di Time Machine non fermano la sincronizzazione cloud (spostalo fuori dalla cartella di { $service })
+scan-protected-excluded = ⚠️  Un percorso protetto è escluso da Time Machine: { $path } (esegui 'asimeow include' per ripristinarlo)
+scan-report-only-backend = ℹ️  Time Machine non è disponibile su questo sistema: le esclusioni sono solo segnalate, come con --dry-run
+scan-command-no-path = Avviso: il comando di esclusione '{ $command }' non ha stampato un percorso esistente
+scan-exclude-path-skipped = Avviso: percorso di esclusione '{ $entry }' saltato: { $error }
+scan-outside-confinement = Avviso: { $path } - { $rule } saltato (fuori da { $dir })
+
+## Elenco
+
+list-contents = Contenuto di: { $path }
+list-status-directory = Stato della cartella: { $path }
+list-status-file = Stato del file: { $path }
+list-fixed-path = (percorso fisso)
+list-empty = (cartella vuota)
+list-legend = Legenda:
+list-legend-excluded = 🟡 - Escluso da Time Machine
+list-legend-included = - Incluso in Time Machine
+list-legend-directory = / - Cartella
+list-legend-fixed-path = { list-fixed-path } - Esclusione a percorso fisso delle preferenze di Time Machine
+list-entry-error = Errore di accesso alla voce: { $error }
+
+## Esclusione e inclusione
+
+exclude-would = 📋 Verrebbe escluso: { $path }
+exclude-already = 🟡 Già escluso: { $path }
+exclude-done = ✅ Escluso: { $path }
+exclude-failed = ❌ Esclusione non riuscita: { $path }
+exclude-failed-count = Esclusione non riuscita per { $count } percorsi
+include-already = Già incluso: { $path }
+include-done = ✅ Incluso: { $path }
+include-failed = ❌ Inclusione non riuscita: { $path }
+include-failed-count = Inclusione non riuscita per { $count } percorsi
+
+## Spiegazioni
+
+explain-outcome-exclude = verrebbe escluso
+explain-outcome-warn = verrebbe segnalato con un avviso
+explain-outcome-report = verrebbe solo segnalato
+explain-outcome-skipped = saltato ({ $reason })
+explain-skip-marker = marcatore { $marker }
+explain-skip-symlink = destinazione del link simbolico mancante o fuori dalle basi consentite
+explain-skip-size = { $size } è sotto { $min } MB
+explain-skip-keep = corrisponde a una voce keep
+explain-skip-protected = corrisponde a una voce protetta
+explain-mechanism-sticky = attributo di esclusione permanente
+explain-mechanism-fixed-path = esclusione a percorso fisso
+explain-mechanism-inherited = ereditato dall'antenato escluso { $path }
+explain-header = Spiegazione di: { $path }
+explain-excluded = 🟡 Escluso da Time Machine ({ $mechanism })
+explain-included = Incluso in Time Machine
+explain-config-warning = Avviso: { $error }; le regole configurate non sono state verificate
+explain-keep-entry = 🛡️  Corrisponde a una voce keep: nessuna regola può escluderlo
+explain-protected-entry = 🛡️  Corrisponde a una voce protetta: nessuna regola può escluderlo
+explain-keep-marker = 🛡️  { $marker } trovato in { $folder }: nessuna regola può escluderlo
+explain-no-rule = Nessuna regola configurata riguarda questo percorso o i suoi antenati
+explain-responsible = Responsabili:
+explain-responsibility = - { $source } (corrispondenza in { $folder }): { $target } { $outcome }
+why-not-header = Perché non è escluso: { $path }
+why-not-already-excluded = 🟡 Il percorso è già escluso da Time Machine
+why-not-filesystem-root = ❌ La radice del file system non viene mai esclusa
+why-not-outside-roots = ❌ Non si trova sotto nessuna radice configurata ({ $roots }): le scansioni non lo raggiungono mai
+why-not-nix-store = 🛑 { $folder } è nello store di Nix, che le scansioni saltano
+why-not-ignored-path = 🛑 { $folder } viene saltato: il suo percorso corrisponde al pattern ignore '{ $pattern }'
+why-not-ignored-name = 🛑 { $folder } viene saltato: il suo nome corrisponde al pattern ignore '{ $pattern }'
+why-not-root-ignored = 🛑 { $folder } viene saltato: il suo percorso nella radice corrisponde al pattern ignore della radice '{ $pattern }'
+why-not-excluded-ancestor = 🟡 { $folder } è escluso da Time Machine, e con esso tutto ciò che contiene
+why-not-nobackup = 🟡 { $folder } ha un marcatore { $marker }: è escluso per intero
+why-not-under-target = 🟡 Si trova sotto { $target }, indicato dalla regola '{ $rule }' con corrispondenza in { $folder }: le scansioni non vi scendono
+why-not-descend-stop = 🛑 La regola '{ $rule }' corrisponde in { $folder } con descend: stop, quindi le sue sottocartelle non vengono analizzate
+why-not-descend-depth = 🛑 La regola '{ $rule }' corrisponde in { $folder } con descend: depth({ $depth }), quindi le cartelle a più di { $depth } livelli non vengono analizzate
+why-not-no-candidate = ❌ Nessuna regola configurata ha una voce di esclusione che nomina '{ $name }'
+why-not-rule-disabled = ⚪ La regola '{ $rule }' potrebbe indicarlo da { $folder } ma è disattivata
+why-not-rule-outside = ❌ Regola '{ $rule }': { $folder } è fuori dal suo applies_to ({ $applies_to })
+why-not-rule-no-match = ❌ Regola '{ $rule }': nessun elemento di { $folder } corrisponde a file_match '{ $file_match }'
+why-not-rule-no-match-files = ❌ Regola '{ $rule }': nessun elemento di { $folder } corrisponde a file_match '{ $file_match }' (la / finale corrisponde solo alle cartelle, un file ha quel nome)
+why-not-rule-missing = ❌ Regola '{ $rule }': a { $folder } manca il requisito { $requires }
+why-not-rule-excludes = ✅ La regola '{ $rule }' corrisponde in { $folder } e lo indica: la prossima scansione lo esclude, a meno che l'attraversamento si fermi prima
+why-not-rule-outcome = 🛑 La regola '{ $rule }' corrisponde in { $folder } e lo indica, ma { $outcome }
+why-not-rule-case = ❌ Regola '{ $rule }': la sua voce di esclusione '{ $entry }' differisce da '{ $relative }' per maiuscole e minuscole
+why-not-rule-unresolved = ❌ La regola '{ $rule }' corrisponde in { $folder } ma nessuna delle sue esclusioni porta al percorso
+test-rule-header = Prova della regola '{ $rule }' su: { $folder }
+test-rule-disabled = ⚠️  La regola è disattivata, una scansione non la eseguirebbe
+test-rule-applies-everywhere = ✅ applies_to: la regola si applica ovunque
+test-rule-applies = ✅ applies_to: la cartella è dentro { $applies_to }
+test-rule-outside = ❌ applies_to: la cartella è fuori da { $applies_to }
+test-rule-no-match = ❌ file_match '{ $file_match }': nessun elemento corrisponde
+test-rule-match = ✅ file_match '{ $file_match }': corrisponde a { $entry }
+test-rule-missing = ❌ requires: { $required } manca
+test-rule-found = ✅ requires: { $required } trovato
+test-rule-keep-marker = 🛑 { $marker } trovato in { $folder }: nessuna esclusione verrebbe applicata
+test-rule-exclusions = Esclusioni:
+test-rule-no-exclusion = (nessuna voce di esclusione porta a un percorso esistente)
+test-rule-traversal = Attraversamento:
+test-rule-descend-stop = Nessuna sottocartella di { $folder } verrebbe esplorata
+test-rule-descend-continue = I percorsi esclusi non verrebbero esplorati, le altre sottocartelle sì
+test-rule-descend-depth = Le sottocartelle verrebbero esplorate al massimo { $depth } livelli sotto { $folder }
+test-rule-recursive = Le esclusioni verrebbero applicate anche in ogni sottocartella (recursive)
+
+## Comandi remoti
+
+remote-summary = Riepilogo di { $host }:
+remote-errors = Errori: { $count }
+
+## Stato di uscita
+
+exit-partial-failure = La scansione è terminata con { $count ->
+    [one] 1 errore
+   *[other] { $count } errori
+    }
+exit-changes = { $count ->
+    [one] 1 percorso non è ancora escluso
+   *[other] { $count } percorsi non sono ancora esclusi
+    }
+
+## Primo avvio
+
+onboarding-welcome = 👋 Benvenuto in asimeow! Non è stato trovato alcun file di configurazione.
+onboarding-intro = asimeow esclude dai backup di Time Machine le cartelle di dipendenze e di build (node_modules, target, .venv, ...).
+onboarding-create = Creare una configurazione predefinita in { $path }?
+onboarding-init-later = Esegui 'asimeow init' per crearla in seguito.
+onboarding-created = ✅ File di configurazione predefinito creato in: { $path }
+onboarding-scanning = 🔎 Ricerca delle cartelle da escludere (sola lettura, ancora nessuna modifica)...
+onboarding-nothing = Niente di nuovo da escludere: i tuoi backup sono già leggeri.
+onboarding-more = ... e altri { $count }
+onboarding-savings = Risparmio stimato: { $count } percorsi, { $size }
+onboarding-apply = Applicare ora queste esclusioni?
+onboarding-apply-later = Nessuna esclusione applicata. Esegui 'asimeow' per applicarle in seguito.
+onboarding-excluded = ✅ { $count } percorsi esclusi dai backup di Time Machine
+
+## Privilegi di root
+
+elevated-fixed-path-reason = Le esclusioni a percorso fisso sono salvate nelle preferenze di Time Machine, che solo root può modificare
+elevated-question = Eseguire questo comando come root?
+elevated-cancelled = Annullato: l'operazione richiede i privilegi di root
+elevated-failed = Comando privilegiato non riuscito ({ $status })
+elevation-required-fixed-paths = Le esclusioni a percorso fisso richiedono i privilegi di root: esegui con sudo, o imposta `privilege_helper` nella configurazione (ad es. `sudo -n`)
+elevation-required-fixed-rules = Le regole { $rules } creano esclusioni a percorso fisso, che richiedono i privilegi di root: esegui con sudo, o imposta `privilege_helper` nella configurazione (ad es. `sudo -n`)
+
+## Inventario, pulizia e inclusione ricorsiva
+
+inventory-header = Esclusioni in: { $path }
+inventory-no-backup = ⚠️  Nessun backup trovato (il disco di backup è collegato?): sono mostrate solo le dimensioni su disco
+inventory-entry = 🟡 { $path } ({ $mechanism })
+inventory-entry-missing = 🟡 { $path } ({ $mechanism }, mancante)
+inventory-impact = → { $size } su disco, { $backup } nel backup più recente
+inventory-impact-not-in-backup = → { $size } su disco, assente dal backup più recente
+inventory-total = Esclusioni totali: { $count } ({ $sticky } permanenti, { $fixed } a percorso fisso)
+inventory-space = Spazio escluso: { $size } su disco, { $backup } ancora occupati dal backup più recente
+inventory-space-listed = Spazio escluso dalle voci elencate: { $size } su disco, { $backup } ancora occupati dal backup più recente
+inventory-snapshots = Istantanee locali: { $count }, che conservano ~{ $size } di modifiche escluse
+inventory-remove-failed = ⚠️  Rimozione dell'esclusione di { $path } non riuscita
+clean-none = Nessuna esclusione a percorso fisso obsoleta
+clean-entry = 🧹 { $path } (esclusione a percorso fisso, mancante)
+clean-question = Rimuovere { $count } esclusioni a percorso fisso obsolete?
+clean-unchanged = Preferenze di Time Machine non modificate
+clean-removed = { $count } esclusioni a percorso fisso obsolete rimosse
+include-recursive-none = Nessuna esclusione trovata
+include-recursive-dry-run = Prova: verrebbero rimosse { $count } esclusioni
+include-recursive-question = Rimuovere { $count } esclusioni?
+include-recursive-unchanged = Impostazioni di Time Machine non modificate
+include-recursive-removed = { $count } esclusioni rimosse
+
+## Ricerca, stima ed esportazione
+
+find-header = Esclusi dalla regola '{ $rule }':
+find-total = Totale: { $count } percorsi, { $size }
+estimate-header = Stima (sola lettura, nessuna modifica)
+estimate-new = Verrebbero esclusi: { $count } percorsi, { $size }
+estimate-already = Già esclusi: { $count } percorsi, { $size }
+export-done = ✅ { $count } esclusioni esportate in { $path }
+size-cache-save-failed = Avviso: impossibile salvare la cache delle dimensioni: { $error }
+
+## Suggerimenti di regole
+
+suggest-none = Nessuna nuova regola da suggerire
+suggest-header = 💡 Regole suggerite ({ $count }):
+suggest-found-in = # trovate in: { $paths }
+suggest-question = Aggiungere { $count } regole a { $path }? I commenti del file andranno persi
+suggest-appended = { $count } regole aggiunte a { $path }
@@ -1,7 +1,30 @@
 use anyhow::Result;
+use asimeow::backend;
 use asimeow::config;
+use asimeow::estimate;
+use asimeow::exit;
+use asimeow::explain;
 use asimeow::explorer;
+use asimeow::export;
+use asimeow::find;
+use asimeow::i18n::{self, Lang};
+use asimeow::import;
+use asimeow::inventory;
+use asimeow::onboarding;
+use asimeow::output::{self, OutputFormat};
+use asimeow::pagination::Pagination;
+use asimeow::presets;
+use asimeow::privileged;
+use asimeow::remote;
+use asimeow::rules;
+use asimeow::size::SizeMode;
+use asimeow::snapshots;
+use asimeow::suggest;
+use asimeow::tui;
+use asimeow::users;
 use clap::{Parser, Subcommand};
+use std::io::Read;
+use std::process::ExitCode;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -22,6 +45,77 @@ struct Args {
     #[arg(short, long, default_value = "4")]
     threads: usize,
 
+    /// Only run rules having this tag (can be repeated)
+    #[arg(long = "only-tag", value_name = "TAG")]
+    only_tags: Vec<String>,
+
+    /// Skip rules having this tag (can be repeated)
+    #[arg(long = "skip-tag", value_name = "TAG")]
+    skip_tags: Vec<String>,
+
+    /// Only run the rule with this name (can be repeated)
+    #[arg(long = "rule", value_name = "NAME")]
+    rules: Vec<String>,
+
+    /// Also exclude the directories matching the name patterns of this file, one per line
+    /// like rsync's option, during this run only (can be repeated)
+    #[arg(long = "exclude-from", value_name = "FILE")]
+    exclude_from: Vec<String>,
+
+    /// Answer yes to every confirmation instead of asking, so scheduled runs never wait
+    /// for input (also the `assume_yes` config option)
+    #[arg(short, long, visible_alias = "non-interactive")]
+    yes: bool,
+
+    /// Ask before each new exclusion, showing its path, rule and size: yes, no, always
+    /// (for the rest of the rule's matches) or never (added to the protected entries)
+    #[arg(long)]
+    interactive: bool,
+
+    /// Show a live dashboard while scanning, with the directory each worker is processing,
+    /// the queue length, the exclusions so far and the errors, instead of the per-path output
+    #[arg(long, conflicts_with = "interactive")]
+    watch_progress: bool,
+
+    /// Format of the scan output: text, or a JSON summary (json) or JSON lines (ndjson)
+    /// with the exclusions and structured errors (kind, path, message, errno)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, conflicts_with_all = ["interactive", "watch_progress"])]
+    format: OutputFormat,
+
+    /// Scan the configured roots without changing anything and exit with 1 when paths
+    /// are not excluded yet (see the exit codes in the README)
+    #[arg(long, conflicts_with = "interactive")]
+    check: bool,
+
+    /// Language of the messages, detected from LC_ALL, LC_MESSAGES or LANG by default
+    #[arg(long, value_enum)]
+    lang: Option<Lang>,
+
+    /// Only scan this many levels of directories below each root, overriding the
+    /// `max_depth` settings (0 only scans the roots themselves)
+    #[arg(long, value_name = "LEVELS")]
+    max_depth: Option<usize>,
+
+    /// Only scan the configured root with this path or name, i.e. its last path component
+    /// (can be repeated)
+    #[arg(long = "root", value_name = "NAME_OR_PATH")]
+    roots: Vec<String>,
+
+    /// How sizes are measured (list --size, estimate, inventory): the length of the files
+    /// or the blocks allocated on disk, far smaller for sparse files such as Docker.raw
+    #[arg(long, value_enum, default_value_t = SizeMode::Apparent)]
+    size_mode: SizeMode,
+
+    /// Measure the exclusions found by a scan and show their total size in the summary
+    /// (slower, as each excluded directory is walked)
+    #[arg(long)]
+    sizes: bool,
+
+    /// Scan the home directory of every user in /Users with their own config, or else the
+    /// system config (-c, default /Library/Application Support/asimeow/config.yaml). Requires root
+    #[arg(long)]
+    all_users: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -47,48 +141,514 @@ enum Commands {
     List {
         /// Path to list exclusions for
         path: Option<String>,
+
+        /// Show the size of each entry, largest first. Sizes are computed in parallel and
+        /// cached for 15 minutes
+        #[arg(long)]
+        size: bool,
+
+        /// Compute the sizes again instead of using the cached ones
+        #[arg(long, requires = "size")]
+        refresh: bool,
+
+        #[command(flatten)]
+        pagination: Pagination,
     },
-    /// Explicitly exclude a single file or folder from Time Machine backups
+    /// Explicitly exclude files or folders from Time Machine backups
     Exclude {
-        /// Path to exclude from Time Machine backups
-        path: String,
+        /// Paths to exclude from Time Machine backups, or glob patterns expanded by asimeow
+        paths: Vec<String>,
+
+        /// Also read paths from stdin, one per line or NUL delimited (e.g. `find -print0`)
+        #[arg(long)]
+        stdin: bool,
+
+        /// Apply the configured rules recursively under the paths, instead of excluding
+        /// the paths themselves (the configured roots are ignored)
+        #[arg(long, conflicts_with = "fixed_path")]
+        by_rules: bool,
+
+        /// Create a fixed-path exclusion, kept when the item is deleted and recreated (requires root)
+        #[arg(long)]
+        fixed_path: bool,
     },
-    /// Explicitly include a single file or folder in Time Machine backups (remove exclusion)
+    /// Explicitly include files or folders in Time Machine backups (remove exclusions)
     Include {
-        /// Path to include in Time Machine backups
+        /// Paths to include in Time Machine backups, or glob patterns expanded by asimeow
+        paths: Vec<String>,
+
+        /// Also read paths from stdin, one per line or NUL delimited (e.g. `find -print0`)
+        #[arg(long)]
+        stdin: bool,
+
+        /// Remove a fixed-path exclusion instead of a sticky one (requires root)
+        #[arg(long)]
+        fixed_path: bool,
+
+        /// Remove every exclusion found under the paths (sticky and fixed-path), after
+        /// confirmation
+        #[arg(long, conflicts_with = "fixed_path")]
+        recursive: bool,
+
+        /// With --recursive, only list the exclusions that would be removed
+        #[arg(long, requires = "recursive")]
+        dry_run: bool,
+
+        /// With --recursive, remove them without asking for confirmation
+        #[arg(long, requires = "recursive")]
+        yes: bool,
+    },
+    /// Explain step by step whether a rule matches a directory, which exclusions would
+    /// result and how the traversal would be pruned, without changing anything
+    TestRule {
+        /// Name of the configured rule
+        rule: String,
+
+        /// Directory to evaluate the rule against
         path: String,
     },
+    /// Explain whether a path is excluded, through which mechanism (sticky, fixed-path or
+    /// inherited from an ancestor) and which configured rule is responsible
+    Explain {
+        /// Path to explain
+        path: String,
+    },
+    /// Explain why a scan does not exclude a path: where the traversal stops before reaching
+    /// it and why the rules that could target it do not match
+    WhyNot {
+        /// Path expected to be excluded
+        path: String,
+    },
+    /// List every item excluded from Time Machine under a path, whether or not asimeow
+    /// created the exclusion: sticky exclusion attributes and fixed-path exclusions
+    Inventory {
+        /// Path to inventory (default: the whole startup volume)
+        root: Option<String>,
+
+        /// Estimate the space each exclusion takes on disk and in the newest backup
+        /// (`tmutil uniquesize`)
+        #[arg(long)]
+        impact: bool,
+
+        #[command(flatten)]
+        pagination: Pagination,
+    },
+    /// List the local Time Machine snapshots and the space they keep for excluded items
+    /// modified since the oldest one
+    Snapshots {
+        /// Path whose exclusions are measured (default: the home directory)
+        root: Option<String>,
+    },
+    /// Remove the fixed-path exclusions of paths that no longer exist, after confirmation
+    Clean {
+        /// Remove them without asking for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Browse a directory in the terminal with the exclusion status, sizes and matching
+    /// rules of its entries, toggle exclusions, rescan it and apply the pending plan
+    Tui {
+        /// Directory to browse (default: the first configured root)
+        path: Option<String>,
+    },
+    /// Scan with the configured rules, only under the given paths (the configured roots
+    /// are ignored) or, without paths, under the configured roots like a plain run
+    Scan {
+        /// Paths to scan instead of the configured roots
+        paths: Vec<String>,
+    },
+    /// Run the rules against a directory instead of the configured roots, only reporting
+    /// what would be excluded (e.g. to iterate on rules against fixture trees)
+    Simulate {
+        /// Directory to scan (can be repeated)
+        #[arg(long = "root", value_name = "DIR", required = true)]
+        roots: Vec<String>,
+    },
+    /// Scan the configured roots without excluding anything and print how many paths
+    /// would be excluded and how much space they occupy
+    Estimate,
+    /// List the paths still excluded from Time Machine that a rule targets, with their
+    /// sizes, by scanning the configured roots with that rule alone (nothing is changed)
+    Find {
+        /// Rule to audit (can be repeated)
+        #[arg(long = "rule", value_name = "NAME", required = true)]
+        rules: Vec<String>,
+    },
+    /// Scan the configured roots without excluding anything and export the exclusion set
+    /// for another backup tool
+    Export {
+        /// Format of the export
+        #[arg(long, value_enum)]
+        format: export::ExportFormat,
+
+        /// Write the export to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Convert the configuration of another tool into an asimeow config, flagging
+    /// anything that cannot be converted
+    Import {
+        /// Tool to import from
+        #[arg(long, value_enum)]
+        from: import::ImportSource,
+
+        /// Path of the tool's script or config file (default: its usual location)
+        #[arg(long, value_name = "FILE")]
+        file: Option<String>,
+
+        /// Write the config to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Run an asimeow command on a remote Mac over SSH with the local config, streaming
+    /// its output back (e.g. `remote --host mac-mini.local -- --rule node`)
+    Remote {
+        /// SSH destination, e.g. `mac-mini.local` or `admin@build-1`
+        #[arg(long)]
+        host: String,
+
+        /// asimeow executable on the remote host
+        #[arg(long, default_value = "asimeow")]
+        remote_bin: String,
+
+        /// Arguments of the remote command (a scan when empty)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// List or apply built-in presets of well-known paths, or add curated rule packs
+    Presets {
+        #[command(subcommand)]
+        action: Option<PresetsCommand>,
+    },
+    /// Manage the rules of the config file
+    Rules {
+        #[command(subcommand)]
+        action: RulesCommand,
+    },
+    /// Scan without excluding anything and propose rules for unmatched cache-like
+    /// directories and report-only findings
+    Suggest {
+        /// Minimum size of an unmatched directory to be considered, in megabytes
+        #[arg(long, value_name = "MB")]
+        min_size_mb: Option<u64>,
+
+        /// Append the suggested rules to the config file, after confirmation
+        #[arg(long)]
+        append: bool,
+    },
 }
 
-fn main() -> Result<()> {
+#[derive(Subcommand, Debug)]
+enum PresetsCommand {
+    /// List the available presets and rule packs
+    List,
+    /// Merge the rules of a curated rule pack (ios, android, gamedev, ml, devops) into the config
+    Add {
+        /// Name of the rule pack to add
+        name: String,
+    },
+    /// Exclude the paths of a preset that exist on this machine, reporting their size
+    Apply {
+        /// Name of the preset to apply
+        name: String,
+
+        /// Only report what would be excluded
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum RulesCommand {
+    /// Enable or disable the configured rules and add rules of the built-in packs from a
+    /// checklist
+    Toggle,
+}
+
+/// Builds the rule selection of the `--only-tag`, `--skip-tag` and `--rule` flags
+fn rule_filter(args: &Args) -> config::RuleFilter {
+    config::RuleFilter {
+        only_tags: args.only_tags.clone(),
+        skip_tags: args.skip_tags.clone(),
+        rule_names: args.rules.clone(),
+    }
+}
+
+/// Restricts the ruleset to the CLI selection, if any, then adds the ad-hoc patterns of
+/// `--exclude-from`, which the rule filters do not apply to, and applies `--max-depth` and
+/// `--sizes`
+fn apply_cli_selection(config: &mut config::Config, args: &Args) -> Result<()> {
+    config.apply_rule_filter(&rule_filter(args))?;
+    config.add_pattern_rules(&config::read_pattern_files(&args.exclude_from)?);
+    config.override_max_depth(args.max_depth);
+    config.size_mode = args.sizes.then_some(args.size_mode);
+    Ok(())
+}
+
+/// Appends the paths read from stdin with `--stdin` to the paths given as arguments
+fn with_stdin_paths(paths: &[String], stdin: bool) -> Result<Vec<String>> {
+    let mut paths = paths.to_vec();
+    if stdin {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        paths.extend(explorer::parse_path_list(&input));
+    }
+    Ok(paths)
+}
+
+/// Maps the `--fixed-path` flag to the Time Machine exclusion type
+fn exclusion_type(fixed_path: bool) -> config::ExclusionType {
+    if fixed_path {
+        config::ExclusionType::Fixed
+    } else {
+        config::ExclusionType::Sticky
+    }
+}
+
+/// Reads the privilege helper of the config, if any, for fixed-path exclusions.
+/// Other commands do not need a config file
+fn privilege_helper(config_path: Option<&str>, fixed_path: bool, verbose: bool) -> Option<String> {
+    if !fixed_path {
+        return None;
+    }
+    config::read_config(config_path, verbose)
+        .ok()
+        .and_then(|(config, _)| config.privilege_helper)
+}
+
+/// Returns true if the Time Machine change cannot be made by this process
+fn needs_elevation(fixed_path: bool, privilege_helper: &Option<String>) -> bool {
+    backend::backend_with_privilege_helper(privilege_helper.clone())
+        .requires_elevation(exclusion_type(fixed_path))
+}
+
+/// Re-runs a fixed-path `exclude` or `include` as root, after confirmation
+fn run_fixed_path_elevated(command: &str, paths: &[String]) -> Result<()> {
+    let mut elevated_args = vec![command.to_string(), "--fixed-path".to_string()];
+    for path in explorer::expand_path_args(paths)? {
+        // Absolute paths keep `~`, globs and relative paths independent of root's environment
+        let path = std::fs::canonicalize(&path).unwrap_or(path);
+        elevated_args.push(path.display().to_string());
+    }
+    privileged::run_elevated(&elevated_args, &i18n::tr("elevated-fixed-path-reason", &[]))
+}
+
+fn main() -> ExitCode {
     let args = Args::parse();
+    let format = args.format;
+    match run(args) {
+        Ok(code) => ExitCode::from(code),
+        Err(error) => {
+            let code = exit::code_of(&error);
+            match format {
+                OutputFormat::Text => eprintln!("Error: {:?}", error),
+                _ => eprintln!("{}", output::render_fatal_error(&error, code)),
+            }
+            ExitCode::from(code)
+        }
+    }
+}
 
-    // Handle subcommands
-    if let Some(command) = &args.command {
-        match command {
-            Commands::Init { local, path } => {
-                return config::create_default_config(*local, path.as_deref());
+/// Runs a subcommand
+fn run_command(command: &Commands, args: &Args, config_path: Option<&str>) -> Result<()> {
+    match command {
+        Commands::Init { local, path } => config::create_default_config(*local, path.as_deref()),
+        Commands::Version => {
+            println!("Asimeow version {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        Commands::List {
+            path,
+            size,
+            refresh,
+            pagination,
+        } => Ok(explorer::list_exclusions(
+            path.as_deref(),
+            size.then_some(args.size_mode),
+            *refresh,
+            args.threads,
+            pagination,
+        )?),
+        Commands::Exclude {
+            paths,
+            stdin,
+            by_rules,
+            fixed_path,
+        } => {
+            let paths = with_stdin_paths(paths, *stdin)?;
+            if *by_rules {
+                let (mut config, _) = config::read_config(config_path, args.verbose)?;
+                apply_cli_selection(&mut config, args)?;
+                let stats = explorer::run_on_paths(config, &paths, args.threads, args.verbose)?;
+                return exit::scan_status(&stats);
             }
-            Commands::Version => {
-                println!("Asimeow version {}", env!("CARGO_PKG_VERSION"));
-                return Ok(());
+            let privilege_helper = privilege_helper(config_path, *fixed_path, args.verbose);
+            if needs_elevation(*fixed_path, &privilege_helper) {
+                return run_fixed_path_elevated("exclude", &paths);
             }
-            Commands::List { path } => {
-                return explorer::list_exclusions(path.as_deref());
+            Ok(explorer::exclude_paths(
+                &paths,
+                exclusion_type(*fixed_path),
+                privilege_helper,
+                args.verbose,
+            )?)
+        }
+        Commands::Include {
+            paths,
+            stdin,
+            fixed_path,
+            recursive,
+            dry_run,
+            yes,
+        } => {
+            let paths = with_stdin_paths(paths, *stdin)?;
+            if *recursive {
+                return inventory::run_include_recursive(
+                    &paths,
+                    privilege_helper(config_path, !*dry_run, args.verbose),
+                    *dry_run,
+                    *yes,
+                );
             }
-            Commands::Exclude { path } => {
-                return explorer::exclude_path(path, args.verbose);
+            let privilege_helper = privilege_helper(config_path, *fixed_path, args.verbose);
+            if needs_elevation(*fixed_path, &privilege_helper) {
+                return run_fixed_path_elevated("include", &paths);
             }
-            Commands::Include { path } => {
-                return explorer::include_path(path, args.verbose);
+            Ok(explorer::include_paths(
+                &paths,
+                exclusion_type(*fixed_path),
+                privilege_helper,
+                args.verbose,
+            )?)
+        }
+        Commands::TestRule { rule, path } => {
+            explain::test_rule(config_path, rule, path, args.verbose)
+        }
+        Commands::Explain { path } => explain::explain_path(
+            config_path,
+            path,
+            backend::default_backend().as_ref(),
+            args.verbose,
+        ),
+        Commands::WhyNot { path } => explain::why_not_path(config_path, path, args.verbose),
+        Commands::Inventory {
+            root,
+            impact,
+            pagination,
+        } => inventory::run_inventory(
+            root.as_deref(),
+            *impact,
+            args.size_mode,
+            pagination,
+            args.verbose,
+        ),
+        Commands::Snapshots { root } => snapshots::run_snapshots(root.as_deref()),
+        Commands::Clean { yes } => {
+            inventory::run_clean(privilege_helper(config_path, true, args.verbose), *yes)
+        }
+        Commands::Tui { path } => {
+            let (mut config, _) = config::read_config(config_path, args.verbose)?;
+            apply_cli_selection(&mut config, args)?;
+            tui::run_tui(config, path.as_deref(), args.threads)
+        }
+        Commands::Scan { paths } if !paths.is_empty() => {
+            let (mut config, _) = config::read_config(config_path, args.verbose)?;
+            apply_cli_selection(&mut config, args)?;
+            config.watch_progress = args.watch_progress;
+            config.format = args.format;
+            let stats = explorer::run_on_paths(config, paths, args.threads, args.verbose)?;
+            exit::scan_status(&stats)
+        }
+        // Without paths, the configured roots are scanned by `run`
+        Commands::Scan { .. } => Ok(()),
+        Commands::Simulate { roots } => {
+            let (mut config, _) = config::read_config(config_path, args.verbose)?;
+            apply_cli_selection(&mut config, args)?;
+            config.watch_progress = args.watch_progress;
+            config.format = args.format;
+            let stats = explorer::run_simulation(config, roots, args.threads, args.verbose)?;
+            exit::scan_status(&stats)
+        }
+        Commands::Estimate => {
+            let (mut config, _) = config::load_config(config_path, args.verbose)?;
+            config.retain_roots(&args.roots)?;
+            apply_cli_selection(&mut config, args)?;
+            estimate::run_estimate(config, args.threads, args.size_mode, args.verbose)
+        }
+        Commands::Find { rules } => {
+            let (mut config, _) = config::load_config(config_path, args.verbose)?;
+            config.retain_roots(&args.roots)?;
+            config.override_max_depth(args.max_depth);
+            find::run_find(config, rules, args.threads, args.size_mode, args.verbose)
+        }
+        Commands::Export { format, output } => {
+            let (mut config, _) = config::load_config(config_path, args.verbose)?;
+            config.retain_roots(&args.roots)?;
+            apply_cli_selection(&mut config, args)?;
+            export::run_export(
+                config,
+                *format,
+                output.as_deref(),
+                args.threads,
+                args.verbose,
+            )
+        }
+        Commands::Import { from, file, output } => {
+            import::run_import(*from, file.as_deref(), output.as_deref())
+        }
+        Commands::Remote {
+            host,
+            remote_bin,
+            args: remote_args,
+        } => remote::run_remote(
+            host,
+            remote_bin,
+            config_path,
+            remote_args,
+            args.format,
+            args.verbose,
+        ),
+        Commands::Presets { action } => {
+            match action {
+                None | Some(PresetsCommand::List) => presets::list_presets(),
+                Some(PresetsCommand::Apply { name, dry_run }) => {
+                    let privilege_helper = privilege_helper(config_path, true, args.verbose);
+                    if !*dry_run && needs_elevation(true, &privilege_helper) {
+                        let paths: Vec<String> = presets::existing_paths(name)?
+                            .iter()
+                            .map(|path| path.display().to_string())
+                            .collect();
+                        // Resolved here, as root's home directory is not the user's one
+                        if !paths.is_empty() {
+                            return run_fixed_path_elevated("exclude", &paths);
+                        }
+                    }
+                    presets::apply_preset(name, *dry_run, privilege_helper, args.verbose)
+                }
+                Some(PresetsCommand::Add { name }) => presets::add_pack(name, config_path),
             }
         }
+        Commands::Rules { action } => match action {
+            RulesCommand::Toggle => rules::run_toggle(config_path),
+        },
+        Commands::Suggest {
+            min_size_mb,
+            append,
+        } => suggest::run_suggest(
+            config_path,
+            args.threads,
+            args.verbose,
+            *min_size_mb,
+            *append,
+        ),
     }
+}
 
-    if args.verbose {
-        println!("Asimeow - Time Machine Exclusion Tool");
-        println!("------------------------------------");
-        println!("Using {} worker threads", args.threads);
+/// Runs the command line, returning the exit code of a successful run
+fn run(args: Args) -> Result<u8> {
+    i18n::set_lang(args.lang.unwrap_or_else(i18n::detect));
+    if args.yes {
+        privileged::set_assume_yes();
     }
 
     // Load the configuration
@@ -99,10 +659,87 @@ fn main() -> Result<()> {
         None
     };
 
-    let (config, _) = config::load_config(config_path, args.verbose)?;
+    // Handle subcommands, a `scan` without paths scans the configured roots below
+    match &args.command {
+        Some(Commands::Scan { paths }) if paths.is_empty() => {}
+        Some(command) => {
+            run_command(command, &args, config_path)?;
+            return Ok(exit::SUCCESS);
+        }
+        None => {}
+    }
 
-    // Run the explorer with the loaded configuration
-    explorer::run_explorer(config, args.threads, args.verbose)?;
+    // Stdout only holds the report with the JSON formats
+    if args.verbose && args.format == OutputFormat::Text {
+        println!("Asimeow - Time Machine Exclusion Tool");
+        println!("------------------------------------");
+        println!("Using {} worker threads", args.threads);
+    } else if args.verbose {
+        eprintln!("Using {} worker threads", args.threads);
+    }
 
-    Ok(())
+    if args.all_users {
+        users::run_all_users(config_path, &rule_filter(&args), args.threads, args.verbose)?;
+        return Ok(exit::SUCCESS);
+    }
+
+    // First run without a config: guide the setup instead of failing
+    if args.command.is_none() && onboarding::needs_onboarding(config_path) {
+        onboarding::run_onboarding(args.threads, args.size_mode, args.verbose)?;
+        return Ok(exit::SUCCESS);
+    }
+
+    let (mut config, config_path_str) = config::load_config(config_path, args.verbose)?;
+
+    // Restrict the roots and the ruleset to the CLI selection, if any
+    config.retain_roots(&args.roots)?;
+    apply_cli_selection(&mut config, &args)?;
+    if config.rules.is_empty() {
+        eprintln!("{}", i18n::tr("config-no-rules-selected", &[]));
+    }
+
+    config.watch_progress = args.watch_progress;
+    config.format = args.format;
+    if args.interactive {
+        config.interactive = true;
+        let (stats, state) = explorer::run_explorer_with_state(config, args.threads, args.verbose)?;
+        let never_paths = std::mem::take(&mut state.review.write().unwrap().never_paths);
+        if !never_paths.is_empty() {
+            let question = i18n::tr(
+                "config-protect-question",
+                &[
+                    ("count", never_paths.len().into()),
+                    ("path", config_path_str.as_str().into()),
+                ],
+            );
+            if privileged::confirm(&format!("\n{}", question))? {
+                for entry in config::append_protected(&config_path_str, &never_paths)? {
+                    println!(
+                        "{}",
+                        i18n::tr("config-protected-added", &[("entry", entry.into())])
+                    );
+                }
+            } else {
+                println!("{}", i18n::tr("config-unchanged", &[]));
+            }
+        }
+        exit::scan_status(&stats)?;
+        return Ok(exit::SUCCESS);
+    }
+
+    // Run the explorer with the loaded configuration, only reporting with --check
+    config.dry_run = args.check;
+    let (stats, state) = explorer::run_explorer_with_state(config, args.threads, args.verbose)?;
+    exit::scan_status(&stats)?;
+    if !args.check {
+        return Ok(exit::SUCCESS);
+    }
+    let pending = exit::pending_exclusions(&state);
+    if !pending.is_empty() {
+        eprintln!(
+            "{}",
+            i18n::tr("exit-changes", &[("count", pending.len().into())])
+        );
+    }
+    Ok(exit::check_status(&pending))
 }
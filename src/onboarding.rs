@@ -0,0 +1,107 @@
+use crate::backend::{backend_with_privilege_helper, BackupExclusionBackend};
+use crate::config::{self, Config, ExclusionType};
+use crate::estimate::{estimate_entries, EstimateEntry};
+use crate::export;
+use crate::i18n::tr;
+use crate::privileged;
+use crate::size::{format_size, SizeMode};
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Number of paths listed by the detection scan, largest first
+const LISTED_ENTRIES: usize = 10;
+
+/// True on a first run: no config file was given and none is found in the usual places
+pub fn needs_onboarding(config_path: Option<&str>) -> bool {
+    config_path.is_none() && config::find_config_file(None).is_err()
+}
+
+/// Guides a first run: proposes to write `config` to `config_file`, runs a read-only
+/// detection scan with it, shows the estimated savings and asks before excluding the
+/// detected paths. Questions go through `confirm`. Returns the number of newly excluded
+/// paths
+pub fn onboard(
+    config: Config,
+    config_file: &str,
+    backend: &dyn BackupExclusionBackend,
+    thread_count: usize,
+    size_mode: SizeMode,
+    verbose: bool,
+    confirm: &mut dyn FnMut(&str) -> Result<bool>,
+) -> Result<usize> {
+    let path = || [("path", config_file.into())];
+    println!("{}", tr("onboarding-welcome", &[]));
+    println!("{}", tr("onboarding-intro", &[]));
+    if !confirm(&tr("onboarding-create", &path()))? {
+        println!("{}", tr("onboarding-init-later", &[]));
+        return Ok(0);
+    }
+    config::write_config(config_file, &config)?;
+    println!("{}", tr("onboarding-created", &path()));
+
+    println!("\n{}", tr("onboarding-scanning", &[]));
+    let exclusions = export::collect_exclusions(config, thread_count, verbose)?;
+    let entries = estimate_entries(&exclusions, backend, thread_count, size_mode);
+    let new: Vec<&EstimateEntry> = entries
+        .iter()
+        .filter(|entry| !entry.already_excluded)
+        .collect();
+    if new.is_empty() {
+        println!("{}", tr("onboarding-nothing", &[]));
+        return Ok(0);
+    }
+
+    for entry in new.iter().take(LISTED_ENTRIES) {
+        println!(
+            "📋 {:>10}  {}",
+            format_size(entry.size),
+            entry.path.display()
+        );
+    }
+    if new.len() > LISTED_ENTRIES {
+        let more = new.len() - LISTED_ENTRIES;
+        println!("   {}", tr("onboarding-more", &[("count", more.into())]));
+    }
+    let size = format_size(new.iter().map(|entry| entry.size).sum());
+    println!(
+        "{}",
+        tr(
+            "onboarding-savings",
+            &[("count", new.len().into()), ("size", size.into())]
+        )
+    );
+
+    if !confirm(&tr("onboarding-apply", &[]))? {
+        println!("{}", tr("onboarding-apply-later", &[]));
+        return Ok(0);
+    }
+    let paths: Vec<PathBuf> = new.iter().map(|entry| entry.path.clone()).collect();
+    if !backend.add_all(&paths, ExclusionType::Sticky) {
+        return Err(anyhow::anyhow!("Failed to exclude the detected paths"));
+    }
+    println!(
+        "{}",
+        tr("onboarding-excluded", &[("count", paths.len().into())])
+    );
+    Ok(paths.len())
+}
+
+/// Runs the onboarding with the default configuration in ~/.config/asimeow/, asking on
+/// the terminal
+pub fn run_onboarding(thread_count: usize, size_mode: SizeMode, verbose: bool) -> Result<()> {
+    let config_file = config::expand_tilde("~/.config/asimeow/config.yaml")?
+        .to_string_lossy()
+        .to_string();
+    let config = config::default_config();
+    let backend = backend_with_privilege_helper(config.privilege_helper.clone());
+    onboard(
+        config,
+        &config_file,
+        backend.as_ref(),
+        thread_count,
+        size_mode,
+        verbose,
+        &mut privileged::confirm,
+    )?;
+    Ok(())
+}
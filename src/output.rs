@@ -0,0 +1,433 @@
+use crate::events::{Event, ExclusionOutcome, MessageLevel};
+use crate::explorer::{ExplorerStats, State};
+use crate::i18n::tr;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Format of the scan output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable lines, with a summary of the errors on stderr
+    #[default]
+    Text,
+    /// A single JSON object with the counters, the exclusions and the errors, at the end
+    Json,
+    /// One JSON object per line: the exclusions, the errors, then the summary
+    Ndjson,
+}
+
+/// Category of a scan error, so automation can tell permission problems from missing paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorKind {
+    PermissionDenied,
+    NotFound,
+    NotADirectory,
+    /// Any other I/O error
+    Io,
+    /// A path could not be excluded
+    Exclusion,
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = match self {
+            ErrorKind::PermissionDenied => "error-kind-permission-denied",
+            ErrorKind::NotFound => "error-kind-not-found",
+            ErrorKind::NotADirectory => "error-kind-not-a-directory",
+            ErrorKind::Io => "error-kind-io",
+            ErrorKind::Exclusion => "error-kind-exclusion",
+            ErrorKind::Other => "error-kind-other",
+        };
+        f.write_str(&tr(id, &[]))
+    }
+}
+
+impl ErrorKind {
+    fn of(error: &io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            io::ErrorKind::NotFound => ErrorKind::NotFound,
+            io::ErrorKind::NotADirectory => ErrorKind::NotADirectory,
+            _ => ErrorKind::Io,
+        }
+    }
+}
+
+/// An error met during a scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScanError {
+    pub kind: ErrorKind,
+    pub path: PathBuf,
+    pub message: String,
+    /// OS error number, when the error comes from a system call
+    pub errno: Option<i32>,
+}
+
+impl ScanError {
+    pub fn new(kind: ErrorKind, path: &Path, message: impl Into<String>) -> Self {
+        ScanError {
+            kind,
+            path: path.to_path_buf(),
+            message: message.into(),
+            errno: None,
+        }
+    }
+
+    /// Categorizes an I/O error met on `path`
+    pub fn from_io(path: &Path, error: &io::Error) -> Self {
+        ScanError {
+            kind: ErrorKind::of(error),
+            path: path.to_path_buf(),
+            message: error.to_string(),
+            errno: error.raw_os_error(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    #[serde(flatten)]
+    stats: &'a ExplorerStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclusions: Option<&'a [PathBuf]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<&'a [ScanError]>,
+}
+
+impl<'a> Summary<'a> {
+    fn new(stats: &'a ExplorerStats) -> Self {
+        Summary {
+            stats,
+            exclusions: None,
+            errors: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Record<'a> {
+    Exclusion {
+        path: &'a Path,
+    },
+    Error(&'a ScanError),
+    Summary(Summary<'a>),
+    /// The error ending the run, with its exit code
+    Fatal {
+        message: String,
+        exit_code: u8,
+    },
+}
+
+/// Renders the error ending a run as a JSON line, so the output of the JSON formats stays
+/// machine-readable when a run fails
+pub fn render_fatal_error(error: &anyhow::Error, exit_code: u8) -> String {
+    let record = Record::Fatal {
+        message: format!("{:#}", error),
+        exit_code,
+    };
+    serde_json::to_string(&record).unwrap_or_default()
+}
+
+/// Renders the result of a scan in a JSON format, as one document (`Json`) or one line per
+/// record (`Ndjson`). Returns None for the text format, printed while scanning.
+pub fn render_report(stats: &ExplorerStats, state: &State, format: OutputFormat) -> Option<String> {
+    let mut exclusions: Vec<PathBuf> = state
+        .seen_exclusion_paths
+        .read()
+        .unwrap()
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    exclusions.sort();
+    let errors = state.errors.read().unwrap();
+
+    match format {
+        OutputFormat::Text => None,
+        OutputFormat::Json => {
+            let summary = Summary {
+                exclusions: Some(&exclusions),
+                errors: Some(&errors),
+                ..Summary::new(stats)
+            };
+            serde_json::to_string_pretty(&summary).ok()
+        }
+        OutputFormat::Ndjson => {
+            let records = exclusions
+                .iter()
+                .map(|path| Record::Exclusion { path })
+                .chain(errors.iter().map(Record::Error))
+                .chain(std::iter::once(Record::Summary(Summary::new(stats))));
+            let lines: Vec<String> = records
+                .filter_map(|record| serde_json::to_string(&record).ok())
+                .collect();
+            Some(lines.join("\n"))
+        }
+    }
+}
+
+/// Number of affected directories listed per kind of error
+const LISTED_DIRECTORIES: usize = 3;
+
+/// Returns the top-level directory an error affects: the entry of the root containing it
+/// (e.g. `~/Library` for an error in `~/Library/Caches/x` when scanning `~/`), or the
+/// path itself outside the roots
+fn top_level_directory(path: &Path, roots: &[PathBuf]) -> PathBuf {
+    let root = roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count());
+    let entry = root.and_then(|root| {
+        let first = path.strip_prefix(root).ok()?.components().next()?;
+        Some(root.join(first))
+    });
+    entry.unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Renders the errors of a scan grouped by kind, most frequent first, with their count
+/// and the top-level directories of `roots` they affect
+pub fn render_error_summary(errors: &[ScanError], roots: &[PathBuf]) -> Vec<String> {
+    if errors.is_empty() {
+        return Vec::new();
+    }
+
+    let mut groups: BTreeMap<ErrorKind, (usize, BTreeSet<PathBuf>)> = BTreeMap::new();
+    for error in errors {
+        let (count, directories) = groups.entry(error.kind).or_default();
+        *count += 1;
+        directories.insert(top_level_directory(&error.path, roots));
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(_, (count, _))| std::cmp::Reverse(*count));
+
+    let mut lines = vec![tr("errors-header", &[("count", errors.len().into())])];
+    for (kind, (count, directories)) in groups {
+        let mut listed: Vec<String> = directories
+            .iter()
+            .take(LISTED_DIRECTORIES)
+            .map(|directory| directory.display().to_string())
+            .collect();
+        if directories.len() > LISTED_DIRECTORIES {
+            listed.push(tr(
+                "errors-more",
+                &[("count", (directories.len() - LISTED_DIRECTORIES).into())],
+            ));
+        }
+        let group = tr(
+            "errors-group",
+            &[
+                ("kind", kind.to_string().into()),
+                ("count", count.into()),
+                ("directories", listed.join(", ").into()),
+            ],
+        );
+        lines.push(format!("  {}", group));
+    }
+    lines
+}
+
+/// Presents a scan: receives its events from the worker threads as they happen, then its
+/// result once it is over. The explorer only reports through it, so a new output format
+/// needs a new reporter rather than changes to the traversal
+pub trait Reporter: Send + Sync {
+    fn event(&self, event: &Event);
+
+    /// Called once the scan is over, with the roots it started from
+    fn finish(&self, stats: &ExplorerStats, state: &State, roots: &[PathBuf]);
+}
+
+// A shared reporter, e.g. one the caller inspects after the scan
+impl<R: Reporter + ?Sized> Reporter for std::sync::Arc<R> {
+    fn event(&self, event: &Event) {
+        (**self).event(event)
+    }
+
+    fn finish(&self, stats: &ExplorerStats, state: &State, roots: &[PathBuf]) {
+        (**self).finish(stats, state, roots)
+    }
+}
+
+/// Human-readable output: a line per exclusion on stdout while scanning, then the counters,
+/// and the errors grouped by kind on stderr
+pub struct HumanReporter {
+    pub verbose: bool,
+    /// Print a line per exclusion, off when a dashboard replaces them
+    pub paths: bool,
+    /// Print the counters at the end
+    pub summary: bool,
+}
+
+impl HumanReporter {
+    pub fn new(verbose: bool) -> Self {
+        HumanReporter {
+            verbose,
+            paths: true,
+            summary: true,
+        }
+    }
+}
+
+impl Reporter for HumanReporter {
+    fn event(&self, event: &Event) {
+        if let Event::Message { level, text } = event {
+            match level {
+                MessageLevel::Verbose | MessageLevel::Notice => println!("{}", text),
+                MessageLevel::Warning => eprintln!("{}", text),
+                MessageLevel::Prompt => {
+                    print!("{} ", text);
+                    let _ = io::Write::flush(&mut io::stdout());
+                }
+            }
+            return;
+        }
+        let Event::Excluded {
+            rule,
+            path,
+            outcome,
+        } = event
+        else {
+            return;
+        };
+        if !self.paths {
+            return;
+        }
+        let id = match outcome {
+            // Green tick for newly excluded paths
+            ExclusionOutcome::Excluded => "scan-excluded",
+            // Yellow circle for already excluded paths
+            ExclusionOutcome::AlreadyExcluded => "scan-already-excluded",
+            ExclusionOutcome::DryRun => "scan-dry-run",
+        };
+        let args = [
+            ("path", path.display().to_string().into()),
+            ("rule", rule.as_str().into()),
+        ];
+        println!("{}", tr(id, &args));
+    }
+
+    fn finish(&self, stats: &ExplorerStats, state: &State, roots: &[PathBuf]) {
+        let counts = [
+            ("scan-reported", stats.reported),
+            ("scan-warnings", stats.warnings),
+            ("scan-candidates", stats.candidates),
+            ("scan-crowded", stats.crowded),
+            ("scan-orphans", stats.orphans),
+            ("scan-cloud-synced", stats.cloud_synced),
+            ("scan-dataless", stats.dataless),
+        ];
+        if self.summary
+            && (self.verbose
+                || stats.exclusions_found > 0
+                || counts.iter().any(|(_, count)| *count > 0))
+        {
+            let count_line = |id: &str, count: i32| tr(id, &[("count", count.into())]);
+            println!("\n{}", count_line("scan-processed", stats.processed_paths));
+            println!(
+                "{}",
+                count_line("scan-exclusions-found", stats.exclusions_found)
+            );
+            println!(
+                "{}",
+                count_line("scan-newly-excluded", stats.newly_excluded)
+            );
+            for (id, count) in counts {
+                if count > 0 {
+                    println!("{}", count_line(id, count));
+                }
+            }
+            if let Some(bytes) = stats.bytes {
+                let size = crate::size::format_size(bytes);
+                println!("{}", tr("scan-total-size", &[("size", size.into())]));
+            }
+            if self.verbose {
+                // The rules doing the most work first, those never matching last
+                let mut rules: Vec<_> = stats.rules.iter().collect();
+                rules.sort_by_key(|(_, rule_stats)| {
+                    std::cmp::Reverse((rule_stats.exclusions_found, rule_stats.matches))
+                });
+                println!("{}", tr("scan-rules-header", &[]));
+                for (rule, rule_stats) in rules {
+                    let line = tr(
+                        "scan-rule",
+                        &[
+                            ("rule", rule.as_str().into()),
+                            ("matches", rule_stats.matches.into()),
+                            ("exclusions", rule_stats.exclusions_found.into()),
+                            ("newly_excluded", rule_stats.newly_excluded.into()),
+                        ],
+                    );
+                    println!("  {}", line);
+                }
+                println!("{}", tr("scan-roots-header", &[]));
+                for (root, root_stats) in &stats.roots {
+                    let line = tr(
+                        "scan-root",
+                        &[
+                            ("root", root.display().to_string().into()),
+                            ("processed", root_stats.processed_paths.into()),
+                            ("exclusions", root_stats.exclusions_found.into()),
+                            (
+                                "duration",
+                                format!("{:.1}s", root_stats.duration.as_secs_f64()).into(),
+                            ),
+                        ],
+                    );
+                    println!("  {}", line);
+                }
+                let duration = format!("{:.1}s", stats.duration.as_secs_f64());
+                println!("{}", tr("scan-duration", &[("duration", duration.into())]));
+            }
+        }
+
+        // Errors are grouped at the end rather than scattered among the results
+        let errors = state.errors.read().unwrap();
+        for line in render_error_summary(&errors, roots) {
+            eprintln!("{}", line);
+        }
+        if !errors.is_empty() && !self.verbose {
+            eprintln!("{}", tr("errors-verbose-hint", &[]));
+        }
+    }
+}
+
+/// JSON output in one of the JSON formats, printed at the end, see `render_report`
+pub struct JsonReporter {
+    pub format: OutputFormat,
+}
+
+impl Reporter for JsonReporter {
+    // Stdout only holds the JSON report, the human-readable lines go to stderr
+    fn event(&self, event: &Event) {
+        match event {
+            Event::Message {
+                level: MessageLevel::Prompt,
+                text,
+            } => {
+                eprint!("{} ", text);
+                let _ = io::Write::flush(&mut io::stderr());
+            }
+            Event::Message { text, .. } => eprintln!("{}", text),
+            _ => {}
+        }
+    }
+
+    fn finish(&self, stats: &ExplorerStats, state: &State, _roots: &[PathBuf]) {
+        if let Some(report) = render_report(stats, state, self.format) {
+            println!("{}", report);
+        }
+    }
+}
+
+/// No output, e.g. for library users handling the events themselves
+pub struct SilentReporter;
+
+impl Reporter for SilentReporter {
+    fn event(&self, _event: &Event) {}
+
+    fn finish(&self, _stats: &ExplorerStats, _state: &State, _roots: &[PathBuf]) {}
+}
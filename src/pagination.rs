@@ -0,0 +1,67 @@
+/// Limits of a listing, so directories with tens of thousands of entries stay usable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::Args)]
+pub struct Pagination {
+    /// Show at most this many entries
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_results: Option<usize>,
+
+    /// Page of `--max-results` entries to show, starting at 1
+    #[arg(long, default_value_t = 1, requires = "max_results", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub page: usize,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Pagination {
+            max_results: None,
+            page: 1,
+        }
+    }
+}
+
+impl Pagination {
+    /// Returns the entries of the requested page
+    pub fn apply<'a, T>(&self, items: &'a [T]) -> &'a [T] {
+        match self.max_results {
+            Some(max_results) => {
+                let start = max_results
+                    .saturating_mul(self.page.saturating_sub(1))
+                    .min(items.len());
+                &items[start..(start.saturating_add(max_results)).min(items.len())]
+            }
+            None => items,
+        }
+    }
+
+    /// Describes which entries of `total` the page shows and how to get the next ones,
+    /// or None when everything is shown
+    pub fn footer(&self, total: usize) -> Option<String> {
+        let max_results = self.max_results?;
+        let pages = total.div_ceil(max_results).max(1);
+        let start = max_results.saturating_mul(self.page - 1);
+        if start >= total && self.page > 1 {
+            return Some(format!(
+                "No entries on page {} (there are {} pages of {} entries)",
+                self.page, pages, max_results
+            ));
+        }
+        if total <= max_results {
+            return None;
+        }
+        let end = (start + max_results).min(total);
+        let next = if self.page < pages {
+            format!(", --page {} for the next ones", self.page + 1)
+        } else {
+            String::new()
+        };
+        Some(format!(
+            "Showing {}-{} of {} entries (page {} of {}{})",
+            start + 1,
+            end,
+            total,
+            self.page,
+            pages,
+            next
+        ))
+    }
+}
@@ -0,0 +1,309 @@
+use crate::backend::backend_with_privilege_helper;
+use crate::config::{self, ExclusionType, Rule};
+use crate::explorer::elevation_required_error;
+use crate::size::{directory_size, format_size};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// A curated set of fixed paths that can be excluded in one go
+pub struct Preset {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Absolute paths, or paths relative to the home directory when starting with `~/`.
+    /// Glob patterns are expanded against the file system.
+    pub paths: &'static [&'static str],
+}
+
+const PRESETS: &[Preset] = &[Preset {
+    name: "containers",
+    description: "Container and VM disk images (Docker Desktop, OrbStack, Colima, UTM, Parallels, VMware, VirtualBox)",
+    paths: &[
+        "~/Library/Containers/com.docker.docker/Data/vms",
+        "~/Library/Group Containers/group.com.docker/Data/vms",
+        "~/.orbstack",
+        "~/Library/Group Containers/HUAQ24HBR6.dev.orbstack/data",
+        "~/.colima",
+        "~/.lima",
+        "~/Library/Containers/com.utmapp.UTM/Data/Documents",
+        "~/Parallels",
+        "~/Virtual Machines.localized",
+        "~/Documents/Virtual Machines.localized",
+        "~/VirtualBox VMs",
+    ],
+}, Preset {
+    name: "dev-caches",
+    description: "User-level developer caches (Xcode, Homebrew, Cargo, Gradle, npm, Maven, Go, ...)",
+    paths: &[
+        "~/Library/Developer/Xcode/DerivedData",
+        "~/Library/Developer/Xcode/iOS DeviceSupport",
+        "~/Library/Developer/CoreSimulator/Caches",
+        "~/Library/Caches",
+        "~/Library/Caches/Homebrew",
+        "~/.cache",
+        "~/.cargo/registry",
+        "~/.cargo/git",
+        "~/.gradle/caches",
+        "~/.gradle/wrapper/dists",
+        "~/.npm",
+        "~/.yarn/berry/cache",
+        "~/Library/pnpm/store",
+        "~/.m2/repository",
+        "~/go/pkg/mod",
+        "~/.nuget/packages",
+        "~/.pub-cache",
+        "~/.cocoapods/repos",
+    ],
+}, Preset {
+    name: "ml-models",
+    description: "Machine learning model and package caches (Hugging Face, Ollama, PyTorch, conda)",
+    paths: &[
+        "~/.cache/huggingface",
+        "~/.ollama/models",
+        "~/.cache/torch",
+        "~/.conda/pkgs",
+        "~/miniconda3/pkgs",
+        "~/anaconda3/pkgs",
+        "~/miniforge3/pkgs",
+        "~/mambaforge/pkgs",
+        "~/opt/miniconda3/pkgs",
+        "~/opt/anaconda3/pkgs",
+    ],
+}];
+
+/// A curated set of rules, kept as YAML data in `src/packs`, that can be merged into a config
+pub struct RulePack {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// YAML list of rules, in the same format as the `rules` config section
+    pub rules: &'static str,
+}
+
+const PACKS: &[RulePack] = &[
+    RulePack {
+        name: "ios",
+        description: "iOS and macOS apps (CocoaPods, Carthage, SwiftPM, Xcode, fastlane)",
+        rules: include_str!("packs/ios.yaml"),
+    },
+    RulePack {
+        name: "android",
+        description: "Android apps (Gradle builds, native builds, APK and AAB outputs)",
+        rules: include_str!("packs/android.yaml"),
+    },
+    RulePack {
+        name: "gamedev",
+        description: "Game engines (Unity, Unreal, Godot)",
+        rules: include_str!("packs/gamedev.yaml"),
+    },
+    RulePack {
+        name: "ml",
+        description:
+            "Machine learning projects (DVC cache, notebook checkpoints, experiment logs, model checkpoints)",
+        rules: include_str!("packs/ml.yaml"),
+    },
+    RulePack {
+        name: "devops",
+        description:
+            "Infrastructure tooling (Terraform, Terragrunt, Vagrant, Serverless, CDK, Pulumi)",
+        rules: include_str!("packs/devops.yaml"),
+    },
+];
+
+/// Returns the built-in rule packs
+pub fn all_packs() -> &'static [RulePack] {
+    PACKS
+}
+
+/// Finds a built-in rule pack by name
+pub fn find_pack(name: &str) -> Option<&'static RulePack> {
+    PACKS.iter().find(|pack| pack.name == name)
+}
+
+impl RulePack {
+    /// Parses the rules of the pack
+    pub fn parse_rules(&self) -> Result<Vec<Rule>> {
+        serde_yaml::from_str(self.rules)
+            .map_err(|e| anyhow::anyhow!("Invalid rules in pack '{}': {}", self.name, e))
+    }
+}
+
+/// Merges the rules of a pack into the config file, skipping rules whose name is taken
+pub fn add_pack(name: &str, config_path: Option<&str>) -> Result<()> {
+    let pack = find_pack(name).ok_or_else(|| {
+        let names: Vec<&str> = all_packs().iter().map(|pack| pack.name).collect();
+        anyhow::anyhow!(
+            "Unknown rule pack '{}'. Available packs: {}",
+            name,
+            names.join(", ")
+        )
+    })?;
+    let config_path = config::find_config_file(config_path)?;
+
+    let rules = pack.parse_rules()?;
+    let total = rules.len();
+    let added = config::append_rules(&config_path, rules)?;
+
+    for rule_name in &added {
+        println!("✅ {} - {}", rule_name, pack.name);
+    }
+    println!(
+        "Added {} of {} rules of pack '{}' to {}",
+        added.len(),
+        total,
+        pack.name,
+        config_path
+    );
+    if added.len() < total {
+        println!("Rules with a name already in the config were skipped");
+    }
+    Ok(())
+}
+
+/// Returns all the built-in presets
+pub fn all() -> &'static [Preset] {
+    PRESETS
+}
+
+/// Finds a preset by name
+pub fn find(name: &str) -> Option<&'static Preset> {
+    PRESETS.iter().find(|preset| preset.name == name)
+}
+
+/// Resolves the paths of a preset that exist, expanding `~/` against `home`.
+/// Paths nested in an already resolved path are skipped, so sizes are not counted twice.
+pub fn resolve_paths(preset: &Preset, home: &Path) -> Vec<PathBuf> {
+    let mut resolved: Vec<PathBuf> = Vec::new();
+    for entry in preset.paths {
+        let path = match entry.strip_prefix("~/") {
+            Some(relative) => home.join(relative),
+            None => PathBuf::from(entry),
+        };
+
+        let path_str = path.to_string_lossy();
+        if path_str.contains(['*', '?', '[']) {
+            if let Ok(paths) = glob::glob(&path_str) {
+                resolved.extend(paths.filter_map(|p| p.ok()));
+            }
+        } else if path.exists() {
+            resolved.push(path);
+        }
+    }
+
+    let mut unique: Vec<PathBuf> = Vec::new();
+    for path in resolved {
+        if !unique.iter().any(|kept| path.starts_with(kept)) {
+            unique.retain(|kept| !kept.starts_with(&path));
+            unique.push(path);
+        }
+    }
+    unique
+}
+
+/// Prints the available presets
+pub fn list_presets() -> Result<()> {
+    println!("Available presets:");
+    for preset in all() {
+        println!("  - {}: {}", preset.name, preset.description);
+        for path in preset.paths {
+            println!("      {}", path);
+        }
+    }
+
+    println!("\nAvailable rule packs (presets add <name>):");
+    for pack in all_packs() {
+        println!("  - {}: {}", pack.name, pack.description);
+        for rule in pack.parse_rules()? {
+            println!("      {} ({})", rule.name, rule.file_match);
+        }
+    }
+    Ok(())
+}
+
+/// Finds a built-in preset by name, failing with the list of the available ones
+fn find_or_fail(name: &str) -> Result<&'static Preset> {
+    find(name).ok_or_else(|| {
+        let names: Vec<&str> = all().iter().map(|preset| preset.name).collect();
+        anyhow::anyhow!(
+            "Unknown preset '{}'. Available presets: {}",
+            name,
+            names.join(", ")
+        )
+    })
+}
+
+/// Returns the paths of a preset that exist in the home directory of the current user
+pub fn existing_paths(name: &str) -> Result<Vec<PathBuf>> {
+    let preset = find_or_fail(name)?;
+    let home =
+        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+    Ok(resolve_paths(preset, &home))
+}
+
+/// Excludes the existing paths of a preset as fixed-path exclusions, reporting the size of
+/// each of them. With `dry_run`, only reports what would be excluded.
+pub fn apply_preset(
+    name: &str,
+    dry_run: bool,
+    privilege_helper: Option<String>,
+    verbose: bool,
+) -> Result<()> {
+    let preset = find_or_fail(name)?;
+    let paths = existing_paths(name)?;
+    if paths.is_empty() {
+        println!("No paths of preset '{}' found on this machine", preset.name);
+        return Ok(());
+    }
+
+    let backend = backend_with_privilege_helper(privilege_helper);
+    let dry_run = dry_run || backend.reports_only();
+    if !dry_run && backend.requires_elevation(ExclusionType::Fixed) {
+        return Err(elevation_required_error(&[]).into());
+    }
+
+    let mut total_size = 0;
+    let mut failed = 0;
+    for path in &paths {
+        let size = directory_size(path);
+        total_size += size;
+
+        if dry_run {
+            println!(
+                "📋 {} ({}) - {}",
+                path.display(),
+                format_size(size),
+                preset.name
+            );
+        } else if backend.is_excluded(path) {
+            println!(
+                "🟡 {} ({}) - {}",
+                path.display(),
+                format_size(size),
+                preset.name
+            );
+            if verbose {
+                println!("  → Already excluded from Time Machine");
+            }
+        } else if backend.add(path, ExclusionType::Fixed) {
+            println!(
+                "✅ {} ({}) - {}",
+                path.display(),
+                format_size(size),
+                preset.name
+            );
+        } else {
+            eprintln!("❌ Failed to exclude: {}", path.display());
+            failed += 1;
+        }
+    }
+
+    println!(
+        "\nPreset '{}': {} paths, {} total",
+        preset.name,
+        paths.len(),
+        format_size(total_size)
+    );
+
+    if failed > 0 {
+        return Err(anyhow::anyhow!("Failed to exclude {} paths", failed));
+    }
+    Ok(())
+}
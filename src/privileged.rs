@@ -0,0 +1,62 @@
+use crate::i18n::tr;
+use anyhow::{Context, Result};
+use std::io::{self, BufRead, Write};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Set by `--yes` or the `assume_yes` config option, so scheduled runs never wait for input
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Answers yes to every later question instead of asking
+pub fn set_assume_yes() {
+    ASSUME_YES.store(true, Ordering::Relaxed);
+}
+
+/// Returns true if questions are answered yes without asking
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// Asks a yes/no question on the terminal. Anything but `y`/`yes`, including a closed
+/// stdin, is a no
+pub fn confirm(question: &str) -> Result<bool> {
+    if assume_yes() {
+        println!("{} [y/N] yes (--yes)", question);
+        return Ok(true);
+    }
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Re-runs asimeow through `sudo` for a single narrow subcommand (e.g.
+/// `exclude --fixed-path <path>`), after showing the command and asking for confirmation
+pub fn run_elevated(args: &[String], reason: &str) -> Result<()> {
+    let exe = std::env::current_exe().context("Could not determine the asimeow executable")?;
+
+    println!("🔐 {}", reason);
+    println!("   sudo {} {}", exe.display(), args.join(" "));
+    if !confirm(&tr("elevated-question", &[]))? {
+        return Err(anyhow::anyhow!(tr("elevated-cancelled", &[])));
+    }
+
+    // Without a terminal to answer, sudo fails instead of waiting for a password
+    let mut sudo = Command::new("sudo");
+    if assume_yes() {
+        sudo.arg("-n");
+    }
+    let status = sudo
+        .arg(&exe)
+        .args(args)
+        .status()
+        .context("Failed to run sudo")?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(tr(
+            "elevated-failed",
+            &[("status", status.to_string().into())]
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::explorer::State;
+use std::io::Write;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Delay between two refreshes of the dashboard
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Shortens a path to `width` characters, keeping its end (the most specific components)
+fn truncate_start(path: &str, width: usize) -> String {
+    let count = path.chars().count();
+    if count <= width {
+        return path.to_string();
+    }
+    let kept: String = path.chars().skip(count - width.saturating_sub(1)).collect();
+    format!("…{}", kept)
+}
+
+/// Renders the dashboard of a running scan: the counters, the directory each worker is
+/// processing and for how long, so slow directories stand out, then the progress of each
+/// root. Lines are cut to `width`.
+pub fn render_dashboard(
+    state: &State,
+    thread_count: usize,
+    elapsed: Duration,
+    width: usize,
+) -> Vec<String> {
+    let mut lines = vec![
+        format!("asimeow scan - {}s elapsed", elapsed.as_secs()),
+        format!(
+            "Processed: {}  Queue: {}  Exclusions: {}  Newly excluded: {}  Errors: {}",
+            *state.processed_paths.read().unwrap(),
+            state.folder_queue.read().unwrap().len(),
+            *state.exclusion_found.read().unwrap(),
+            *state.newly_excluded.read().unwrap(),
+            state.errors.read().unwrap().len()
+        ),
+        "Workers:".to_string(),
+    ];
+
+    let workers = state.worker_paths.read().unwrap();
+    for worker in 0..thread_count {
+        let line = match workers.get(&worker) {
+            Some((path, since)) => {
+                let prefix = format!(
+                    "  #{:<3} {:>6.1}s  ",
+                    worker + 1,
+                    since.elapsed().as_secs_f64()
+                );
+                let path_width = width.saturating_sub(prefix.chars().count()).max(10);
+                format!(
+                    "{}{}",
+                    prefix,
+                    truncate_start(&path.display().to_string(), path_width)
+                )
+            }
+            None => format!("  #{:<3} idle", worker + 1),
+        };
+        lines.push(line);
+    }
+
+    // Roots on a slow disk stand out with their own queue and timing
+    let queue = state.folder_queue.read().unwrap();
+    if !queue.roots().is_empty() {
+        lines.push("Roots:".to_string());
+    }
+    for root in queue.roots() {
+        let status = if root.is_done() {
+            format!("done in {:.1}s", root.elapsed().as_secs_f64())
+        } else {
+            format!(
+                "queue {}, workers {}, {:.1}s",
+                root.pending(),
+                root.active,
+                root.elapsed().as_secs_f64()
+            )
+        };
+        let prefix = format!("  {:<28} ", status);
+        let path_width = width.saturating_sub(prefix.chars().count()).max(10);
+        lines.push(format!(
+            "{}{}",
+            prefix,
+            truncate_start(&root.root.display().to_string(), path_width)
+        ));
+    }
+
+    lines
+        .into_iter()
+        .map(|line| line.chars().take(width).collect())
+        .collect()
+}
+
+/// Redraws the dashboard in place on stderr until the scan completes, leaving its final
+/// state on screen
+pub fn watch(state: &State, thread_count: usize) {
+    let started = Instant::now();
+    let mut drawn = 0;
+    loop {
+        let complete = *state.processing_complete.read().unwrap();
+        let width = ratatui::crossterm::terminal::size()
+            .ok()
+            .map(|(columns, _)| columns as usize)
+            .filter(|columns| *columns > 0)
+            .unwrap_or(80);
+        let lines = render_dashboard(state, thread_count, started.elapsed(), width);
+
+        let mut stderr = std::io::stderr().lock();
+        // Back to the first line of the previous frame, which is cleared
+        if drawn > 0 {
+            let _ = write!(stderr, "\x1b[{}F\x1b[J", drawn);
+        }
+        for line in &lines {
+            let _ = writeln!(stderr, "{}", line);
+        }
+        let _ = stderr.flush();
+        drawn = lines.len();
+
+        if complete {
+            break;
+        }
+        thread::sleep(REFRESH_INTERVAL);
+    }
+}
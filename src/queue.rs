@@ -0,0 +1,134 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Directories of a root waiting to be scanned, with the timing of the root
+#[derive(Debug)]
+pub struct RootQueue {
+    pub root: PathBuf,
+    pending: VecDeque<PathBuf>,
+    /// Workers busy with a directory of the root
+    pub active: usize,
+    started: Option<Instant>,
+    /// Time the root took, from its first directory taken to its last one scanned
+    pub duration: Option<Duration>,
+}
+
+impl RootQueue {
+    fn new(root: PathBuf) -> Self {
+        RootQueue {
+            pending: VecDeque::from([root.clone()]),
+            root,
+            active: 0,
+            started: None,
+            duration: None,
+        }
+    }
+
+    /// Number of directories of the root waiting to be scanned
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Time spent on the root so far, or in total once it is done
+    pub fn elapsed(&self) -> Duration {
+        self.duration
+            .or_else(|| self.started.map(|started| started.elapsed()))
+            .unwrap_or_default()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.duration.is_some()
+    }
+}
+
+/// Directories waiting to be scanned, in a queue per root so that roots on different disks
+/// (e.g. the internal SSD and a slow external one) progress side by side: a worker takes
+/// the next directory of the root the fewest workers are busy with, the roots taking turns
+#[derive(Debug, Default)]
+pub struct FolderQueue {
+    roots: Vec<RootQueue>,
+    /// Index of the root served last
+    last: usize,
+}
+
+impl FolderQueue {
+    /// Adds the queue of a root, holding the root itself
+    pub fn add_root(&mut self, root: PathBuf) {
+        match self.roots.iter_mut().find(|queue| queue.root == root) {
+            Some(queue) => queue.pending.push_back(root),
+            None => self.roots.push(RootQueue::new(root)),
+        }
+    }
+
+    /// Queues a directory in the queue of the closest root containing it. A directory
+    /// outside of the roots gets a queue of its own
+    pub fn push(&mut self, path: PathBuf) {
+        match self.index_of(&path) {
+            Some(index) => self.roots[index].pending.push_back(path),
+            None => self.add_root(path),
+        }
+    }
+
+    fn index_of(&self, path: &Path) -> Option<usize> {
+        self.roots
+            .iter()
+            .enumerate()
+            .filter(|(_, queue)| path.starts_with(&queue.root))
+            .max_by_key(|(_, queue)| queue.root.components().count())
+            .map(|(index, _)| index)
+    }
+
+    /// Takes the next directory to scan, with the root it belongs to. Call `done` with
+    /// that root once the directory is scanned
+    pub fn pop(&mut self) -> Option<(PathBuf, PathBuf)> {
+        let count = self.roots.len();
+        // The first of the least busy roots after the one served last
+        let index = (1..=count)
+            .map(|offset| (self.last + offset) % count)
+            .filter(|index| !self.roots[*index].pending.is_empty())
+            .min_by_key(|index| self.roots[*index].active)?;
+        self.last = index;
+
+        let queue = &mut self.roots[index];
+        let path = queue.pending.pop_front()?;
+        queue.active += 1;
+        queue.started.get_or_insert_with(Instant::now);
+        queue.duration = None;
+        Some((queue.root.clone(), path))
+    }
+
+    /// Marks a directory of `root` as scanned, once its subdirectories are queued. The
+    /// duration of the root is recorded when its last directory is done
+    pub fn done(&mut self, root: &Path) {
+        let Some(queue) = self.roots.iter_mut().find(|queue| queue.root == root) else {
+            return;
+        };
+        queue.active = queue.active.saturating_sub(1);
+        if queue.active == 0 && queue.pending.is_empty() {
+            queue.duration = queue.started.map(|started| started.elapsed());
+        }
+    }
+
+    /// Number of directories waiting to be scanned, across the roots
+    pub fn len(&self) -> usize {
+        self.roots.iter().map(RootQueue::pending).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.iter().all(|queue| queue.pending.is_empty())
+    }
+
+    /// Queues of the roots, in the order they were added
+    pub fn roots(&self) -> &[RootQueue] {
+        &self.roots
+    }
+}
+
+impl Extend<PathBuf> for FolderQueue {
+    fn extend<I: IntoIterator<Item = PathBuf>>(&mut self, paths: I) {
+        for path in paths {
+            self.push(path);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::config;
+use crate::export::shell_quote;
+use crate::i18n::tr;
+use crate::output::OutputFormat;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Where the local config is pushed, relative to the remote home directory
+pub const REMOTE_CONFIG_PATH: &str = ".cache/asimeow/remote-config.yaml";
+
+/// A JSON line of a remote scan run with `--format ndjson`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RemoteRecord {
+    Exclusion {
+        path: PathBuf,
+    },
+    Error {
+        path: PathBuf,
+        message: String,
+    },
+    Summary {
+        processed_paths: i32,
+        exclusions_found: i32,
+        newly_excluded: i32,
+        error_count: i32,
+    },
+}
+
+/// Returns the format the remote command prints: the local one for the JSON formats,
+/// relayed as is, and else NDJSON, rendered line by line as the results come in
+pub fn remote_format(format: OutputFormat) -> OutputFormat {
+    match format {
+        OutputFormat::Text => OutputFormat::Ndjson,
+        format => format,
+    }
+}
+
+/// Renders a line printed by the remote command: the NDJSON records of a scan become
+/// text lines naming the host, anything else (e.g. the output of `explain`) is kept
+pub fn render_line(host: &str, line: &str) -> String {
+    match serde_json::from_str::<RemoteRecord>(line) {
+        Ok(RemoteRecord::Exclusion { path }) => format!("📋 {}: {}", host, path.display()),
+        Ok(RemoteRecord::Error { path, message }) => {
+            format!("⚠️  {}: {} - {}", host, path.display(), message)
+        }
+        Ok(RemoteRecord::Summary {
+            processed_paths,
+            exclusions_found,
+            newly_excluded,
+            error_count,
+        }) => [
+            format!("\n{}", tr("remote-summary", &[("host", host.into())])),
+            tr("scan-processed", &[("count", processed_paths.into())]),
+            tr(
+                "scan-exclusions-found",
+                &[("count", exclusions_found.into())],
+            ),
+            tr("scan-newly-excluded", &[("count", newly_excluded.into())]),
+            tr("remote-errors", &[("count", error_count.into())]),
+        ]
+        .join("\n"),
+        Err(_) => line.to_string(),
+    }
+}
+
+/// Builds the shell command running asimeow on the remote host with the pushed config,
+/// printing its results in `format`
+pub fn remote_command(binary: &str, format: OutputFormat, args: &[String]) -> String {
+    let mut command = format!(
+        "{} -c \"$HOME\"/{}",
+        shell_quote(binary),
+        REMOTE_CONFIG_PATH
+    );
+    // An explicit `--format` of the remote arguments wins
+    if !args.iter().any(|arg| arg.starts_with("--format")) {
+        let format = clap::ValueEnum::to_possible_value(&format)
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default();
+        command.push_str(&format!(" --format {}", format));
+    }
+    for arg in args {
+        command.push(' ');
+        command.push_str(&shell_quote(arg));
+    }
+    command
+}
+
+/// Runs an asimeow command on a remote Mac over SSH: the local config is pushed to the
+/// host, then the command runs there with it, its NDJSON results streamed back and
+/// rendered as they are printed (relayed as is with the JSON formats)
+pub fn run_remote(
+    host: &str,
+    binary: &str,
+    config_path: Option<&str>,
+    args: &[String],
+    format: OutputFormat,
+    verbose: bool,
+) -> Result<()> {
+    let config_path = config::find_config_file(config_path)?;
+    let config_content = fs::read(&config_path)
+        .with_context(|| format!("Failed to read config file: {}", config_path))?;
+
+    if verbose {
+        eprintln!(
+            "Pushing {} to {}:~/{}",
+            config_path, host, REMOTE_CONFIG_PATH
+        );
+    }
+    let push = format!(
+        "mkdir -p \"$HOME\"/.cache/asimeow && cat > \"$HOME\"/{}",
+        REMOTE_CONFIG_PATH
+    );
+    let mut ssh = Command::new("ssh")
+        .args([host, &push])
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to run ssh")?;
+    if let Some(mut stdin) = ssh.stdin.take() {
+        stdin.write_all(&config_content)?;
+    }
+    if !ssh.wait()?.success() {
+        return Err(anyhow::anyhow!("Failed to push the config to {}", host));
+    }
+
+    let command = remote_command(binary, remote_format(format), args);
+    if verbose {
+        eprintln!("Running on {}: {}", host, command);
+    }
+    let mut ssh = Command::new("ssh")
+        .args([host, &command])
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to run ssh")?;
+    if let Some(stdout) = ssh.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            let line = line?;
+            match format {
+                OutputFormat::Text => println!("{}", render_line(host, &line)),
+                _ => println!("{}", line),
+            }
+        }
+    }
+    let status = ssh.wait().context("Failed to run ssh")?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "Remote command failed on {} ({})",
+            host,
+            status
+        ));
+    }
+    Ok(())
+}
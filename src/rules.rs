@@ -0,0 +1,223 @@
+use crate::config::{self, Config, Rule};
+use crate::presets;
+use anyhow::{Context, Result};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+use std::fs;
+
+/// A rule of the checklist, either configured or from a built-in rule pack
+#[derive(Debug, Clone)]
+pub struct RuleChoice {
+    pub rule: Rule,
+    /// Rule pack offering the rule, None for a rule of the config
+    pub pack: Option<&'static str>,
+    pub enabled: bool,
+}
+
+/// Lists the configured rules with their enabled state, then the rules of the built-in
+/// packs whose name is not configured, unchecked
+pub fn rule_choices(config: &Config) -> Result<Vec<RuleChoice>> {
+    let mut choices: Vec<RuleChoice> = config
+        .rules
+        .iter()
+        .map(|rule| RuleChoice {
+            rule: rule.clone(),
+            pack: None,
+            enabled: rule.is_enabled(),
+        })
+        .collect();
+    for pack in presets::all_packs() {
+        for rule in pack.parse_rules()? {
+            if choices.iter().all(|choice| choice.rule.name != rule.name) {
+                choices.push(RuleChoice {
+                    rule,
+                    pack: Some(pack.name),
+                    enabled: false,
+                });
+            }
+        }
+    }
+    Ok(choices)
+}
+
+/// Changes made to the config by a checklist
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ToggleSummary {
+    pub enabled: Vec<String>,
+    pub disabled: Vec<String>,
+    /// Checked pack rules, appended to the config
+    pub added: Vec<String>,
+}
+
+/// Writes the checked state of the choices back to the config file: configured rules are
+/// enabled or disabled, checked pack rules are appended. The file is re-serialized, so
+/// comments are lost, and is left untouched when nothing changes
+pub fn apply_choices(config_path: &str, choices: &[RuleChoice]) -> Result<ToggleSummary> {
+    let config_content = fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read config file: {}", config_path))?;
+    let mut config: Config = serde_yaml::from_str(&config_content)
+        .with_context(|| format!("Failed to parse config file: {}", config_path))?;
+
+    let mut summary = ToggleSummary::default();
+    for choice in choices {
+        match config
+            .rules
+            .iter_mut()
+            .find(|rule| rule.name == choice.rule.name)
+        {
+            Some(rule) if rule.is_enabled() != choice.enabled => {
+                // Enabled is the default, so the key is dropped rather than set to true
+                rule.enabled = if choice.enabled { None } else { Some(false) };
+                if choice.enabled {
+                    summary.enabled.push(rule.name.clone());
+                } else {
+                    summary.disabled.push(rule.name.clone());
+                }
+            }
+            Some(_) => {}
+            None if choice.enabled => {
+                let mut rule = choice.rule.clone();
+                rule.enabled = None;
+                summary.added.push(rule.name.clone());
+                config.rules.push(rule);
+            }
+            None => {}
+        }
+    }
+    if summary == ToggleSummary::default() {
+        return Ok(summary);
+    }
+
+    config::write_config(config_path, &config)?;
+    Ok(summary)
+}
+
+/// State of the rule checklist
+pub struct Checklist {
+    pub choices: Vec<RuleChoice>,
+    pub selected: usize,
+    /// Set when the user confirms the checklist
+    pub saved: bool,
+}
+
+impl Checklist {
+    pub fn new(choices: Vec<RuleChoice>) -> Self {
+        Checklist {
+            choices,
+            selected: 0,
+            saved: false,
+        }
+    }
+
+    /// Handles a key press, returning false when the checklist is closed
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Enter | KeyCode::Char('s') => {
+                self.saved = true;
+                return false;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.choices.len().saturating_sub(1))
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char(' ') => {
+                if let Some(choice) = self.choices.get_mut(self.selected) {
+                    choice.enabled = !choice.enabled;
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+fn draw(frame: &mut Frame, checklist: &Checklist, config_path: &str) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(
+        Paragraph::new(format!("asimeow rules - {}", config_path)),
+        header,
+    );
+
+    let items: Vec<ListItem> = checklist
+        .choices
+        .iter()
+        .map(|choice| {
+            let check = if choice.enabled { "[x]" } else { "[ ]" };
+            let source = match choice.pack {
+                Some(pack) => format!("  (pack {})", pack),
+                None => String::new(),
+            };
+            ListItem::new(format!(
+                "{} {}  {}{}",
+                check, choice.rule.name, choice.rule.file_match, source
+            ))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Rules"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut list_state = ListState::default().with_selected(Some(checklist.selected));
+    frame.render_stateful_widget(list, body, &mut list_state);
+
+    frame.render_widget(
+        Paragraph::new(Line::from("↑↓ move  space toggle  ⏎ save  q cancel")),
+        footer,
+    );
+}
+
+/// Shows a checklist of the configured rules and the rules of the built-in packs in the
+/// terminal, and writes the checked state back to the config file
+pub fn run_toggle(config_path: Option<&str>) -> Result<()> {
+    let config_path = config::find_config_file(config_path)?;
+    let config_content = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read config file: {}", config_path))?;
+    let config: Config = serde_yaml::from_str(&config_content)
+        .with_context(|| format!("Failed to parse config file: {}", config_path))?;
+
+    let mut checklist = Checklist::new(rule_choices(&config)?);
+    let mut terminal = ratatui::init();
+    let result = (|| -> Result<()> {
+        loop {
+            terminal.draw(|frame| draw(frame, &checklist, &config_path))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !checklist.handle_key(key.code) {
+                    return Ok(());
+                }
+            }
+        }
+    })();
+    ratatui::restore();
+    result?;
+
+    if !checklist.saved {
+        println!("Cancelled, {} was not changed", config_path);
+        return Ok(());
+    }
+    let summary = apply_choices(&config_path, &checklist.choices)?;
+    for name in &summary.enabled {
+        println!("✅ Enabled {}", name);
+    }
+    for name in &summary.disabled {
+        println!("⚪ Disabled {}", name);
+    }
+    for name in &summary.added {
+        println!("✅ Added {}", name);
+    }
+    if summary == ToggleSummary::default() {
+        println!("No changes to {}", config_path);
+    } else {
+        println!("Updated {}", config_path);
+    }
+    Ok(())
+}
@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Location of the size cache, relative to the home directory
+pub const SIZE_CACHE_PATH: &str = ".cache/asimeow/sizes.yaml";
+
+/// Age after which a cached size is computed again
+pub const SIZE_CACHE_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// How the size of a file is measured
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum SizeMode {
+    /// Length of the files, as the Finder and `ls -l` show it
+    #[default]
+    Apparent,
+    /// Blocks actually allocated, as `du` shows it: sparse files (e.g. `Docker.raw`) take
+    /// far less than their length
+    OnDisk,
+}
+
+/// Computes the total size in bytes of a file or directory tree.
+/// Symlinks are not followed and unreadable entries are skipped, as are the contents of
+/// dataless directories, which listing would download (see `icloud::is_dataless`).
+pub fn directory_size(path: &Path) -> u64 {
+    directory_size_as(path, SizeMode::Apparent)
+}
+
+/// Same as `directory_size`, measuring the files with the given mode. In `OnDisk` mode,
+/// the blocks of the directories themselves are counted too, like `du` does.
+pub fn directory_size_as(path: &Path, mode: SizeMode) -> u64 {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return 0,
+    };
+
+    let own_size = match mode {
+        SizeMode::Apparent if metadata.is_dir() => 0,
+        SizeMode::Apparent => metadata.len(),
+        // st_blocks counts 512-byte units whatever the block size of the file system
+        SizeMode::OnDisk => metadata.blocks() * 512,
+    };
+    if !metadata.is_dir() || crate::icloud::is_dataless(&metadata) {
+        return own_size;
+    }
+
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            own_size
+                + entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| directory_size_as(&entry.path(), mode))
+                    .sum::<u64>()
+        }
+        Err(_) => own_size,
+    }
+}
+
+/// Computes the total size in bytes of the files under a path modified after `since`,
+/// e.g. the churn a snapshot taken at that time keeps on disk. Dataless directories count
+/// as empty
+pub fn size_modified_since(path: &Path, since: SystemTime) -> u64 {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return 0,
+    };
+    if metadata.is_dir() && crate::icloud::is_dataless(&metadata) {
+        return 0;
+    }
+
+    if !metadata.is_dir() {
+        return match metadata.modified() {
+            Ok(modified) if modified > since => metadata.len(),
+            _ => 0,
+        };
+    }
+
+    match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| size_modified_since(&entry.path(), since))
+            .sum(),
+        Err(_) => 0,
+    }
+}
+
+/// Formats a size in bytes using human-readable binary units (e.g. `1.5 GB`).
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Computes the sizes of several paths, spread over `thread_count` threads.
+/// Returns them in the order of `paths`.
+pub fn directory_sizes(paths: &[PathBuf], thread_count: usize, mode: SizeMode) -> Vec<u64> {
+    let chunk_size = paths.len().div_ceil(thread_count.max(1)).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|path| directory_size_as(path, mode))
+                        .collect::<Vec<u64>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct CachedSize {
+    size: u64,
+    /// Seconds since the Unix epoch
+    computed_at: u64,
+}
+
+/// Sizes computed by earlier runs, so listing the same large directories again is instant
+#[derive(Debug, Default)]
+pub struct SizeCache {
+    file: Option<PathBuf>,
+    entries: BTreeMap<SizeMode, BTreeMap<PathBuf, CachedSize>>,
+}
+
+impl SizeCache {
+    /// Loads the cache file, starting empty when it is missing or unreadable
+    pub fn load<P: Into<PathBuf>>(file: P) -> Self {
+        let file = file.into();
+        let entries = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default();
+        SizeCache {
+            file: Some(file),
+            entries,
+        }
+    }
+
+    /// Loads the cache of the user (`~/.cache/asimeow/sizes.yaml`)
+    pub fn load_default() -> Self {
+        match dirs::home_dir() {
+            Some(home) => Self::load(home.join(SIZE_CACHE_PATH)),
+            None => Self::default(),
+        }
+    }
+
+    /// Returns the cached size of a path, unless it is older than `max_age`
+    pub fn get(&self, path: &Path, mode: SizeMode, max_age: Duration) -> Option<u64> {
+        let cached = self.entries.get(&mode)?.get(path)?;
+        let age = unix_now().saturating_sub(cached.computed_at);
+        (age < max_age.as_secs()).then_some(cached.size)
+    }
+
+    pub fn insert(&mut self, path: &Path, mode: SizeMode, size: u64) {
+        self.entries.entry(mode).or_default().insert(
+            path.to_path_buf(),
+            CachedSize {
+                size,
+                computed_at: unix_now(),
+            },
+        );
+    }
+
+    /// Returns the sizes of `paths`, computing in parallel those missing from the cache or
+    /// older than `max_age`
+    pub fn sizes(
+        &mut self,
+        paths: &[PathBuf],
+        thread_count: usize,
+        mode: SizeMode,
+        max_age: Duration,
+    ) -> Vec<u64> {
+        let missing: Vec<PathBuf> = paths
+            .iter()
+            .filter(|path| self.get(path, mode, max_age).is_none())
+            .cloned()
+            .collect();
+        for (path, size) in missing
+            .iter()
+            .zip(directory_sizes(&missing, thread_count, mode))
+        {
+            self.insert(path, mode, size);
+        }
+        let cached = self.entries.get(&mode);
+        paths
+            .iter()
+            .map(|path| {
+                cached
+                    .and_then(|entries| entries.get(path))
+                    .map_or(0, |cached| cached.size)
+            })
+            .collect()
+    }
+
+    /// Writes the cache back to its file, dropping the expired entries
+    pub fn save(&mut self) -> anyhow::Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        let now = unix_now();
+        for entries in self.entries.values_mut() {
+            entries.retain(|_, cached| {
+                now.saturating_sub(cached.computed_at) < SIZE_CACHE_MAX_AGE.as_secs()
+            });
+        }
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file, serde_yaml::to_string(&self.entries)?)?;
+        Ok(())
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
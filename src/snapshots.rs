@@ -0,0 +1,131 @@
+use crate::config;
+use crate::explorer;
+use crate::inventory;
+use crate::size::{format_size, size_modified_since};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, SystemTime};
+
+/// Prefix of the local snapshots created by Time Machine
+pub const SNAPSHOT_PREFIX: &str = "com.apple.TimeMachine.";
+
+/// A local APFS snapshot created by Time Machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalSnapshot {
+    /// Full snapshot name, e.g. `com.apple.TimeMachine.2026-10-01-120000.local`
+    pub name: String,
+    /// Creation date from the name, `YYYY-MM-DD-HHMMSS`
+    pub date: String,
+}
+
+impl LocalSnapshot {
+    /// Returns the creation time, reading the local time of the name as UTC (so it is
+    /// off by the time zone offset, which is fine for churn estimates)
+    pub fn created(&self) -> Option<SystemTime> {
+        let mut fields = self.date.splitn(4, '-');
+        let year: i64 = fields.next()?.parse().ok()?;
+        let month: i64 = fields.next()?.parse().ok()?;
+        let day: i64 = fields.next()?.parse().ok()?;
+        let time = fields.next()?;
+        if time.len() != 6 {
+            return None;
+        }
+        let hours: u64 = time[0..2].parse().ok()?;
+        let minutes: u64 = time[2..4].parse().ok()?;
+        let seconds: u64 = time[4..6].parse().ok()?;
+
+        let days = u64::try_from(days_since_epoch(year, month, day)).ok()?;
+        let secs = days * 86400 + hours * 3600 + minutes * 60 + seconds;
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+/// Days between 1970-01-01 and a date of the proleptic Gregorian calendar
+fn days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Parses the output of `tmutil listlocalsnapshots`, skipping the header line of newer
+/// macOS versions (`Snapshots for disk /:`), sorted from oldest to newest
+pub fn parse_local_snapshots(output: &str) -> Vec<LocalSnapshot> {
+    let mut snapshots: Vec<LocalSnapshot> = output
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            let date = line.strip_prefix(SNAPSHOT_PREFIX)?;
+            let date = date.strip_suffix(".local").unwrap_or(date);
+            Some(LocalSnapshot {
+                name: line.to_string(),
+                date: date.to_string(),
+            })
+        })
+        .collect();
+    snapshots.sort_by(|a, b| a.date.cmp(&b.date));
+    snapshots
+}
+
+/// Lists the local Time Machine snapshots of the volume holding `volume`
+pub fn list_local_snapshots(volume: &Path) -> Vec<LocalSnapshot> {
+    explorer::tmutil_command()
+        .arg("listlocalsnapshots")
+        .arg(volume)
+        .stderr(Stdio::null())
+        .output()
+        .map(|output| parse_local_snapshots(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Approximates the space the local snapshots keep for excluded items: the files under
+/// the exclusions modified since the oldest snapshot, whose previous versions only the
+/// snapshots still hold (e.g. rebuilt `target/` folders)
+pub fn excluded_churn(exclusions: &[PathBuf], snapshots: &[LocalSnapshot]) -> u64 {
+    let Some(oldest) = snapshots.iter().find_map(LocalSnapshot::created) else {
+        return 0;
+    };
+    exclusions
+        .iter()
+        .map(|path| size_modified_since(path, oldest))
+        .sum()
+}
+
+/// Prints the local Time Machine snapshots and the approximate space they keep for the
+/// excluded items under a path (the home directory by default)
+pub fn run_snapshots(root: Option<&str>) -> Result<()> {
+    let root = config::expand_tilde(root.unwrap_or("~"))?;
+    if !root.is_dir() {
+        return Err(anyhow::anyhow!("Not a directory: {}", root.display()));
+    }
+
+    let snapshots = list_local_snapshots(Path::new("/"));
+    println!("Local Time Machine snapshots");
+    println!("------------------------------------");
+    if snapshots.is_empty() {
+        println!("No local snapshots");
+        return Ok(());
+    }
+    for snapshot in &snapshots {
+        println!("📸 {}", snapshot.name);
+    }
+
+    let exclusions: Vec<PathBuf> = inventory::inventory(&root, &explorer::fixed_path_exclusions())
+        .into_iter()
+        .map(|entry| entry.path)
+        .collect();
+    println!(
+        "\nTotal snapshots: {} (oldest {})",
+        snapshots.len(),
+        snapshots[0].date
+    );
+    println!(
+        "Excluded churn kept by the snapshots under {}: ~{} (purgeable once they are thinned)",
+        root.display(),
+        format_size(excluded_churn(&exclusions, &snapshots))
+    );
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use crate::config::{self, Rule};
+use crate::explorer;
+use crate::i18n::tr;
+use crate::privileged;
+use anyhow::Result;
+use glob::Pattern;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sibling files that say nothing about the kind of project they belong to
+const GENERIC_FILES: &[&str] = &[
+    ".ds_store",
+    ".editorconfig",
+    ".env*",
+    ".git*",
+    "changelog*",
+    "license*",
+    "makefile",
+    "readme*",
+];
+
+/// A proposed rule and the paths that led to it
+#[derive(Debug)]
+pub struct Suggestion {
+    pub rule: Rule,
+    pub paths: Vec<PathBuf>,
+}
+
+/// Proposes a rule for each group of same-named paths: the marker is a file found next
+/// to every path of the group, and the exclusion is the path name itself.
+/// Groups without a common marker, or whose rule name is already taken, are skipped.
+pub fn suggest_rules(paths: &[PathBuf], existing_names: &[String]) -> Vec<Suggestion> {
+    let mut groups: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for path in paths {
+        if let Some(name) = path.file_name() {
+            groups
+                .entry(name.to_string_lossy().to_string())
+                .or_default()
+                .push(path.clone());
+        }
+    }
+
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+    for (name, mut group) in groups {
+        group.sort();
+        group.dedup();
+
+        let marker = match common_marker(&name, &group) {
+            Some(marker) => marker,
+            None => continue,
+        };
+
+        let rule_name = rule_name(&marker, &name);
+        if existing_names.contains(&rule_name)
+            || suggestions.iter().any(|s| s.rule.name == rule_name)
+        {
+            continue;
+        }
+
+        suggestions.push(Suggestion {
+            rule: Rule {
+                name: rule_name,
+                file_match: Pattern::escape(&marker),
+                exclusions: vec![Pattern::escape(&name).into()],
+                tags: vec!["suggested".to_string()],
+                ..Default::default()
+            },
+            paths: group,
+        });
+    }
+    suggestions
+}
+
+/// Picks a non-generic file present next to every path, preferring visible files
+fn common_marker(name: &str, group: &[PathBuf]) -> Option<String> {
+    let mut common: Option<BTreeSet<String>> = None;
+    for path in group {
+        let siblings = sibling_files(path.parent()?, name);
+        common = Some(match common {
+            Some(common) => common.intersection(&siblings).cloned().collect(),
+            None => siblings,
+        });
+    }
+
+    let common = common?;
+    common
+        .iter()
+        .find(|file| !file.starts_with('.'))
+        .or_else(|| common.iter().next())
+        .cloned()
+}
+
+fn sibling_files(folder: &Path, name: &str) -> BTreeSet<String> {
+    let entries = match fs::read_dir(folder) {
+        Ok(entries) => entries,
+        Err(_) => return BTreeSet::new(),
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|file| file != name && !is_generic_file(file))
+        .collect()
+}
+
+fn is_generic_file(file: &str) -> bool {
+    let file = file.to_lowercase();
+    GENERIC_FILES.iter().any(|pattern| {
+        Pattern::new(pattern)
+            .map(|pattern| pattern.matches(&file))
+            .unwrap_or(false)
+    })
+}
+
+/// Builds a rule name such as `build-zig-cache` from `build.zig` and `.zig-cache`
+fn rule_name(marker: &str, name: &str) -> String {
+    let stem = Path::new(marker)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| marker.to_string());
+    let slug = |text: &str| {
+        text.to_lowercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+    format!("{}-{}", slug(&stem), slug(name))
+}
+
+/// Scans the configured roots without excluding anything, then proposes rules for the
+/// cache-like directories no rule matched and for the findings of report-only rules.
+/// With `append`, the rules are added to the config file after confirmation.
+pub fn run_suggest(
+    config_path: Option<&str>,
+    thread_count: usize,
+    verbose: bool,
+    min_size_mb: Option<u64>,
+    append: bool,
+) -> Result<()> {
+    let (mut config, config_path) = config::load_config(config_path, verbose)?;
+
+    let mut heuristics = config.heuristics.take().unwrap_or_default();
+    if let Some(min_size_mb) = min_size_mb {
+        heuristics.min_size_mb = min_size_mb;
+    }
+    config.heuristics = Some(heuristics);
+    config.dry_run = true;
+
+    let existing_names: Vec<String> = config.rules.iter().map(|rule| rule.name.clone()).collect();
+
+    let (_, state) = explorer::run_explorer_with_state(config, thread_count, verbose)?;
+
+    let mut paths: Vec<PathBuf> = state
+        .heuristic_candidates
+        .read()
+        .unwrap()
+        .iter()
+        .cloned()
+        .collect();
+    paths.extend(
+        state
+            .reported_paths
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(_, path)| path.clone()),
+    );
+
+    let suggestions = suggest_rules(&paths, &existing_names);
+    if suggestions.is_empty() {
+        println!("\n{}", tr("suggest-none", &[]));
+        return Ok(());
+    }
+
+    println!(
+        "\n{}\n",
+        tr("suggest-header", &[("count", suggestions.len().into())])
+    );
+    for suggestion in &suggestions {
+        let found_in = suggestion
+            .paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("{}", tr("suggest-found-in", &[("paths", found_in.into())]));
+        print!("{}", serde_yaml::to_string(&[&suggestion.rule])?);
+    }
+
+    if append {
+        let question = tr(
+            "suggest-question",
+            &[
+                ("count", suggestions.len().into()),
+                ("path", config_path.as_str().into()),
+            ],
+        );
+        if privileged::confirm(&format!("\n{}", question))? {
+            let added = config::append_rules(
+                &config_path,
+                suggestions.into_iter().map(|s| s.rule).collect(),
+            )?;
+            println!(
+                "{}",
+                tr(
+                    "suggest-appended",
+                    &[
+                        ("count", added.len().into()),
+                        ("path", config_path.as_str().into()),
+                    ],
+                )
+            );
+        } else {
+            println!("{}", tr("config-unchanged", &[]));
+        }
+    }
+
+    Ok(())
+}
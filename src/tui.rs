@@ -0,0 +1,537 @@
+use crate::backend::{backend_with_privilege_helper, BackupExclusionBackend};
+use crate::config::{self, Config, ExclusionType};
+use crate::explain::{self, ExclusionOutcome};
+use crate::explorer;
+use crate::inventory;
+use crate::size::{directory_size, format_size};
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// An entry of the browsed directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TuiEntry {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// Excluded from Time Machine (the path itself, not an ancestor)
+    pub excluded: bool,
+    /// Computed on demand, as large trees take a while
+    pub size: Option<u64>,
+    /// Rules of the config matching in the browsed directory and targeting the entry
+    pub rules: Vec<String>,
+}
+
+/// What the main pane shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    /// Entries of the browsed directory
+    Tree,
+    /// Exclusions a scan of the browsed directory would add
+    Plan,
+    /// Project directories and exclusions under the root, fuzzy filtered by a query
+    Search,
+}
+
+/// State of the `tui` command, independent of the terminal so it can be driven in tests
+pub struct App {
+    config: Config,
+    backend: Arc<dyn BackupExclusionBackend>,
+    threads: usize,
+    /// Directory the TUI was opened on, indexed by the search
+    root: PathBuf,
+    pub current: PathBuf,
+    pub entries: Vec<TuiEntry>,
+    pub selected: usize,
+    /// Pending exclusions found by the last rescan
+    pub plan: Vec<PathBuf>,
+    pub view: View,
+    /// Status line, e.g. the result of the last action
+    pub message: String,
+    /// Query typed in the search view
+    pub query: String,
+    /// Project directories and exclusions under the root, indexed on the first search
+    pub candidates: Vec<PathBuf>,
+    /// Candidates matching the query, best first
+    pub matches: Vec<PathBuf>,
+}
+
+impl App {
+    pub fn new(
+        config: Config,
+        backend: Arc<dyn BackupExclusionBackend>,
+        root: PathBuf,
+        threads: usize,
+    ) -> Self {
+        let mut app = App {
+            config,
+            backend,
+            threads,
+            current: root.clone(),
+            root,
+            entries: Vec::new(),
+            selected: 0,
+            plan: Vec::new(),
+            view: View::Tree,
+            message: String::new(),
+            query: String::new(),
+            candidates: Vec::new(),
+            matches: Vec::new(),
+        };
+        app.load_entries();
+        app
+    }
+
+    /// Lists the browsed directory, directories first, with their exclusion status and
+    /// the rules targeting them
+    pub fn load_entries(&mut self) {
+        let mut entries: Vec<TuiEntry> = match fs::read_dir(&self.current) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| {
+                    let path = entry.path();
+                    TuiEntry {
+                        is_dir: entry.file_type().map(|t| t.is_dir()).unwrap_or(false),
+                        excluded: self.backend.is_excluded(&path),
+                        size: None,
+                        rules: Vec::new(),
+                        path,
+                    }
+                })
+                .collect(),
+            Err(e) => {
+                self.message = format!("Cannot read {}: {}", self.current.display(), e);
+                Vec::new()
+            }
+        };
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.path.cmp(&b.path)));
+
+        for rule in self.config.rules.iter().filter(|rule| rule.is_enabled()) {
+            let evaluation = explain::evaluate_rule(&self.current, rule, &self.config, false);
+            for (path, outcome) in evaluation.exclusions {
+                if matches!(outcome, ExclusionOutcome::Skipped(_)) {
+                    continue;
+                }
+                if let Some(entry) = entries.iter_mut().find(|entry| entry.path == path) {
+                    entry.rules.push(rule.name.clone());
+                }
+            }
+        }
+
+        self.entries = entries;
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    }
+
+    fn items_len(&self) -> usize {
+        match self.view {
+            View::Tree => self.entries.len(),
+            View::Plan => self.plan.len(),
+            View::Search => self.matches.len(),
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.items_len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Browses into the selected directory
+    pub fn enter(&mut self) {
+        if let Some(entry) = self.entries.get(self.selected).filter(|entry| entry.is_dir) {
+            self.current = entry.path.clone();
+            self.selected = 0;
+            self.load_entries();
+        }
+    }
+
+    /// Browses back to the parent directory, selecting the directory left
+    pub fn leave(&mut self) {
+        let Some(parent) = self.current.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        let left = std::mem::replace(&mut self.current, parent);
+        self.load_entries();
+        self.selected = self
+            .entries
+            .iter()
+            .position(|entry| entry.path == left)
+            .unwrap_or(0);
+    }
+
+    /// Excludes the selected entry, or includes it back when it is excluded
+    pub fn toggle_selected(&mut self) {
+        let Some(entry) = self.entries.get_mut(self.selected) else {
+            return;
+        };
+        let (changed, verb) = if entry.excluded {
+            (
+                self.backend.include(&entry.path, ExclusionType::Sticky),
+                "Included",
+            )
+        } else {
+            (
+                self.backend.exclude(&entry.path, ExclusionType::Sticky),
+                "Excluded",
+            )
+        };
+        if changed {
+            entry.excluded = !entry.excluded;
+            self.message = format!("{}: {}", verb, entry.path.display());
+        } else {
+            self.message = format!("Unchanged: {}", entry.path.display());
+        }
+    }
+
+    /// Computes the sizes of the entries of the browsed directory
+    pub fn compute_sizes(&mut self) {
+        for entry in &mut self.entries {
+            entry.size = Some(directory_size(&entry.path));
+        }
+    }
+
+    /// Returns the paths the configured rules target under a directory, excluded or not,
+    /// with a scan that leaves them untouched
+    fn dry_scan(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut config = self.config.clone();
+        config.roots = vec![config::Root {
+            path: dir.display().to_string(),
+            ..Default::default()
+        }];
+        config.exclude_paths.clear();
+        config.dry_run = true;
+        config.quiet = true;
+
+        let (_, state) =
+            explorer::run_explorer_with_backend(config, self.backend.clone(), self.threads, false)?;
+        let mut paths: Vec<PathBuf> = state
+            .seen_exclusion_paths
+            .read()
+            .unwrap()
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Scans the browsed directory with the configured rules, without excluding anything,
+    /// and shows the exclusions it would add
+    pub fn rescan(&mut self) -> Result<()> {
+        let plan: Vec<PathBuf> = self
+            .dry_scan(&self.current)?
+            .into_iter()
+            .filter(|path| !self.backend.is_excluded(path))
+            .collect();
+
+        self.message = format!("{} pending exclusions", plan.len());
+        self.plan = plan;
+        self.view = View::Plan;
+        self.selected = 0;
+        Ok(())
+    }
+
+    /// Applies the pending exclusions of the last rescan
+    pub fn apply_plan(&mut self) {
+        let excluded = self
+            .plan
+            .iter()
+            .filter(|path| self.backend.exclude(path, ExclusionType::Sticky))
+            .count();
+        self.message = format!("Excluded {} of {} paths", excluded, self.plan.len());
+        self.plan.clear();
+        self.view = View::Tree;
+        self.selected = 0;
+        self.load_entries();
+    }
+
+    /// Indexes the root for the search: the project directories (where a rule matched),
+    /// the paths the rules target and the exclusions found on disk
+    pub fn index(&mut self) -> Result<()> {
+        let targeted = self.dry_scan(&self.root)?;
+        let mut candidates: Vec<PathBuf> = targeted
+            .iter()
+            .filter_map(|path| path.parent())
+            .filter(|project| project.starts_with(&self.root))
+            .map(Path::to_path_buf)
+            .collect();
+        candidates.extend(targeted);
+        candidates.extend(
+            inventory::inventory(&self.root, &explorer::fixed_path_exclusions())
+                .into_iter()
+                .map(|entry| entry.path),
+        );
+        candidates.sort();
+        candidates.dedup();
+        self.candidates = candidates;
+        Ok(())
+    }
+
+    /// Opens the search view, indexing the root the first time
+    pub fn start_search(&mut self) {
+        if self.candidates.is_empty() {
+            if let Err(e) = self.index() {
+                self.message = format!("Indexing failed: {}", e);
+            }
+        }
+        self.view = View::Search;
+        self.update_matches();
+    }
+
+    /// Filters the candidates with the query, best matches first
+    pub fn update_matches(&mut self) {
+        let mut scored: Vec<(i64, &PathBuf)> = self
+            .candidates
+            .iter()
+            .filter_map(|path| {
+                let relative = path.strip_prefix(&self.root).unwrap_or(path);
+                fuzzy_score(&self.query, &relative.to_string_lossy()).map(|score| (score, path))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        self.matches = scored.into_iter().map(|(_, path)| path.clone()).collect();
+        self.selected = 0;
+    }
+
+    /// Browses to the directory holding the selected match and selects it
+    pub fn jump_to_selected(&mut self) {
+        let Some(target) = self.matches.get(self.selected).cloned() else {
+            return;
+        };
+        self.current = target
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| target.clone());
+        self.view = View::Tree;
+        self.load_entries();
+        self.selected = self
+            .entries
+            .iter()
+            .position(|entry| entry.path == target)
+            .unwrap_or(0);
+    }
+
+    /// Handles a key press, returning false when the user quits
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        // Typed characters go to the query, so most shortcuts do not apply while searching
+        if self.view == View::Search {
+            match key {
+                KeyCode::Esc => {
+                    self.view = View::Tree;
+                    self.selected = 0;
+                }
+                KeyCode::Enter => self.jump_to_selected(),
+                KeyCode::Down => self.select_next(),
+                KeyCode::Up => self.select_previous(),
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.update_matches();
+                }
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.update_matches();
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        match (self.view, key) {
+            (_, KeyCode::Char('q')) | (_, KeyCode::Esc) => return false,
+            (_, KeyCode::Down) | (_, KeyCode::Char('j')) => self.select_next(),
+            (_, KeyCode::Up) | (_, KeyCode::Char('k')) => self.select_previous(),
+            (_, KeyCode::Char('r')) => {
+                if let Err(e) = self.rescan() {
+                    self.message = format!("Scan failed: {}", e);
+                }
+            }
+            (_, KeyCode::Char('/')) => self.start_search(),
+            (View::Tree, KeyCode::Enter) | (View::Tree, KeyCode::Right) => self.enter(),
+            (View::Tree, KeyCode::Left) | (View::Tree, KeyCode::Backspace) => self.leave(),
+            (View::Tree, KeyCode::Char(' ')) => self.toggle_selected(),
+            (View::Tree, KeyCode::Char('s')) => self.compute_sizes(),
+            (View::Tree, KeyCode::Char('p')) => {
+                self.view = View::Plan;
+                self.selected = 0;
+            }
+            (View::Plan, KeyCode::Char('a')) => self.apply_plan(),
+            (View::Plan, KeyCode::Char('p')) | (View::Plan, KeyCode::Left) => {
+                self.view = View::Tree;
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(2),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(
+        Paragraph::new(format!("asimeow - {}", app.current.display())),
+        header,
+    );
+
+    let (title, items): (&str, Vec<ListItem>) = match app.view {
+        View::Tree => (
+            "Entries",
+            app.entries
+                .iter()
+                .map(|entry| {
+                    let name = entry.path.file_name().unwrap_or_default().to_string_lossy();
+                    let marker = if entry.excluded { "🟡" } else { "  " };
+                    let slash = if entry.is_dir { "/" } else { "" };
+                    let size = entry.size.map(format_size).unwrap_or_default();
+                    let rules = if entry.rules.is_empty() {
+                        String::new()
+                    } else {
+                        format!("  [{}]", entry.rules.join(", "))
+                    };
+                    ListItem::new(format!("{} {}{}  {}{}", marker, name, slash, size, rules))
+                })
+                .collect(),
+        ),
+        View::Plan => (
+            "Pending exclusions (a: apply)",
+            app.plan
+                .iter()
+                .map(|path| ListItem::new(format!("+ {}", path.display())))
+                .collect(),
+        ),
+        View::Search => (
+            "Search (⏎ jump, esc back)",
+            app.matches
+                .iter()
+                .map(|path| {
+                    let relative = path.strip_prefix(&app.root).unwrap_or(path);
+                    ListItem::new(relative.display().to_string())
+                })
+                .collect(),
+        ),
+    };
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut list_state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(list, body, &mut list_state);
+
+    let help = match app.view {
+        View::Tree => {
+            "↑↓ move  ⏎ open  ← parent  space toggle  s sizes  r rescan  p plan  / search  q quit"
+        }
+        View::Plan => "↑↓ move  a apply  r rescan  p back  / search  q quit",
+        View::Search => "",
+    };
+    let prompt = format!("/{}", app.query);
+    let help = if app.view == View::Search {
+        prompt.as_str()
+    } else {
+        help
+    };
+    frame.render_widget(
+        Paragraph::new(vec![Line::from(app.message.as_str()), Line::from(help)]),
+        footer,
+    );
+}
+
+/// Scores how well a query matches a candidate, fuzzily: the characters of the query must
+/// appear in order, ignoring case and spaces. Consecutive characters and characters starting
+/// a path component or word score higher, gaps lower. Returns None when it does not match.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let chars: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    // The first match is greedy, so each occurrence of the first character is tried as
+    // the start, e.g. for `react` in `Users/me/react-app`
+    chars
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| **c == query[0])
+        .filter_map(|(start, _)| {
+            let mut score = 0;
+            let mut last: Option<usize> = None;
+            let mut matched = 0;
+            for (i, c) in chars.iter().enumerate().skip(start) {
+                if matched == query.len() {
+                    break;
+                }
+                if *c != query[matched] {
+                    continue;
+                }
+                score += 1;
+                if i == 0 || matches!(chars[i - 1], '/' | '-' | '_' | '.' | ' ') {
+                    score += 6;
+                }
+                match last {
+                    Some(last) if last + 1 == i => score += 8,
+                    Some(last) => score -= (i - last - 1).min(5) as i64,
+                    None => {}
+                }
+                last = Some(i);
+                matched += 1;
+            }
+            (matched == query.len()).then_some(score)
+        })
+        .max()
+}
+
+/// Browses a directory (the first configured root by default) in the terminal, showing
+/// the exclusion status, sizes and matching rules of its entries, with keys to toggle
+/// exclusions, rescan it, apply the pending plan and fuzzy search its projects and exclusions
+pub fn run_tui(config: Config, root: Option<&str>, threads: usize) -> Result<()> {
+    let root = match root {
+        Some(root) => config::expand_tilde(root)?,
+        None => match config.roots.first() {
+            Some(root) => config::expand_path(&root.path)?,
+            None => std::env::current_dir()?,
+        },
+    };
+    if !root.is_dir() {
+        return Err(anyhow::anyhow!("Not a directory: {}", root.display()));
+    }
+
+    let backend = backend_with_privilege_helper(config.privilege_helper.clone());
+    let mut app = App::new(config, backend, root, threads);
+
+    let mut terminal = ratatui::init();
+    let result = (|| -> Result<()> {
+        loop {
+            terminal.draw(|frame| draw(frame, &app))?;
+            if let Event::Key(key) = event::read()? {
+                let searching = app.view == View::Search;
+                if key.kind == KeyEventKind::Press && !app.handle_key(key.code) {
+                    return Ok(());
+                }
+                // Warnings printed by a rescan or the indexing would otherwise stay on screen
+                if !searching && matches!(key.code, KeyCode::Char('r') | KeyCode::Char('/')) {
+                    terminal.clear()?;
+                }
+            }
+        }
+    })();
+    ratatui::restore();
+    result
+}
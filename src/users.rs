@@ -0,0 +1,188 @@
+use crate::config::{self, Backend, Config, Exclusion, ExclusionType, RuleFilter};
+use crate::explorer;
+use anyhow::Result;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Parent directory of the home directories on macOS
+pub const USERS_DIR: &str = "/Users";
+
+/// Config used for the users without their own config
+pub const SYSTEM_CONFIG_PATH: &str = "/Library/Application Support/asimeow/config.yaml";
+
+/// Returns the home directories of the users, skipping the shared and guest folders
+pub fn user_homes(users_dir: &Path) -> Vec<PathBuf> {
+    let mut homes: Vec<PathBuf> = match fs::read_dir(users_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir() && !path.is_symlink())
+            .filter(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                !name.starts_with('.') && name != "Shared" && name != "Guest"
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    homes.sort();
+    homes
+}
+
+/// Returns the config of a user: their own `~/.config/asimeow/config.yaml`, or else the
+/// system config if it exists
+pub fn user_config(home: &Path, system_config: &Path) -> Option<PathBuf> {
+    [
+        home.join(".config").join("asimeow").join("config.yaml"),
+        system_config.to_path_buf(),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
+/// Returns true if `path` is `dir` or below it, once symlinks are resolved. A missing path
+/// is checked as written, and rejected if it contains `..`
+pub fn is_within(path: &Path, dir: &Path) -> bool {
+    match path.canonicalize() {
+        Ok(canonical) => canonical.starts_with(dir),
+        Err(_) => path.starts_with(dir) && !path.components().any(|c| c == Component::ParentDir),
+    }
+}
+
+/// Drops the settings of a user's own config that root would otherwise apply on the user's
+/// behalf: command exclusions (and the rules left without exclusions), the `tmutil` and
+/// privilege helper overrides, the roots, `exclude_paths` and `symlink_bases` outside the
+/// user's home, fixed-path exclusions (made sticky) and the backends other than Time
+/// Machine. The scan is also confined to the home, see `Config::confine_to`, so symlinks
+/// cannot lead it elsewhere. Returns a description of each dropped setting
+pub fn restrict_user_config(config: &mut Config, home: &Path) -> Vec<String> {
+    let home = home.canonicalize().unwrap_or_else(|_| home.to_path_buf());
+    let mut dropped = Vec::new();
+    let outside_home =
+        |entry: &str| config::expand_path(entry).map_or(true, |path| !is_within(&path, &home));
+    config.roots.retain(|root| {
+        let outside = outside_home(&root.path);
+        if outside {
+            dropped.push(format!("`roots` entry {}", root.path));
+        }
+        !outside
+    });
+    for (setting, entries) in [
+        ("exclude_paths", &mut config.exclude_paths),
+        ("symlink_bases", &mut config.symlink_bases),
+    ] {
+        entries.retain(|entry| {
+            let outside = outside_home(entry);
+            if outside {
+                dropped.push(format!("`{}` entry {}", setting, entry));
+            }
+            !outside
+        });
+    }
+    for (setting, value) in [
+        ("tmutil_path", &mut config.tmutil_path),
+        ("tmutil_wrapper", &mut config.tmutil_wrapper),
+        ("privilege_helper", &mut config.privilege_helper),
+    ] {
+        if value.take().is_some() {
+            dropped.push(format!("`{}`", setting));
+        }
+    }
+
+    config.rules.retain_mut(|rule| {
+        let had_exclusions = !rule.exclusions.is_empty();
+        rule.exclusions.retain(|exclusion| match exclusion {
+            Exclusion::Command { command, .. } => {
+                dropped.push(format!("`{}` of rule '{}'", command, rule.name));
+                false
+            }
+            Exclusion::Path(_) => true,
+        });
+        if rule.exclusion_type == ExclusionType::Fixed {
+            rule.exclusion_type = ExclusionType::Sticky;
+            dropped.push(format!("`exclusion_type: fixed` of rule '{}'", rule.name));
+        }
+        let had_backends = !rule.backends.is_empty();
+        rule.backends.retain(|backend| {
+            if *backend != Backend::TimeMachine {
+                dropped.push(format!("{} backend of rule '{}'", backend, rule.name));
+            }
+            *backend == Backend::TimeMachine
+        });
+        // Without exclusions, the rule would exclude the matched entry itself, and without
+        // backends it would exclude with Time Machine
+        (!had_exclusions || !rule.exclusions.is_empty())
+            && (!had_backends || !rule.backends.is_empty())
+    });
+    config.confine_to = Some(home);
+    dropped
+}
+
+/// Scans the home directory of every user with their config, as root. `HOME` is switched
+/// to each user's home while their config is loaded and applied, so `~` expands to it.
+/// A user's own config is writable by them, so the settings running commands or reaching
+/// outside their home are dropped from it, see `restrict_user_config`
+pub fn run_all_users(
+    system_config: Option<&str>,
+    rule_filter: &RuleFilter,
+    thread_count: usize,
+    verbose: bool,
+) -> Result<()> {
+    if !explorer::is_elevated() {
+        return Err(anyhow::anyhow!(
+            "--all-users requires root privileges to read and change every user's files: run it with sudo"
+        ));
+    }
+    let system_config = config::expand_tilde(system_config.unwrap_or(SYSTEM_CONFIG_PATH))?;
+
+    let original_home = std::env::var_os("HOME");
+    let mut failed = Vec::new();
+    for home in user_homes(Path::new(USERS_DIR)) {
+        let user = home
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let Some(config_path) = user_config(&home, &system_config) else {
+            if verbose {
+                println!("Skipping user {}: no config", user);
+            }
+            continue;
+        };
+
+        println!("\n👤 {} ({})", user, config_path.display());
+        std::env::set_var("HOME", &home);
+        let result = (|| -> anyhow::Result<()> {
+            let (mut cfg, _) = config::load_config(config_path.to_str(), verbose)?;
+            if config_path != system_config {
+                let dropped = restrict_user_config(&mut cfg, &home);
+                explorer::set_tmutil_overrides(None, None);
+                if !dropped.is_empty() {
+                    eprintln!(
+                        "⚠️  Ignoring settings of {} that root cannot apply for the user: {}",
+                        config_path.display(),
+                        dropped.join(", ")
+                    );
+                }
+            }
+            cfg.apply_rule_filter(rule_filter)?;
+            explorer::run_explorer(cfg, thread_count, verbose)?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            eprintln!("Error scanning user {}: {:#}", user, e);
+            failed.push(user);
+        }
+    }
+
+    match original_home {
+        Some(home) => std::env::set_var("HOME", home),
+        None => std::env::remove_var("HOME"),
+    }
+    if !failed.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Failed to scan users: {}",
+            failed.join(", ")
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use anyhow::Result;
+use asimeow::backend::{BackupExclusionBackend, FileBackend, MockBackend, NoopBackend};
+use asimeow::{config, explorer};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+#[test]
+fn test_mock_backend_exclude_and_include() {
+    let backend = MockBackend::new();
+    let path = PathBuf::from("/tmp/project/node_modules");
+
+    assert!(backend.exclude(&path, config::ExclusionType::Fixed));
+    assert!(!backend.exclude(&path, config::ExclusionType::Sticky));
+    assert_eq!(
+        backend.exclusion_type(&path),
+        Some(config::ExclusionType::Fixed)
+    );
+
+    assert!(backend.include(&path, config::ExclusionType::Fixed));
+    assert!(!backend.include(&path, config::ExclusionType::Fixed));
+    assert!(backend.excluded_paths().is_empty());
+}
+
+#[test]
+fn test_backend_batches() {
+    let backend = MockBackend::new();
+    let paths = vec![
+        PathBuf::from("/tmp/a/node_modules"),
+        PathBuf::from("/tmp/b/target"),
+    ];
+
+    assert!(backend.add_all(&paths, config::ExclusionType::Sticky));
+    assert_eq!(backend.excluded_paths(), paths);
+    // Every path is attempted even when one of them fails
+    let more = vec![PathBuf::from("/tmp/c/build"), paths[0].clone()];
+    assert!(!backend.remove_all(
+        &[PathBuf::from("/tmp/c/build"), paths[1].clone()],
+        config::ExclusionType::Sticky
+    ));
+    assert_eq!(backend.excluded_paths(), vec![paths[0].clone()]);
+    assert!(backend.add_all(&more, config::ExclusionType::Sticky));
+    assert_eq!(backend.excluded_paths().len(), 2);
+}
+
+#[test]
+fn test_parse_path_list() {
+    assert_eq!(
+        explorer::parse_path_list("/tmp/a\n/tmp/b c\r\n\n"),
+        vec!["/tmp/a", "/tmp/b c"]
+    );
+    // NUL delimited lists keep newlines within names
+    assert_eq!(
+        explorer::parse_path_list("/tmp/a\0/tmp/new\nline\0"),
+        vec!["/tmp/a", "/tmp/new\nline"]
+    );
+    assert!(explorer::parse_path_list("").is_empty());
+}
+
+#[test]
+fn test_explorer_applies_exclusions_with_the_backend() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("workspace");
+    let app = root.join("app");
+    let service = root.join("service");
+    fs::create_dir_all(app.join("node_modules"))?;
+    fs::create_dir_all(app.join("dist"))?;
+    fs::create_dir_all(app.join("src"))?;
+    File::create(app.join("package.json"))?;
+    fs::create_dir_all(service.join("target"))?;
+    File::create(service.join("Cargo.toml"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: root.display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into(), "dist".into()],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "rust".to_string(),
+                file_match: "Cargo.toml".to_string(),
+                exclusions: vec!["target".into()],
+                exclusion_type: config::ExclusionType::Fixed,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    // dist is already excluded
+    let backend = Arc::new(MockBackend::with_excluded([app.join("dist")]));
+    let (stats, _) = explorer::run_explorer_with_backend(cfg, backend.clone(), 2, false)?;
+
+    assert_eq!(stats.exclusions_found, 3);
+    assert_eq!(stats.newly_excluded, 2);
+    assert_eq!(
+        backend.excluded_paths(),
+        vec![
+            app.join("dist"),
+            app.join("node_modules"),
+            service.join("target"),
+        ]
+    );
+    assert_eq!(
+        backend.exclusion_type(&service.join("target")),
+        Some(config::ExclusionType::Fixed)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_exclusion_mechanism_uses_the_backend() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let node_modules = temp_dir.path().join("p").join("node_modules");
+    let record = temp_dir.path().join("exclusions.txt");
+    let backend = FileBackend::new(&record);
+    assert_eq!(backend.exclusion_mechanism(&node_modules), None);
+
+    assert!(backend.add(&node_modules, config::ExclusionType::Sticky));
+    assert_eq!(
+        backend.exclusion_mechanism(&node_modules),
+        Some(explorer::ExclusionMechanism::Sticky)
+    );
+    assert_eq!(
+        backend.exclusion_mechanism(&node_modules.join("react")),
+        Some(explorer::ExclusionMechanism::Inherited(node_modules))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_file_backend_records_exclusions() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(project.join("node_modules"))?;
+    File::create(project.join("package.json"))?;
+    let record = temp_dir.path().join("exclusions.txt");
+
+    let cfg = || config::Config {
+        roots: vec![config::Root {
+            path: project.display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let backend = Arc::new(FileBackend::new(&record));
+    let (stats, _) = explorer::run_explorer_with_backend(cfg(), backend.clone(), 1, false)?;
+    assert_eq!(stats.newly_excluded, 1);
+    assert_eq!(backend.excluded_paths(), vec![project.join("node_modules")]);
+
+    // A new backend on the same file sees the recorded exclusion
+    let backend = Arc::new(FileBackend::new(&record));
+    let (stats, _) = explorer::run_explorer_with_backend(cfg(), backend.clone(), 1, false)?;
+    assert_eq!(stats.newly_excluded, 0);
+
+    assert!(backend.include(&project.join("node_modules"), config::ExclusionType::Sticky));
+    assert!(backend.excluded_paths().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_noop_backend_only_reports_exclusions() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(project.join("node_modules"))?;
+    File::create(project.join("package.json"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: project.display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let (stats, _) = explorer::run_explorer_with_backend(cfg, Arc::new(NoopBackend), 1, false)?;
+
+    // Without Time Machine the exclusion is found, but not reported as newly excluded
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.newly_excluded, 0);
+
+    Ok(())
+}
+
+/// Backend refusing fixed-path exclusions, like `tmutil` without root privileges
+struct UnprivilegedBackend(MockBackend);
+
+impl BackupExclusionBackend for UnprivilegedBackend {
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.0.is_excluded(path)
+    }
+
+    fn add(&self, path: &Path, exclusion_type: config::ExclusionType) -> bool {
+        self.0.add(path, exclusion_type)
+    }
+
+    fn remove(&self, path: &Path, exclusion_type: config::ExclusionType) -> bool {
+        self.0.remove(path, exclusion_type)
+    }
+
+    fn requires_elevation(&self, exclusion_type: config::ExclusionType) -> bool {
+        exclusion_type == config::ExclusionType::Fixed
+    }
+}
+
+#[test]
+fn test_fixed_path_rules_fail_without_elevation() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(project.join("DerivedData"))?;
+    File::create(project.join("App.xcodeproj"))?;
+
+    let make_config = |dry_run| config::Config {
+        roots: vec![config::Root {
+            path: project.display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![config::Rule {
+            name: "xcode".to_string(),
+            file_match: "*.xcodeproj".to_string(),
+            exclusions: vec!["DerivedData".into()],
+            exclusion_type: config::ExclusionType::Fixed,
+            ..Default::default()
+        }],
+        dry_run,
+        ..Default::default()
+    };
+
+    let backend = Arc::new(UnprivilegedBackend(MockBackend::new()));
+    let error = explorer::run_explorer_with_backend(make_config(false), backend.clone(), 1, false)
+        .err()
+        .expect("fixed-path rules need root");
+    assert!(format!("{:#}", error).contains("xcode"));
+    assert!(format!("{:#}", error).contains("privilege_helper"));
+    assert!(backend.0.excluded_paths().is_empty());
+
+    // The library reports the same failure as a typed error
+    let error = explorer::Explorer::builder()
+        .config(make_config(false))
+        .backend(backend.clone())
+        .threads(1)
+        .run()
+        .expect_err("fixed-path rules need root");
+    assert!(matches!(
+        error,
+        asimeow::error::Error::Backend {
+            kind: asimeow::error::BackendErrorKind::ElevationRequired,
+            ..
+        }
+    ));
+    assert_eq!(error.failure(), Some(asimeow::exit::Failure::Environment));
+
+    // A dry run changes nothing, so it needs no privileges
+    let (stats, _) = explorer::run_explorer_with_backend(make_config(true), backend, 1, false)?;
+    assert_eq!(stats.exclusions_found, 1);
+
+    Ok(())
+}
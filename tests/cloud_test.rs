@@ -0,0 +1,68 @@
+use anyhow::Result;
+use asimeow::cloud::{self, CloudSync, SyncedFolders};
+use asimeow::config::{self, Backend};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_synced_by() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let home = temp_dir.path();
+    let app = |folder: &str| home.join(folder).join("app/node_modules");
+    let folders = SyncedFolders::of(home);
+    assert_eq!(cloud::synced_by(&app("Documents"), &folders), None);
+
+    fs::create_dir_all(home.join("Library/Mobile Documents/com~apple~CloudDocs/Documents"))?;
+    fs::create_dir_all(home.join("Library/CloudStorage/Dropbox"))?;
+    fs::create_dir_all(home.join("Library/CloudStorage/OneDrive-Personal"))?;
+    fs::create_dir_all(home.join("OneDrive - Contoso"))?;
+    let folders = SyncedFolders::of(home);
+
+    assert_eq!(
+        cloud::synced_by(&app("Documents"), &folders),
+        Some(CloudSync::ICloud)
+    );
+    assert_eq!(cloud::synced_by(&app("Desktop"), &folders), None);
+    assert_eq!(
+        cloud::synced_by(&app("Library/CloudStorage/Dropbox"), &folders),
+        Some(CloudSync::Dropbox)
+    );
+    assert_eq!(
+        cloud::synced_by(&app("Library/CloudStorage/OneDrive-Personal"), &folders),
+        Some(CloudSync::OneDrive)
+    );
+    assert_eq!(
+        cloud::synced_by(&app("OneDrive - Contoso"), &folders),
+        Some(CloudSync::OneDrive)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sync_warning() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let home = temp_dir.path();
+    fs::create_dir_all(home.join("Library/Mobile Documents/com~apple~CloudDocs/Documents"))?;
+    fs::create_dir_all(home.join("OneDrive"))?;
+    let folders = SyncedFolders::of(home);
+
+    let mut rule = config::Rule {
+        name: "node".to_string(),
+        ..Default::default()
+    };
+    let documents = home.join("Documents/app/node_modules");
+    let warning = cloud::sync_warning(&documents, &rule, &folders).unwrap();
+    assert!(warning.contains("iCloud Drive"));
+    assert!(warning.contains("add `icloud` to the backends"));
+    assert!(cloud::sync_warning(&home.join("Desktop/app/node_modules"), &rule, &folders).is_none());
+
+    // OneDrive has no backend, so the warning stays
+    let onedrive = home.join("OneDrive/app/node_modules");
+    rule.backends = vec![Backend::TimeMachine, Backend::ICloud];
+    assert!(cloud::sync_warning(&documents, &rule, &folders).is_none());
+    let warning = cloud::sync_warning(&onedrive, &rule, &folders).unwrap();
+    assert!(warning.contains("move it out of the OneDrive folder"));
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
 
+    use anyhow::Result;
+    use asimeow::config;
     use std::fs;
     use std::path::Path;
 
@@ -58,4 +60,262 @@ mod tests {
                 .unwrap_or_else(|| panic!("Rule {} 'exclusions' is not an array", i));
         }
     }
+
+    #[test]
+    fn test_rule_enabled_flag_and_filters() -> Result<()> {
+        let rules_yaml = r#"
+    - name: node
+      file_match: package.json
+      exclusions: [node_modules]
+      tags: [js, heavy]
+    - name: bower
+      file_match: bower.json
+      exclusions: [bower_components]
+      tags: [js]
+    - name: rust
+      file_match: Cargo.toml
+      exclusions: [target]
+      tags: [heavy]
+    - name: go
+      file_match: go.mod
+      exclusions: [vendor]
+      enabled: false
+    "#;
+        let rule_names = |filter: config::RuleFilter| -> Result<Vec<String>> {
+            let mut cfg = config::Config {
+                rules: serde_yaml::from_str(rules_yaml)?,
+                ..Default::default()
+            };
+            cfg.apply_rule_filter(&filter)?;
+            Ok(cfg.rules.into_iter().map(|rule| rule.name).collect())
+        };
+
+        // Disabled rules are always dropped
+        assert_eq!(
+            rule_names(config::RuleFilter::default())?,
+            vec!["node", "bower", "rust"]
+        );
+        assert_eq!(
+            rule_names(config::RuleFilter {
+                only_tags: vec!["js".to_string()],
+                ..Default::default()
+            })?,
+            vec!["node", "bower"]
+        );
+        assert_eq!(
+            rule_names(config::RuleFilter {
+                only_tags: vec!["js".to_string()],
+                skip_tags: vec!["heavy".to_string()],
+                ..Default::default()
+            })?,
+            vec!["bower"]
+        );
+        assert_eq!(
+            rule_names(config::RuleFilter {
+                rule_names: vec!["rust".to_string(), "go".to_string()],
+                ..Default::default()
+            })?,
+            vec!["rust"]
+        );
+        // Unknown names are rejected rather than silently selecting no rule
+        assert!(rule_names(config::RuleFilter {
+            rule_names: vec!["rsut".to_string()],
+            ..Default::default()
+        })
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_retain_roots() -> Result<()> {
+        let roots_yaml = r#"
+    roots:
+      - path: ~/Development
+      - path: /Volumes/External/projects/
+      - path: ~/Documents
+    exclude_paths:
+      - ~/Development/huge-dataset
+      - ~/Library/Caches
+    rules: []
+    "#;
+        let retain = |selectors: &[&str]| -> Result<config::Config> {
+            let mut cfg: config::Config = serde_yaml::from_str(roots_yaml)?;
+            let selectors: Vec<String> = selectors.iter().map(|s| s.to_string()).collect();
+            cfg.retain_roots(&selectors)?;
+            Ok(cfg)
+        };
+        let root_paths = |cfg: &config::Config| -> Vec<String> {
+            cfg.roots.iter().map(|root| root.path.clone()).collect()
+        };
+
+        // No selector keeps everything
+        assert_eq!(retain(&[])?.roots.len(), 3);
+
+        // By name, as configured, or expanded
+        let cfg = retain(&["Development"])?;
+        assert_eq!(root_paths(&cfg), vec!["~/Development"]);
+        assert_eq!(cfg.exclude_paths, vec!["~/Development/huge-dataset"]);
+        let home = dirs::home_dir().unwrap();
+        let cfg = retain(&[
+            "/Volumes/External/projects",
+            &home.join("Documents").display().to_string(),
+        ])?;
+        assert_eq!(
+            root_paths(&cfg),
+            vec!["/Volumes/External/projects/", "~/Documents"]
+        );
+        assert!(cfg.exclude_paths.is_empty());
+
+        assert!(retain(&["Downloads"]).is_err());
+        let mut cfg: config::Config = serde_yaml::from_str(roots_yaml)?;
+        assert!(matches!(
+            cfg.retain_roots(&["Downloads".to_string()]),
+            Err(asimeow::error::Error::Config { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pattern_file_rules() -> Result<()> {
+        let patterns = config::parse_pattern_file("# experiments\n.venv\n\n*.egg-info\ntmp/\n")?;
+        assert_eq!(patterns, vec![".venv", "*.egg-info", "tmp"]);
+        assert!(config::parse_pattern_file("src/generated\n").is_err());
+
+        let mut cfg = config::Config::default();
+        cfg.add_pattern_rules(&patterns);
+        let file_matches: Vec<&str> = cfg
+            .rules
+            .iter()
+            .map(|rule| rule.file_match.as_str())
+            .collect();
+        // Directories only, excluding the matched directory itself
+        assert_eq!(file_matches, vec![".venv/", "*.egg-info/", "tmp/"]);
+        assert!(cfg.rules.iter().all(|rule| rule.exclusions.is_empty()));
+        assert_eq!(cfg.rules[0].name, "exclude-from:.venv");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_protected() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let config_path = temp_dir.path().join("config.yaml");
+        fs::write(
+            &config_path,
+            "roots:\n  - path: ~/Development\nprotected:\n  - ~/Development/thesis\nrules: []\n",
+        )?;
+        let home = dirs::home_dir().unwrap();
+
+        let added = config::append_protected(
+            config_path.to_str().unwrap(),
+            &[
+                home.join("Development/thesis"),
+                home.join("Development/app/vendor"),
+                Path::new("/Volumes/External/data").to_path_buf(),
+            ],
+        )?;
+        assert_eq!(
+            added,
+            vec!["~/Development/app/vendor", "/Volumes/External/data"]
+        );
+
+        let (cfg, _) = config::read_config(config_path.to_str(), false)?;
+        assert_eq!(
+            cfg.protected,
+            vec![
+                "~/Development/thesis",
+                "~/Development/app/vendor",
+                "/Volumes/External/data"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_path_with_env_vars() -> Result<()> {
+        std::env::set_var("ASIMEOW_TEST_VMS", "/tmp/vms");
+
+        assert_eq!(
+            config::expand_path("$ASIMEOW_TEST_VMS/disk.img")?,
+            Path::new("/tmp/vms/disk.img")
+        );
+        assert_eq!(
+            config::expand_path("${ASIMEOW_TEST_VMS}-old")?,
+            Path::new("/tmp/vms-old")
+        );
+        assert!(config::expand_path("$ASIMEOW_TEST_UNSET_VAR/disk.img").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rule_exclusion_type_and_backends() -> Result<()> {
+        let rules_yaml = r#"
+    - name: derived-data
+      file_match: DerivedData/
+      exclusions: [DerivedData]
+      exclusion_type: fixed
+    - name: node
+      file_match: package.json
+      exclusions: [node_modules]
+      backends: [time_machine, spotlight]
+    "#;
+        let rules: Vec<config::Rule> = serde_yaml::from_str(rules_yaml)?;
+
+        assert_eq!(rules[0].exclusion_type, config::ExclusionType::Fixed);
+        assert_eq!(rules[0].backends(), vec![config::Backend::TimeMachine]);
+        assert_eq!(rules[1].exclusion_type, config::ExclusionType::Sticky);
+        assert_eq!(
+            rules[1].backends(),
+            vec![config::Backend::TimeMachine, config::Backend::Spotlight]
+        );
+
+        // Defaults are not serialized
+        let yaml = serde_yaml::to_string(&rules[1])?;
+        assert!(!yaml.contains("exclusion_type"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bazel_rules_skip_nx_workspaces() -> Result<()> {
+        let shipped: config::Config = serde_yaml::from_str(&fs::read_to_string("config.yaml")?)?;
+        for cfg in [config::default_config(), shipped] {
+            let bazel_rules: Vec<&config::Rule> = cfg
+                .rules
+                .iter()
+                .filter(|rule| rule.name.starts_with("bazel-workspace"))
+                .collect();
+            let matched_by = |name: &str| {
+                bazel_rules
+                    .iter()
+                    .filter(|rule| asimeow::explorer::file_match_matches(rule, Path::new(name)))
+                    .count()
+            };
+
+            assert_eq!(matched_by("WORKSPACE"), 1);
+            assert_eq!(matched_by("WORKSPACE.bazel"), 1);
+            assert_eq!(matched_by("workspace.json"), 0);
+            assert_eq!(matched_by("WORKSPACE.bzlmod"), 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_rules_named_like_config_yaml() -> Result<()> {
+        let shipped: config::Config = serde_yaml::from_str(&fs::read_to_string("config.yaml")?)?;
+        // A rule matching the same files has the same name in both, so merging them
+        // does not give two names to one rule
+        for rule in config::default_config().rules {
+            if let Some(twin) = shipped
+                .rules
+                .iter()
+                .find(|other| other.file_match == rule.file_match)
+            {
+                assert_eq!(twin.name, rule.name, "rule for {}", rule.file_match);
+            }
+        }
+        Ok(())
+    }
 }
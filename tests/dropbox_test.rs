@@ -0,0 +1,64 @@
+use anyhow::Result;
+use asimeow::dropbox::{self, DropboxFolders, SyncRoot};
+use std::fs::{self, File};
+use tempfile::tempdir;
+
+#[test]
+fn test_sync_root_from_dropbox_info() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let home = temp_dir.path();
+    let personal = home.join("Dropbox");
+    let business = home.join("Dropbox (Acme)");
+    fs::create_dir_all(home.join(".dropbox"))?;
+    fs::write(
+        home.join(".dropbox/info.json"),
+        format!(
+            r#"{{"personal": {{"path": "{}", "host": 1}}, "business": {{"path": "{}", "host": 2}}}}"#,
+            personal.display(),
+            business.display()
+        ),
+    )?;
+
+    assert_eq!(
+        dropbox::dropbox_folders(home),
+        vec![personal.clone(), business]
+    );
+    let folders = DropboxFolders::of(home);
+    assert_eq!(
+        dropbox::sync_root(&personal.join("app/node_modules"), &folders),
+        Some(SyncRoot::Dropbox(personal))
+    );
+    assert_eq!(dropbox::sync_root(&home.join("code/app"), &folders), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_maestral_paths_are_added_to_mignore() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let home = temp_dir.path();
+    let root = home.join("Dropbox (Maestral)");
+    let node_modules = root.join("app/node_modules");
+    fs::create_dir_all(&node_modules)?;
+    File::create(root.join("app/package.json"))?;
+    fs::create_dir_all(home.join(".config/maestral"))?;
+    fs::write(
+        home.join(".config/maestral/maestral.ini"),
+        format!("[sync]\npath = {}\nexcluded_items = []\n", root.display()),
+    )?;
+    fs::write(root.join(".mignore"), "*.tmp")?;
+
+    let sync_root = dropbox::sync_root(&node_modules, &DropboxFolders::of(home));
+    assert_eq!(sync_root, Some(SyncRoot::Maestral(root.clone())));
+    let sync_root = sync_root.unwrap();
+
+    assert!(dropbox::ignore(&node_modules, &sync_root));
+    assert!(!dropbox::ignore(&node_modules, &sync_root));
+    assert!(dropbox::ignore(&root.join("app/package.json"), &sync_root));
+    assert_eq!(
+        fs::read_to_string(root.join(".mignore"))?,
+        "*.tmp\n/app/node_modules/\n/app/package.json\n"
+    );
+
+    Ok(())
+}
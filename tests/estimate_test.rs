@@ -0,0 +1,35 @@
+use anyhow::Result;
+use asimeow::backend::MockBackend;
+use asimeow::estimate;
+use asimeow::size::SizeMode;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_estimate_entries() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let small = temp_dir.path().join("small");
+    let large = temp_dir.path().join("large");
+    let cached = temp_dir.path().join("cached");
+    fs::create_dir_all(&small)?;
+    fs::create_dir_all(&large)?;
+    fs::create_dir_all(&cached)?;
+    fs::write(small.join("a"), vec![0u8; 1_000])?;
+    fs::write(large.join("a"), vec![0u8; 50_000])?;
+    fs::write(large.join("b"), vec![0u8; 50_000])?;
+    fs::write(cached.join("a"), vec![0u8; 10_000])?;
+
+    let backend = MockBackend::with_excluded([cached.clone()]);
+    let exclusions = vec![cached.clone(), large.clone(), small.clone()];
+    let entries = estimate::estimate_entries(&exclusions, &backend, 2, SizeMode::Apparent);
+
+    // Largest first, already excluded paths flagged
+    let paths: Vec<_> = entries.iter().map(|entry| entry.path.clone()).collect();
+    assert_eq!(paths, vec![large, cached, small]);
+    assert_eq!(entries[0].size, 100_000);
+    assert!(!entries[0].already_excluded);
+    assert!(entries[1].already_excluded);
+
+    assert!(estimate::estimate_entries(&[], &backend, 4, SizeMode::Apparent).is_empty());
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use anyhow::Result;
+use asimeow::backend::MockBackend;
+use asimeow::config;
+use asimeow::events::{Event, ExclusionOutcome, MessageLevel};
+use asimeow::explorer::Explorer;
+use asimeow::output::ErrorKind;
+use std::fs::{self, File};
+use std::sync::{Arc, Mutex};
+use tempfile::tempdir;
+
+#[test]
+fn test_builder_reports_events() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("workspace");
+    let app = root.join("app");
+    fs::create_dir_all(app.join("node_modules"))?;
+    fs::create_dir_all(app.join("dist"))?;
+    File::create(app.join("package.json"))?;
+    let missing = temp_dir.path().join("missing");
+
+    let cfg = config::Config {
+        roots: vec![
+            config::Root {
+                path: root.display().to_string(),
+                ..Default::default()
+            },
+            config::Root {
+                path: missing.display().to_string(),
+                ..Default::default()
+            },
+        ],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into(), "dist".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let backend = Arc::new(MockBackend::with_excluded([app.join("dist")]));
+    let stats = Explorer::builder()
+        .config(cfg)
+        .threads(2)
+        .backend(backend.clone())
+        .on_event({
+            let events = events.clone();
+            move |event| events.lock().unwrap().push(event.clone())
+        })
+        .run()?;
+    assert_eq!(stats.exclusions_found, 2);
+    assert_eq!(
+        backend.excluded_paths(),
+        vec![app.join("dist"), app.join("node_modules")]
+    );
+
+    let events = events.lock().unwrap();
+    let scanned: Vec<&Event> = events
+        .iter()
+        .filter(|event| matches!(event, Event::DirScanned { .. }))
+        .collect();
+    assert_eq!(scanned.len(), stats.processed_paths as usize);
+    assert!(events.contains(&Event::DirScanned { path: app.clone() }));
+    assert!(events.contains(&Event::RuleMatched {
+        rule: "node".to_string(),
+        folder: app.clone(),
+        entry: app.join("package.json"),
+    }));
+    assert!(events.contains(&Event::Excluded {
+        rule: "node".to_string(),
+        path: app.join("node_modules"),
+        outcome: ExclusionOutcome::Excluded,
+    }));
+    assert!(events.contains(&Event::Excluded {
+        rule: "node".to_string(),
+        path: app.join("dist"),
+        outcome: ExclusionOutcome::AlreadyExcluded,
+    }));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        Event::Error(error) if error.kind == ErrorKind::NotFound && error.path == missing
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn test_builder_dry_run_events() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let app = temp_dir.path().join("app");
+    fs::create_dir_all(app.join("node_modules"))?;
+    File::create(app.join("package.json"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: temp_dir.path().display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        dry_run: true,
+        ..Default::default()
+    };
+
+    let outcomes = Arc::new(Mutex::new(Vec::new()));
+    let backend = Arc::new(MockBackend::new());
+    Explorer::builder()
+        .config(cfg)
+        .backend(backend.clone())
+        .on_event({
+            let outcomes = outcomes.clone();
+            move |event| {
+                if let Event::Excluded { outcome, .. } = event {
+                    outcomes.lock().unwrap().push(*outcome);
+                }
+            }
+        })
+        .run()?;
+
+    assert_eq!(*outcomes.lock().unwrap(), vec![ExclusionOutcome::DryRun]);
+    assert!(backend.excluded_paths().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_verbose_lines_are_events() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let app = temp_dir.path().join("app");
+    fs::create_dir_all(app.join("node_modules"))?;
+    File::create(app.join("package.json"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: temp_dir.path().display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    // The reporter prints the lines of a verbose run, instead of the workers
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    Explorer::builder()
+        .config(cfg)
+        .verbose(true)
+        .backend(Arc::new(MockBackend::new()))
+        .on_event({
+            let messages = messages.clone();
+            move |event| {
+                if let Event::Message { level, text } = event {
+                    messages.lock().unwrap().push((*level, text.clone()));
+                }
+            }
+        })
+        .run()?;
+
+    let messages = messages.lock().unwrap();
+    assert!(messages.contains(&(
+        MessageLevel::Verbose,
+        format!("Processing path: {}", app.display())
+    )));
+    assert!(messages
+        .iter()
+        .any(|(_, text)| text.starts_with("Found match for rule 'node'")));
+
+    Ok(())
+}
+
+#[test]
+fn test_warnings_are_events() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let app = temp_dir.path().join("app");
+    fs::create_dir_all(app.join("node_modules"))?;
+    File::create(app.join("package.json"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: temp_dir.path().display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            action: config::RuleAction::Warn,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    // Without a reporter the lines only reach the handler, nothing is printed
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    Explorer::builder()
+        .config(cfg)
+        .backend(Arc::new(MockBackend::new()))
+        .on_event({
+            let messages = messages.clone();
+            move |event| {
+                if let Event::Message { level, text } = event {
+                    messages.lock().unwrap().push((*level, text.clone()));
+                }
+            }
+        })
+        .run()?;
+
+    let messages = messages.lock().unwrap();
+    assert!(messages.contains(&(
+        MessageLevel::Warning,
+        format!("⚠️  {} - node [info]", app.join("node_modules").display())
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_yields_findings_as_they_come() -> Result<()> {
+    let temp_dir = tempdir()?;
+    for project in ["a", "b", "c"] {
+        let project = temp_dir.path().join(project);
+        fs::create_dir_all(project.join("node_modules"))?;
+        File::create(project.join("package.json"))?;
+    }
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: temp_dir.path().display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let handled = Arc::new(Mutex::new(0));
+    let mut stream = Explorer::builder()
+        .config(cfg.clone())
+        .threads(2)
+        .backend(Arc::new(MockBackend::new()))
+        .on_event({
+            let handled = handled.clone();
+            move |_| *handled.lock().unwrap() += 1
+        })
+        .stream();
+
+    let mut excluded: Vec<_> = stream
+        .by_ref()
+        .filter_map(|event| match event {
+            Event::Excluded { path, .. } => Some(path),
+            _ => None,
+        })
+        .collect();
+    excluded.sort();
+    assert_eq!(
+        excluded,
+        ["a", "b", "c"].map(|project| temp_dir.path().join(project).join("node_modules"))
+    );
+
+    let stats = stream.finish()?;
+    assert_eq!(stats.exclusions_found, 3);
+    // The handler of on_event still receives every event
+    assert!(*handled.lock().unwrap() >= 3 + stats.processed_paths as usize);
+
+    // Finishing early drops the remaining events but still waits for the scan
+    let stats = Explorer::builder()
+        .config(cfg)
+        .backend(Arc::new(MockBackend::new()))
+        .stream()
+        .finish()?;
+    assert_eq!(stats.exclusions_found, 3);
+
+    Ok(())
+}
@@ -14,20 +14,24 @@ fn test_exclusion_logic() -> Result<()> {
     let config = config::Config {
         roots: vec![config::Root {
             path: project_dir.to_str().unwrap().to_string(),
+            ..Default::default()
         }],
         ignore: vec![".git".to_string(), ".DS_Store".to_string()],
         rules: vec![
             config::Rule {
                 name: "node".to_string(),
                 file_match: "package.json".to_string(),
-                exclusions: vec!["node_modules".to_string(), "dist".to_string()],
+                exclusions: vec!["node_modules".into(), "dist".into()],
+                ..Default::default()
             },
             config::Rule {
                 name: "rust".to_string(),
                 file_match: "Cargo.toml".to_string(),
-                exclusions: vec!["target".to_string()],
+                exclusions: vec!["target".into()],
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     // Save the config
@@ -63,9 +67,8 @@ fn test_exclusion_logic() -> Result<()> {
     let result = explorer::process_path(
         &project_dir,
         state.clone(),
-        &config.rules,
+        &config,
         false, // verbose
-        &config.ignore,
     );
 
     // Verify the processing completed successfully
@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use asimeow::backend::MockBackend;
+use asimeow::config;
+use asimeow::exit::{self, Failure};
+use asimeow::explorer;
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+#[test]
+fn test_exit_codes_of_errors() {
+    let error = anyhow::Error::from(asimeow::error::Error::config("bad yaml"));
+    assert_eq!(exit::code_of(&error), exit::CONFIG_ERROR);
+    assert_eq!(error.to_string(), "bad yaml");
+
+    // Context added later keeps the category
+    let error = Err::<(), _>(error).context("Loading").unwrap_err();
+    assert_eq!(exit::failure_of(&error), Some(Failure::Config));
+
+    let error = exit::categorize(Failure::Environment, anyhow::anyhow!("no tmutil"));
+    assert_eq!(exit::code_of(&error), exit::ENVIRONMENT_ERROR);
+    // Unclassified failures have their own code, apart from the changes of `--check`
+    assert_eq!(exit::code_of(&anyhow::anyhow!("other")), exit::OTHER_ERROR);
+
+    // Typed library errors carry their category
+    let error = config::load_config(Some("/nonexistent/asimeow.yaml"), false).unwrap_err();
+    assert!(matches!(error, asimeow::error::Error::Config { .. }));
+    let error = anyhow::Error::from(error).context("Loading");
+    assert_eq!(exit::code_of(&error), exit::CONFIG_ERROR);
+}
+
+#[test]
+fn test_scan_status() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("workspace");
+    fs::create_dir_all(root.join("app/node_modules"))?;
+    File::create(root.join("app/package.json"))?;
+
+    let cfg = |roots: Vec<PathBuf>| config::Config {
+        roots: roots
+            .iter()
+            .map(|root| config::Root {
+                path: root.display().to_string(),
+                ..Default::default()
+            })
+            .collect(),
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        dry_run: true,
+        ..Default::default()
+    };
+
+    // A check finds the path that is not excluded yet
+    let backend = Arc::new(MockBackend::new());
+    let (stats, state) =
+        explorer::run_explorer_with_backend(cfg(vec![root.clone()]), backend, 2, false)?;
+    let pending = exit::pending_exclusions(&state);
+    assert_eq!(pending, vec![root.join("app/node_modules")]);
+    assert!(exit::scan_status(&stats).is_ok());
+    assert_eq!(exit::check_status(&pending), exit::CHANGES);
+
+    // Nothing pending once excluded
+    let backend = Arc::new(MockBackend::with_excluded([root.join("app/node_modules")]));
+    let (stats, state) =
+        explorer::run_explorer_with_backend(cfg(vec![root.clone()]), backend, 2, false)?;
+    assert!(exit::scan_status(&stats).is_ok());
+    assert_eq!(
+        exit::check_status(&exit::pending_exclusions(&state)),
+        exit::SUCCESS
+    );
+
+    // A root that cannot be scanned is a partial failure
+    let backend = Arc::new(MockBackend::new());
+    let (stats, _) = explorer::run_explorer_with_backend(
+        cfg(vec![root, temp_dir.path().join("missing")]),
+        backend,
+        2,
+        false,
+    )?;
+    assert_eq!(stats.errors, 1);
+    let error = exit::scan_status(&stats).unwrap_err();
+    assert_eq!(exit::code_of(&error), exit::PARTIAL_FAILURE);
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use anyhow::Result;
+use asimeow::backend::MockBackend;
+use asimeow::config;
+use asimeow::explain::{self, ExclusionOutcome};
+use std::fs::{self, File};
+use tempfile::tempdir;
+
+#[test]
+fn test_evaluate_rule_explains_outcomes() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let project = temp_dir.path().join("game");
+    for dir in ["Assets", "Library", "Logs"] {
+        fs::create_dir_all(project.join(dir))?;
+    }
+
+    let rule = config::Rule {
+        name: "unity".to_string(),
+        file_match: "Assets/".to_string(),
+        requires: vec!["ProjectSettings".to_string()],
+        exclusions: vec!["Library".into(), "Temp".into(), "Logs".into()],
+        ..Default::default()
+    };
+    let cfg = config::Config {
+        keep: vec![project.join("Logs").display().to_string()],
+        ..Default::default()
+    };
+
+    // ProjectSettings is missing
+    let evaluation = explain::evaluate_rule(&project, &rule, &cfg, false);
+    assert!(!evaluation.matches());
+    assert_eq!(evaluation.matched_entries, vec![project.join("Assets")]);
+    assert_eq!(evaluation.missing_requirements, vec!["ProjectSettings"]);
+    assert!(evaluation.exclusions.is_empty());
+
+    fs::create_dir_all(project.join("ProjectSettings"))?;
+    let evaluation = explain::evaluate_rule(&project, &rule, &cfg, false);
+    assert!(evaluation.matches());
+    assert_eq!(
+        evaluation.exclusions,
+        vec![
+            (project.join("Library"), ExclusionOutcome::Exclude),
+            (
+                project.join("Logs"),
+                ExclusionOutcome::Skipped("matches a keep entry".to_string())
+            ),
+        ]
+    );
+    assert_eq!(evaluation.descend, config::Descend::Continue);
+
+    Ok(())
+}
+
+#[test]
+fn test_evaluate_rule_without_exclusions_targets_matches() -> Result<()> {
+    let temp_dir = tempdir()?;
+    File::create(temp_dir.path().join("disk.iso"))?;
+
+    let rule = config::Rule {
+        name: "iso".to_string(),
+        file_match: "*.iso".to_string(),
+        action: config::RuleAction::Report,
+        ..Default::default()
+    };
+    let evaluation =
+        explain::evaluate_rule(temp_dir.path(), &rule, &config::Config::default(), false);
+
+    assert_eq!(
+        evaluation.exclusions,
+        vec![(temp_dir.path().join("disk.iso"), ExclusionOutcome::Report)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_find_responsible_walks_ancestors() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let app = temp_dir.path().join("app");
+    let module = app.join("node_modules").join("react");
+    fs::create_dir_all(&module)?;
+    File::create(app.join("package.json"))?;
+    fs::create_dir_all(temp_dir.path().join("scratch"))?;
+    File::create(temp_dir.path().join("scratch").join(".nobackup"))?;
+
+    let cfg = config::Config {
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        explain::find_responsible(&module, &cfg, false),
+        vec![explain::Responsibility {
+            source: "node".to_string(),
+            folder: app.clone(),
+            target: app.join("node_modules"),
+            outcome: ExclusionOutcome::Exclude,
+        }]
+    );
+    assert!(explain::find_responsible(&app, &cfg, false).is_empty());
+
+    let scratch = temp_dir.path().join("scratch");
+    let responsible = explain::find_responsible(&scratch, &cfg, false);
+    assert_eq!(responsible.len(), 1);
+    assert_eq!(responsible[0].source, "nobackup");
+
+    Ok(())
+}
+
+#[test]
+fn test_why_not_traces_traversal_and_rules() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("dev");
+    let app = root.join("app");
+    fs::create_dir_all(app.join("node_modules"))?;
+    fs::create_dir_all(app.join("build"))?;
+    fs::create_dir_all(app.join("misc"))?;
+    File::create(app.join("package.json"))?;
+    let mono = root.join("mono");
+    fs::create_dir_all(mono.join("web/node_modules"))?;
+    File::create(mono.join("web/package.json"))?;
+    File::create(mono.join("WORKSPACE"))?;
+    fs::create_dir_all(root.join("vendor/lib/node_modules"))?;
+    File::create(root.join("vendor/lib/package.json"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: root.display().to_string(),
+            ..Default::default()
+        }],
+        ignore: vec!["vendor".to_string()],
+        rules: vec![
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into(), "Build".into()],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "bazel".to_string(),
+                file_match: "WORKSPACE".to_string(),
+                exclusions: vec!["bazel-out".into()],
+                descend: Some(config::Descend::Stop),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let backend = MockBackend::new();
+    let why_not = |path: &std::path::Path| explain::why_not(path, &cfg, &backend, false);
+    let has = |findings: &[String], text: &str| findings.iter().any(|f| f.contains(text));
+
+    // Targeted and reachable: the next scan excludes it
+    let findings = why_not(&app.join("node_modules"));
+    assert!(has(&findings, "✅ Rule 'node'"), "{:?}", findings);
+
+    // An exclusion entry differing by case does not resolve on case-sensitive volumes
+    if !app.join("BUILD").exists() {
+        let findings = why_not(&app.join("build"));
+        assert!(
+            has(&findings, "'Build' differs from 'build' by case"),
+            "{:?}",
+            findings
+        );
+    }
+
+    // A parent rule stops the descent, an ignore pattern prunes the tree
+    let findings = why_not(&mono.join("web/node_modules"));
+    assert!(has(&findings, "Rule 'bazel' matched in"), "{:?}", findings);
+    assert!(has(&findings, "descend: stop"));
+    let findings = why_not(&root.join("vendor/lib/node_modules"));
+    assert!(has(&findings, "ignore pattern 'vendor'"), "{:?}", findings);
+
+    // Nothing names the path, or it is outside the roots
+    let findings = why_not(&app.join("misc"));
+    assert!(has(
+        &findings,
+        "No configured rule has an exclusion entry naming 'misc'"
+    ));
+    let findings = why_not(temp_dir.path());
+    assert!(has(&findings, "Not under any configured root"));
+
+    // Already excluded
+    let backend = MockBackend::with_excluded([app.join("misc")]);
+    let findings = explain::why_not(&app.join("misc"), &cfg, &backend, false);
+    assert!(has(&findings, "already excluded"));
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use anyhow::Result;
+use asimeow::backend::{BackupExclusionBackend, MockBackend};
 use asimeow::{config, explorer};
 use std::fs::{self, File};
+use std::path::PathBuf;
+use std::sync::Arc;
 use tempfile::tempdir;
 
 fn create_test_project(project_name: &str, rules: Vec<config::Rule>) -> Result<tempfile::TempDir> {
@@ -12,9 +15,11 @@ fn create_test_project(project_name: &str, rules: Vec<config::Rule>) -> Result<t
     let config = config::Config {
         roots: vec![config::Root {
             path: project_dir.to_str().unwrap().to_string(),
+            ..Default::default()
         }],
         ignore: vec![".git".to_string(), ".DS_Store".to_string()],
         rules,
+        ..Default::default()
     };
 
     // Save the config to the temp dir for reference
@@ -33,7 +38,8 @@ fn test_process_path_with_node_project() -> Result<()> {
         vec![config::Rule {
             name: "node".to_string(),
             file_match: "package.json".to_string(),
-            exclusions: vec!["node_modules".to_string(), "dist".to_string()],
+            exclusions: vec!["node_modules".into(), "dist".into()],
+            ..Default::default()
         }],
     )?;
 
@@ -77,7 +83,8 @@ fn test_does_not_enqueue_children_of_excluded_dir() -> Result<()> {
         vec![config::Rule {
             name: "node".to_string(),
             file_match: "package.json".to_string(),
-            exclusions: vec!["node_modules".to_string()],
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
         }],
     )?;
 
@@ -118,7 +125,8 @@ fn test_ignore_patterns() -> Result<()> {
         vec![config::Rule {
             name: "node".to_string(),
             file_match: "package.json".to_string(),
-            exclusions: vec!["node_modules".to_string()],
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
         }],
     )?;
 
@@ -149,6 +157,390 @@ fn test_ignore_patterns() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_full_path_ignore_patterns() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-path-ignores",
+        vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+    )?;
+    let project_dir = temp_dir.path().join("test-path-ignores");
+
+    let containers = project_dir.join("Library").join("Containers");
+    let archive = project_dir.join("archive");
+    for project in [
+        containers.join("app"),
+        archive.join("old"),
+        project_dir.join("app"),
+    ] {
+        fs::create_dir_all(project.join("node_modules"))?;
+        File::create(project.join("package.json"))?;
+    }
+
+    let ignore = vec![
+        "**/Library/Containers/**".to_string(),
+        format!("{}/archive/**", project_dir.display()),
+    ];
+    assert!(explorer::is_ignored(&containers.join("app"), &ignore));
+    assert!(explorer::is_ignored(&archive.join("old"), &ignore));
+    assert!(!explorer::is_ignored(&project_dir.join("app"), &ignore));
+    // `*` does not cross directories, and path entries never match a bare name
+    assert!(!explorer::is_ignored(
+        &containers.join("app").join("src"),
+        &["**/Containers/*/src/x".to_string()]
+    ));
+    assert!(!explorer::is_ignored(
+        &project_dir.join("Containers"),
+        &["Library/Containers".to_string()]
+    ));
+    assert!(explorer::is_ignored(
+        &containers,
+        &["Library/Containers".to_string()]
+    ));
+
+    let (mut cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    cfg.ignore.extend(ignore);
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Only the node_modules of the project outside the ignored locations is found
+    assert_eq!(stats.exclusions_found, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_absolute_ignore_paths() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-absolute-ignores",
+        vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+    )?;
+    let project_dir = temp_dir.path().join("test-absolute-ignores");
+
+    let untouched = project_dir.join("do-not-touch");
+    let elsewhere = project_dir.join("other").join("do-not-touch");
+    for project in [&untouched, &elsewhere] {
+        fs::create_dir_all(project.join("node_modules"))?;
+        File::create(project.join("package.json"))?;
+    }
+
+    let home = dirs::home_dir().unwrap();
+    assert_eq!(
+        explorer::absolute_ignore_path("~/Development/do-not-touch/"),
+        Some(home.join("Development").join("do-not-touch"))
+    );
+    assert_eq!(explorer::absolute_ignore_path("do-not-touch"), None);
+    assert_eq!(explorer::absolute_ignore_path("~/Development/*"), None);
+
+    // Only that location is pruned, not other directories with the same name
+    let ignore = vec![format!("{}/", untouched.display())];
+    assert!(explorer::is_ignored(&untouched, &ignore));
+    assert!(!explorer::is_ignored(&elsewhere, &ignore));
+
+    let (mut cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    cfg.ignore.extend(ignore);
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+    assert_eq!(stats.exclusions_found, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_root_relative_ignore_patterns() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let development = temp_dir.path().join("Development");
+    let sandbox = development.join("experiments").join("rust").join("sandbox");
+    let deep = development
+        .join("experiments")
+        .join("rust")
+        .join("nested")
+        .join("sandbox");
+    let outside = temp_dir
+        .path()
+        .join("other")
+        .join("experiments")
+        .join("rust")
+        .join("sandbox");
+    for project in [&sandbox, &deep, &outside] {
+        fs::create_dir_all(project.join("node_modules"))?;
+        File::create(project.join("package.json"))?;
+    }
+
+    let roots = vec![
+        config::Root {
+            path: development.display().to_string(),
+            ignore: vec!["experiments/*/sandbox".to_string()],
+            ..Default::default()
+        },
+        config::Root {
+            path: temp_dir.path().join("other").display().to_string(),
+            ..Default::default()
+        },
+    ];
+    // Patterns are anchored at their root, and `*` does not cross directories
+    assert_eq!(
+        explorer::root_ignore_pattern(&sandbox, &roots),
+        Some("experiments/*/sandbox")
+    );
+    assert_eq!(explorer::root_ignore_pattern(&deep, &roots), None);
+    assert_eq!(explorer::root_ignore_pattern(&outside, &roots), None);
+
+    let cfg = config::Config {
+        roots,
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Only the sandbox under the first root is skipped
+    assert_eq!(stats.exclusions_found, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_follow_symlinks_with_loop_protection() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let workspace = temp_dir.path().join("workspace");
+    let external = temp_dir.path().join("ssd").join("app");
+    for project in [workspace.join("local"), external.clone()] {
+        fs::create_dir_all(project.join("node_modules"))?;
+        File::create(project.join("package.json"))?;
+    }
+    std::os::unix::fs::symlink(&external, workspace.join("app"))?;
+    // A cycle back to the root, and a second link to the same target
+    std::os::unix::fs::symlink(&workspace, workspace.join("local").join("loop"))?;
+    std::os::unix::fs::symlink(&external, workspace.join("local").join("app-again"))?;
+
+    let scan = |follow_symlinks: Option<bool>| {
+        let cfg = config::Config {
+            roots: vec![config::Root {
+                path: workspace.display().to_string(),
+                ..Default::default()
+            }],
+            follow_symlinks,
+            rules: vec![config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        explorer::run_explorer_with_stats(cfg, 2, false)
+    };
+
+    // By default symlinked directories are followed: the external project is reached
+    // once, and the cycle ends
+    let stats = scan(None)?;
+    assert_eq!(stats.exclusions_found, 2);
+    assert_eq!(stats.processed_paths, 3);
+
+    // `follow_symlinks: false` skips them
+    let stats = scan(Some(false))?;
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.processed_paths, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_max_depth() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let downloads = temp_dir.path().join("Downloads");
+    let development = temp_dir.path().join("Development");
+    for project in [
+        downloads.join("app"),
+        downloads.join("archive").join("old").join("app"),
+        development.join("work").join("app"),
+    ] {
+        fs::create_dir_all(project.join("node_modules"))?;
+        File::create(project.join("package.json"))?;
+    }
+
+    let mut cfg = config::Config {
+        roots: vec![
+            config::Root {
+                path: downloads.display().to_string(),
+                max_depth: Some(1),
+                ..Default::default()
+            },
+            config::Root {
+                path: development.display().to_string(),
+                ..Default::default()
+            },
+        ],
+        max_depth: Some(5),
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    // The root's own limit wins over the global one
+    let old = downloads.join("archive").join("old");
+    assert_eq!(explorer::depth_below_root(&old, &cfg), Some((1, 2)));
+    assert_eq!(
+        explorer::depth_below_root(&development.join("work"), &cfg),
+        Some((5, 1))
+    );
+
+    // Downloads/archive/old is beyond the limit of its root
+    let stats = explorer::run_explorer_with_stats(cfg.clone(), 1, false)?;
+    assert_eq!(stats.exclusions_found, 2);
+
+    // --max-depth replaces every configured limit
+    cfg.override_max_depth(Some(0));
+    assert_eq!(explorer::depth_below_root(&old, &cfg), Some((0, 2)));
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+    assert_eq!(stats.exclusions_found, 0);
+    assert_eq!(stats.processed_paths, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_stats_per_rule_and_root() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let web = temp_dir.path().join("web");
+    let rust = temp_dir.path().join("rust");
+    let missing = temp_dir.path().join("missing");
+    fs::create_dir_all(web.join("app").join("node_modules"))?;
+    File::create(web.join("app").join("package.json"))?;
+    fs::write(web.join("app").join("node_modules").join("index.js"), "x")?;
+    fs::create_dir_all(rust.join("cli").join("target"))?;
+    File::create(rust.join("cli").join("Cargo.toml"))?;
+    fs::write(rust.join("cli").join("target").join("cli"), "binary")?;
+
+    let root = |path: &std::path::Path| config::Root {
+        path: path.display().to_string(),
+        ..Default::default()
+    };
+    let cfg = config::Config {
+        roots: vec![root(&web), root(&rust), root(&missing)],
+        rules: vec![
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into()],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "rust".to_string(),
+                file_match: "Cargo.toml".to_string(),
+                exclusions: vec!["target".into()],
+                ..Default::default()
+            },
+        ],
+        size_mode: Some(asimeow::size::SizeMode::Apparent),
+        ..Default::default()
+    };
+    let (stats, _) =
+        explorer::run_explorer_with_backend(cfg, Arc::new(MockBackend::new()), 2, false)?;
+
+    assert_eq!(stats.exclusions_found, 2);
+    assert_eq!(stats.rules["node"].exclusions_found, 1);
+    assert_eq!(stats.rules["rust"].exclusions_found, 1);
+    assert_eq!(stats.roots[&web].processed_paths, 2);
+    assert_eq!(stats.roots[&web].exclusions_found, 1);
+    assert_eq!(stats.roots[&rust].exclusions_found, 1);
+    assert!(stats.roots[&web].duration <= stats.duration);
+
+    // The missing root is listed with no paths, and counted as an error
+    assert_eq!(stats.roots[&missing].processed_paths, 0);
+    assert_eq!(stats.roots[&missing].exclusions_found, 0);
+    assert_eq!(stats.errors, 1);
+    assert_eq!(
+        stats.errors_by_kind[&asimeow::output::ErrorKind::NotFound],
+        1
+    );
+
+    // "x" and "binary"
+    assert_eq!(stats.bytes, Some(7));
+
+    Ok(())
+}
+
+#[test]
+fn test_rule_counters() -> Result<()> {
+    let temp_dir = tempdir()?;
+    for app in ["one", "two"] {
+        let project = temp_dir.path().join(app);
+        fs::create_dir_all(project.join("node_modules"))?;
+        fs::create_dir_all(project.join(".next"))?;
+        File::create(project.join("package.json"))?;
+    }
+    let backend = MockBackend::new();
+    backend.add(
+        &temp_dir.path().join("one").join("node_modules"),
+        config::ExclusionType::Sticky,
+    );
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: temp_dir.path().display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into(), ".next".into()],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "rust".to_string(),
+                file_match: "Cargo.toml".to_string(),
+                exclusions: vec!["target".into()],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "disabled".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["dist".into()],
+                enabled: Some(false),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let (stats, state) = explorer::run_explorer_with_backend(cfg, Arc::new(backend), 2, false)?;
+
+    let node = &stats.rules["node"];
+    assert_eq!(
+        (node.matches, node.exclusions_found, node.newly_excluded),
+        (2, 4, 3)
+    );
+    // Rules that never match are listed too, disabled ones are not
+    assert_eq!(stats.rules["rust"], explorer::RuleStats::default());
+    assert!(!stats.rules.contains_key("disabled"));
+    assert_eq!(*state.rule_stats.read().unwrap(), stats.rules);
+
+    Ok(())
+}
+
 #[test]
 fn test_multiple_rules() -> Result<()> {
     // Create a project with multiple rules
@@ -158,12 +550,14 @@ fn test_multiple_rules() -> Result<()> {
             config::Rule {
                 name: "node".to_string(),
                 file_match: "package.json".to_string(),
-                exclusions: vec!["node_modules".to_string(), "dist".to_string()],
+                exclusions: vec!["node_modules".into(), "dist".into()],
+                ..Default::default()
             },
             config::Rule {
                 name: "rust".to_string(),
                 file_match: "Cargo.toml".to_string(),
-                exclusions: vec!["target".to_string()],
+                exclusions: vec!["target".into()],
+                ..Default::default()
             },
         ],
     )?;
@@ -201,7 +595,8 @@ fn test_nested_projects() -> Result<()> {
         vec![config::Rule {
             name: "node".to_string(),
             file_match: "package.json".to_string(),
-            exclusions: vec!["node_modules".to_string()],
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
         }],
     )?;
 
@@ -232,3 +627,1155 @@ fn test_nested_projects() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_glob_exclusion_entries() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-glob-exclusions",
+        vec![config::Rule {
+            name: "cmake".to_string(),
+            file_match: "CMakeLists.txt".to_string(),
+            exclusions: vec!["cmake-build-*".into()],
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-glob-exclusions");
+    File::create(project_dir.join("CMakeLists.txt"))?;
+    fs::create_dir_all(project_dir.join("cmake-build-debug").join("nested"))?;
+    fs::create_dir_all(project_dir.join("cmake-build-release"))?;
+    fs::create_dir_all(project_dir.join("src"))?;
+
+    // Glob entries expand against the children of the matched folder
+    let expanded = explorer::expand_exclusion(&project_dir, "cmake-build-*");
+    assert_eq!(
+        expanded,
+        vec![
+            project_dir.join("cmake-build-debug"),
+            project_dir.join("cmake-build-release"),
+        ]
+    );
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Both build folders are found, and only the root and src are traversed
+    assert_eq!(stats.exclusions_found, 2);
+    assert_eq!(stats.processed_paths, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_nested_path_exclusion_entries() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-nested-exclusions",
+        vec![config::Rule {
+            name: "rust".to_string(),
+            file_match: "Cargo.toml".to_string(),
+            exclusions: vec!["target/debug".into(), ".idea/cache*".into()],
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-nested-exclusions");
+    File::create(project_dir.join("Cargo.toml"))?;
+    fs::create_dir_all(project_dir.join("target").join("debug").join("deps"))?;
+    fs::create_dir_all(project_dir.join("target").join("release"))?;
+    fs::create_dir_all(project_dir.join(".idea").join("caches"))?;
+
+    assert_eq!(
+        explorer::expand_exclusion(&project_dir, "target/debug"),
+        vec![project_dir.join("target").join("debug")]
+    );
+    assert_eq!(
+        explorer::expand_exclusion(&project_dir, ".idea/cache*"),
+        vec![project_dir.join(".idea").join("caches")]
+    );
+    assert!(explorer::expand_exclusion(&project_dir, "target/missing").is_empty());
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 2, false)?;
+
+    // Only the nested folders are excluded: root, target, target/release and .idea
+    // are traversed, while target/debug and .idea/caches are pruned
+    assert_eq!(stats.exclusions_found, 2);
+    assert_eq!(stats.processed_paths, 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_file_pattern_exclusions() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-file-exclusions",
+        vec![config::Rule {
+            name: "xcode".to_string(),
+            file_match: "*.xcodeproj".to_string(),
+            exclusions: vec!["*.ipa".into(), "build/*.o".into()],
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-file-exclusions");
+    fs::create_dir_all(project_dir.join("App.xcodeproj"))?;
+    File::create(project_dir.join("App.ipa"))?;
+    File::create(project_dir.join("App.swift"))?;
+    fs::create_dir_all(project_dir.join("build"))?;
+    File::create(project_dir.join("build").join("main.o"))?;
+    File::create(project_dir.join("build").join("util.o"))?;
+
+    assert_eq!(
+        explorer::expand_exclusion(&project_dir, "build/*.o"),
+        vec![
+            project_dir.join("build").join("main.o"),
+            project_dir.join("build").join("util.o"),
+        ]
+    );
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // The .ipa and both object files are excluded individually
+    assert_eq!(stats.exclusions_found, 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_unicode_normalized_matching() -> Result<()> {
+    // Names as HFS+ stores them (decomposed) and patterns as typed (composed)
+    let decomposed = "Cafe\u{301}";
+    let composed = "Caf\u{e9}";
+
+    let temp_dir = tempdir()?;
+    let folder = temp_dir.path().join("project");
+    fs::create_dir_all(folder.join(format!("{}.xcodeproj", decomposed)))?;
+    fs::create_dir_all(folder.join(format!("{} Build", decomposed)))?;
+
+    let rule = config::Rule {
+        name: "xcode".to_string(),
+        file_match: format!("{}.xcodeproj", composed),
+        ..Default::default()
+    };
+    let marker = folder.join(format!("{}.xcodeproj", decomposed));
+    assert!(explorer::file_match_matches(&rule, &marker));
+
+    assert_eq!(
+        explorer::expand_exclusion(&folder, &format!("{} Build", composed)),
+        vec![folder.join(format!("{} Build", decomposed))]
+    );
+    assert_eq!(
+        explorer::expand_exclusion(&folder, &format!("{}*", composed)),
+        vec![
+            folder.join(format!("{} Build", decomposed)),
+            folder.join(format!("{}.xcodeproj", decomposed)),
+        ]
+    );
+
+    let ignored = folder.join(format!("{} Build", decomposed));
+    assert!(explorer::is_ignored(&ignored, &[format!("{} *", composed)]));
+
+    Ok(())
+}
+
+#[test]
+fn test_keep_entries_take_precedence() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("workspace");
+    let critical = root.join("critical-project");
+    let regular = root.join("regular-project");
+    for project in [&critical, &regular] {
+        fs::create_dir_all(project.join("node_modules"))?;
+        File::create(project.join("package.json"))?;
+    }
+
+    // Literal keep entries protect descendants and ancestors, globs protect descendants
+    let keep = vec![critical.display().to_string()];
+    assert!(explorer::is_kept(&critical.join("node_modules"), &keep));
+    assert!(explorer::is_kept(&root, &keep));
+    assert!(!explorer::is_kept(&regular.join("node_modules"), &keep));
+    let glob_keep = vec![format!("{}/crit*", root.display())];
+    assert!(explorer::is_kept(
+        &critical.join("node_modules"),
+        &glob_keep
+    ));
+    assert!(!explorer::is_kept(
+        &regular.join("node_modules"),
+        &glob_keep
+    ));
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: root.display().to_string(),
+            ..Default::default()
+        }],
+        keep,
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Only the regular project's node_modules is excluded
+    assert_eq!(stats.exclusions_found, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_protected_paths_are_never_excluded() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("workspace");
+    let models = root.join("ml-project").join("models");
+    fs::create_dir_all(&models)?;
+    fs::create_dir_all(root.join("ml-project").join(".venv"))?;
+    File::create(root.join("ml-project").join("requirements.txt"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: root.display().to_string(),
+            ..Default::default()
+        }],
+        protected: vec![format!("{}/*/models", root.display())],
+        rules: vec![config::Rule {
+            name: "python".to_string(),
+            file_match: "requirements.txt".to_string(),
+            exclusions: vec!["models".into(), ".venv".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    // Nothing protected is excluded in a fresh tree
+    let backend = Arc::new(MockBackend::new());
+    assert!(explorer::find_excluded_protected_paths(&cfg.protected, backend.as_ref()).is_empty());
+    assert_eq!(
+        explorer::find_excluded_protected_paths(
+            &cfg.protected,
+            &MockBackend::with_excluded([models.clone()])
+        ),
+        vec![models]
+    );
+
+    let (stats, _) = explorer::run_explorer_with_backend(cfg, backend.clone(), 1, false)?;
+
+    // Only .venv is excluded, models is protected
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(
+        backend.excluded_paths(),
+        vec![root.join("ml-project").join(".venv")]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_keep_marker_skips_exclusions() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-keep-marker",
+        vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-keep-marker");
+    let opted_out = project_dir.join("opted-out");
+    let nested = opted_out.join("packages").join("app");
+    let regular = project_dir.join("regular");
+    for project in [&nested, &regular] {
+        fs::create_dir_all(project.join("node_modules"))?;
+        File::create(project.join("package.json"))?;
+    }
+    File::create(opted_out.join(explorer::KEEP_MARKER))?;
+
+    assert_eq!(explorer::find_keep_marker(&nested), Some(opted_out.clone()));
+    assert_eq!(explorer::find_keep_marker(&regular), None);
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // The nested project below the marker is left alone
+    assert_eq!(stats.exclusions_found, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_nobackup_marker_excludes_directory() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-nobackup-marker",
+        vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-nobackup-marker");
+    let scratch = project_dir.join("scratch");
+    fs::create_dir_all(scratch.join("deep").join("deeper"))?;
+    File::create(scratch.join(explorer::NOBACKUP_MARKER))?;
+    // Would match the node rule, but lives below an excluded folder
+    File::create(scratch.join("deep").join("package.json"))?;
+    fs::create_dir_all(scratch.join("deep").join("node_modules"))?;
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Only the scratch folder is excluded and its children are not traversed
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.processed_paths, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_rule_priority_and_match_mode() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let project_dir = temp_dir.path().join("layered-project");
+    fs::create_dir_all(project_dir.join("node_modules"))?;
+    fs::create_dir_all(project_dir.join("dist"))?;
+    File::create(project_dir.join("package.json"))?;
+
+    let layered_config = |match_mode| config::Config {
+        roots: vec![config::Root {
+            path: project_dir.display().to_string(),
+            ..Default::default()
+        }],
+        match_mode,
+        rules: vec![
+            config::Rule {
+                name: "node-dist".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["dist".into()],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into()],
+                priority: 10,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let mut cfg = layered_config(config::MatchMode::First);
+    cfg.sort_rules_by_priority();
+    assert_eq!(cfg.rules[0].name, "node");
+
+    // First-match: only the higher priority rule applies
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+    assert_eq!(stats.exclusions_found, 1);
+
+    // All-match: both rules apply
+    let stats =
+        explorer::run_explorer_with_stats(layered_config(config::MatchMode::All), 1, false)?;
+    assert_eq!(stats.exclusions_found, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_report_only_rules() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-report-only",
+        vec![config::Rule {
+            name: "node-trial".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            action: config::RuleAction::Report,
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-report-only");
+    File::create(project_dir.join("package.json"))?;
+    fs::create_dir_all(project_dir.join("node_modules"))?;
+
+    let (mut cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    // A second rule reporting the same path, which is listed once
+    let mut again = cfg.rules[0].clone();
+    again.name = "node-trial-again".to_string();
+    cfg.rules.push(again);
+    cfg.match_mode = config::MatchMode::All;
+    let (stats, state) = explorer::run_explorer_with_state(cfg, 1, false)?;
+
+    // Reported but not excluded, and still traversed
+    assert_eq!(stats.reported, 1);
+    assert_eq!(state.reported_paths.read().unwrap().len(), 1);
+    assert_eq!(stats.exclusions_found, 0);
+    assert_eq!(stats.processed_paths, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_warn_rules_act_as_linter() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let home = temp_dir.path().join("home");
+    fs::create_dir_all(home.join("Downloads"))?;
+    fs::create_dir_all(home.join("project").join("node_modules"))?;
+    File::create(home.join("project").join("package.json"))?;
+
+    let warn_rule = |name: &str| config::Rule {
+        name: name.to_string(),
+        file_match: "Downloads".to_string(),
+        exclusions: vec!["Downloads".into()],
+        action: config::RuleAction::Warn,
+        severity: config::Severity::Warning,
+        ..Default::default()
+    };
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: home.display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![
+            warn_rule("downloads"),
+            warn_rule("downloads-again"),
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into()],
+                ..Default::default()
+            },
+        ],
+        // Both warn rules match Downloads, which is still flagged once
+        match_mode: config::MatchMode::All,
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // The same engine excludes node_modules and flags Downloads without touching it
+    assert_eq!(stats.warnings, 1);
+    assert_eq!(stats.exclusions_found, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_descend_behavior_after_match() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let monorepo = temp_dir.path().join("monorepo");
+    let package = monorepo.join("packages").join("app");
+    fs::create_dir_all(package.join("node_modules"))?;
+    fs::create_dir_all(package.join("src").join("deep"))?;
+    fs::create_dir_all(monorepo.join("node_modules"))?;
+    File::create(monorepo.join("package.json"))?;
+    File::create(package.join("package.json"))?;
+
+    let rules_yaml = r#"
+- name: node
+  file_match: package.json
+  exclusions: [node_modules]
+  descend: depth(2)
+"#;
+    let rules: Vec<config::Rule> = serde_yaml::from_str(rules_yaml)?;
+    assert_eq!(rules[0].descend_behavior(), config::Descend::Depth(2));
+    assert!(
+        serde_yaml::from_str::<Vec<config::Rule>>(&rules_yaml.replace("depth(2)", "deep")).is_err()
+    );
+
+    let run = |descend: config::Descend| -> Result<explorer::ExplorerStats> {
+        let cfg = config::Config {
+            roots: vec![config::Root {
+                path: monorepo.display().to_string(),
+                ..Default::default()
+            }],
+            rules: vec![config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into()],
+                descend: Some(descend),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        Ok(explorer::run_explorer_with_stats(cfg, 1, false)?)
+    };
+
+    // stop: only the monorepo root is processed
+    let stats = run(config::Descend::Stop)?;
+    assert_eq!(stats.processed_paths, 1);
+    assert_eq!(stats.exclusions_found, 1);
+
+    // continue: packages, app (with its own match), src and deep are processed too
+    let stats = run(config::Descend::Continue)?;
+    assert_eq!(stats.processed_paths, 5);
+    assert_eq!(stats.exclusions_found, 2);
+
+    // depth(2): packages and app are processed, but nothing below app
+    let stats = run(config::Descend::Depth(2))?;
+    assert_eq!(stats.processed_paths, 3);
+    assert_eq!(stats.exclusions_found, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_command_exclusion_entries() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let store = temp_dir.path().join("pnpm-store");
+    fs::create_dir_all(&store)?;
+    let project_dir = temp_dir.path().join("workspace");
+    for project in ["app", "lib"] {
+        fs::create_dir_all(project_dir.join(project))?;
+        File::create(project_dir.join(project).join("pnpm-lock.yaml"))?;
+    }
+
+    let rules_yaml = format!(
+        r#"
+- name: pnpm
+  file_match: pnpm-lock.yaml
+  exclusions:
+    - node_modules
+    - command: "echo {}"
+"#,
+        store.display()
+    );
+    let rules: Vec<config::Rule> = serde_yaml::from_str(&rules_yaml)?;
+    assert_eq!(
+        rules[0].exclusions[0],
+        config::Exclusion::from("node_modules")
+    );
+    assert_eq!(
+        explorer::run_exclusion_command(&format!("echo {}", store.display()), None),
+        Some(store.clone())
+    );
+    assert_eq!(explorer::run_exclusion_command("false", None), None);
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: project_dir.display().to_string(),
+            ..Default::default()
+        }],
+        rules,
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 2, false)?;
+
+    // The global store is excluded once, even though both projects match
+    assert_eq!(stats.exclusions_found, 1);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_resolve_symlinked_exclusions() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let cache = temp_dir.path().join("cache").join("bazel").join("output");
+    fs::create_dir_all(&cache)?;
+    let project_dir = temp_dir.path().join("workspace").join("bazel-project");
+    fs::create_dir_all(&project_dir)?;
+    File::create(project_dir.join("MODULE.bazel"))?;
+    std::os::unix::fs::symlink(&cache, project_dir.join("bazel-out"))?;
+    std::os::unix::fs::symlink("/", project_dir.join("bazel-root"))?;
+
+    let bases = vec![temp_dir.path().join("cache").display().to_string()];
+    let canonical_cache = fs::canonicalize(&cache)?;
+    assert_eq!(
+        explorer::resolve_symlink_target(&project_dir.join("bazel-out"), &bases),
+        Some(canonical_cache)
+    );
+    assert_eq!(
+        explorer::resolve_symlink_target(&project_dir.join("bazel-root"), &bases),
+        None
+    );
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: temp_dir.path().join("workspace").display().to_string(),
+            ..Default::default()
+        }],
+        symlink_bases: bases,
+        rules: vec![config::Rule {
+            name: "bazel".to_string(),
+            file_match: "MODULE.bazel".to_string(),
+            exclusions: vec!["bazel-*".into()],
+            resolve_symlinks: true,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Only the allowed target is excluded, and neither link is traversed
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.processed_paths, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_compound_conditions_for_unity_projects() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-unity",
+        vec![config::Rule {
+            name: "unity".to_string(),
+            file_match: "Assets/".to_string(),
+            requires: vec!["ProjectSettings".to_string()],
+            exclusions: vec!["Library".into(), "Temp".into(), "Obj".into(), "Logs".into()],
+            ..Default::default()
+        }],
+    )?;
+
+    let workspace = temp_dir.path().join("test-unity");
+    let game = workspace.join("game");
+    for dir in ["Assets", "ProjectSettings", "Library", "Temp", "Logs"] {
+        fs::create_dir_all(game.join(dir))?;
+    }
+    // Not a Unity project: no ProjectSettings
+    fs::create_dir_all(workspace.join("website").join("Assets"))?;
+    fs::create_dir_all(workspace.join("website").join("Library"))?;
+    // Not a Unity project: Assets is a file
+    fs::create_dir_all(workspace.join("notes").join("ProjectSettings"))?;
+    File::create(workspace.join("notes").join("Assets"))?;
+    fs::create_dir_all(workspace.join("notes").join("Temp"))?;
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    assert!(explorer::requirements_met(&game, &cfg.rules[0]));
+    assert!(!explorer::requirements_met(
+        &workspace.join("website"),
+        &cfg.rules[0]
+    ));
+
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Only the game's Library, Temp and Logs are excluded
+    assert_eq!(stats.exclusions_found, 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_in_project_command_exclusions() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let workspace = temp_dir.path().join("workspace");
+    for project in ["service", "tools"] {
+        fs::create_dir_all(workspace.join(project).join("output-base"))?;
+        File::create(workspace.join(project).join("MODULE.bazel"))?;
+    }
+
+    let rules_yaml = r#"
+- name: bazel
+  file_match: MODULE.bazel
+  resolve_symlinks: true
+  exclusions:
+    - bazel-*
+    - command: echo "$PWD/output-base"
+      in_project: true
+"#;
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: workspace.display().to_string(),
+            ..Default::default()
+        }],
+        rules: serde_yaml::from_str(rules_yaml)?,
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 2, false)?;
+
+    // The command runs from each matched folder, resolving a different output base
+    assert_eq!(stats.exclusions_found, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_virtualenv_detection_by_contents() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-virtualenvs",
+        vec![config::Rule {
+            name: "pyenv".to_string(),
+            file_match: "pyvenv.cfg".to_string(),
+            exclusions: vec![".".into()],
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-virtualenvs");
+    for venv in ["env311", ".venv"] {
+        let site_packages = project_dir.join(venv).join("lib").join("site-packages");
+        fs::create_dir_all(&site_packages)?;
+        File::create(project_dir.join(venv).join("pyvenv.cfg"))?;
+    }
+    fs::create_dir_all(project_dir.join("src"))?;
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Both venvs are excluded whatever their name, and their contents are not traversed
+    assert_eq!(stats.exclusions_found, 2);
+    assert_eq!(stats.processed_paths, 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_exclude_paths_are_excluded_without_rules() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("root");
+    fs::create_dir_all(root.join("VirtualBox VMs").join("ubuntu"))?;
+    fs::create_dir_all(root.join("src"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: root.display().to_string(),
+            ..Default::default()
+        }],
+        exclude_paths: vec![
+            root.join("VirtualBox VMs").display().to_string(),
+            root.join("missing").display().to_string(),
+        ],
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // The fixed path is excluded once and not traversed; the missing one is skipped
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.processed_paths, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_recursive_rule_excludes_every_module() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-multi-module",
+        vec![config::Rule {
+            name: "gradle-multi-module".to_string(),
+            file_match: "settings.gradle".to_string(),
+            exclusions: vec!["build".into()],
+            recursive: true,
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-multi-module");
+    File::create(project_dir.join("settings.gradle"))?;
+    fs::create_dir_all(project_dir.join("build"))?;
+    fs::create_dir_all(project_dir.join("app").join("build").join("tmp"))?;
+    fs::create_dir_all(project_dir.join("libs").join("core").join("build"))?;
+    fs::create_dir_all(project_dir.join("libs").join("core").join("src"))?;
+    fs::create_dir_all(project_dir.join(".git").join("build"))?;
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Each module build is excluded once, while the ignored .git folder is skipped;
+    // the project, app, libs, core and core/src folders are the only ones traversed
+    assert_eq!(stats.exclusions_found, 3);
+    assert_eq!(stats.processed_paths, 5);
+
+    Ok(())
+}
+
+#[test]
+fn test_recursive_rule_stops_at_max_depth() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-deep-modules",
+        vec![config::Rule {
+            name: "gradle-multi-module".to_string(),
+            file_match: "settings.gradle".to_string(),
+            exclusions: vec!["build".into()],
+            recursive: true,
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-deep-modules");
+    File::create(project_dir.join("settings.gradle"))?;
+    fs::create_dir_all(project_dir.join("build"))?;
+    fs::create_dir_all(project_dir.join("app").join("build"))?;
+    fs::create_dir_all(project_dir.join("libs").join("core").join("build"))?;
+
+    let (mut cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    cfg.max_depth = Some(1);
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // The module below the max depth is neither traversed nor excluded
+    assert_eq!(stats.exclusions_found, 2);
+    assert_eq!(stats.processed_paths, 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_large_file_rule_excludes_matched_files_over_threshold() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-disk-images",
+        vec![config::Rule {
+            name: "disk-images".to_string(),
+            file_match: "*.qcow2".to_string(),
+            min_size_mb: Some(1),
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-disk-images");
+    let vms_dir = project_dir.join("vms").join("ubuntu");
+    fs::create_dir_all(&vms_dir)?;
+    fs::write(vms_dir.join("disk.qcow2"), vec![0u8; 2 * 1024 * 1024])?;
+    fs::write(vms_dir.join("seed.qcow2"), vec![0u8; 1024])?;
+    fs::write(project_dir.join("notes.txt"), b"notes")?;
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Only the disk image over the threshold is excluded
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.processed_paths, 3);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_nix_result_links_into_the_store_are_skipped() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-nix",
+        vec![config::Rule {
+            name: "nix-flake".to_string(),
+            file_match: "flake.nix".to_string(),
+            exclusions: vec!["result".into(), "result-*".into(), ".direnv".into()],
+            resolve_symlinks: true,
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-nix");
+    File::create(project_dir.join("flake.nix"))?;
+    fs::write(project_dir.join("results.csv"), b"a,b")?;
+    fs::create_dir_all(project_dir.join(".direnv"))?;
+    std::os::unix::fs::symlink(
+        "/nix/store/00000000000000000000000000000000-hello",
+        project_dir.join("result"),
+    )?;
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Only .direnv is excluded: the store link is skipped and results.csv is not a build link
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.processed_paths, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_unreal_and_godot_projects() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-engines",
+        vec![
+            config::Rule {
+                name: "unreal".to_string(),
+                file_match: "*.uproject".to_string(),
+                exclusions: vec![
+                    "Binaries".into(),
+                    "Intermediate".into(),
+                    "Saved".into(),
+                    "DerivedDataCache".into(),
+                ],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "godot".to_string(),
+                file_match: "project.godot".to_string(),
+                exclusions: vec![".godot".into(), ".import".into()],
+                ..Default::default()
+            },
+        ],
+    )?;
+
+    let workspace = temp_dir.path().join("test-engines");
+    let shooter = workspace.join("shooter");
+    fs::create_dir_all(&shooter)?;
+    File::create(shooter.join("Shooter.uproject"))?;
+    for dir in ["Binaries", "Intermediate", "Saved", "Content"] {
+        fs::create_dir_all(shooter.join(dir))?;
+    }
+    let platformer = workspace.join("platformer");
+    fs::create_dir_all(platformer.join(".godot"))?;
+    fs::create_dir_all(platformer.join("scenes"))?;
+    File::create(platformer.join("project.godot"))?;
+
+    let (cfg, _) = config::load_config(
+        Some(temp_dir.path().join("config.yaml").to_str().unwrap()),
+        false,
+    )?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Binaries, Intermediate, Saved and .godot are excluded
+    assert_eq!(stats.exclusions_found, 4);
+    // workspace, shooter, Content, platformer and scenes are traversed
+    assert_eq!(stats.processed_paths, 5);
+
+    Ok(())
+}
+
+#[test]
+fn test_rules_constrained_to_path_prefixes() -> Result<()> {
+    let temp_dir = create_test_project("test-applies-to", Vec::new())?;
+    let workspace = temp_dir.path().join("test-applies-to");
+    for folder in ["Development/app", "Documents/app", "Development2/app"] {
+        fs::create_dir_all(workspace.join(folder).join("node_modules"))?;
+        File::create(workspace.join(folder).join("package.json"))?;
+    }
+
+    let node_rule = |applies_to: Vec<String>| config::Rule {
+        name: "node".to_string(),
+        file_match: "package.json".to_string(),
+        exclusions: vec!["node_modules".into()],
+        applies_to,
+        ..Default::default()
+    };
+    let development = workspace.join("Development");
+    assert!(explorer::rule_applies_to(
+        &development.join("app"),
+        &node_rule(vec![development.display().to_string()])
+    ));
+    assert!(!explorer::rule_applies_to(
+        &workspace.join("Development2").join("app"),
+        &node_rule(vec![development.display().to_string()])
+    ));
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: workspace.display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![node_rule(vec![format!("{}/**", development.display())])],
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // Only the node_modules under Development is excluded
+    assert_eq!(stats.exclusions_found, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_spotlight_backend_creates_marker() -> Result<()> {
+    let temp_dir = create_test_project(
+        "test-spotlight",
+        vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            backends: vec![config::Backend::Spotlight],
+            ..Default::default()
+        }],
+    )?;
+
+    let project_dir = temp_dir.path().join("test-spotlight");
+    File::create(project_dir.join("package.json"))?;
+    fs::create_dir_all(project_dir.join("node_modules"))?;
+
+    let config_path = temp_dir.path().join("config.yaml");
+    let (cfg, _) = config::load_config(Some(config_path.to_str().unwrap()), false)?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    assert_eq!(stats.newly_excluded, 1);
+    assert!(project_dir
+        .join("node_modules")
+        .join(explorer::SPOTLIGHT_MARKER)
+        .is_file());
+
+    // A second run finds the directory already excluded
+    let (cfg, _) = config::load_config(Some(config_path.to_str().unwrap()), false)?;
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.newly_excluded, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_simulation_ignores_configured_roots() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let fixture = temp_dir.path().join("fixture");
+    fs::create_dir_all(fixture.join("app").join("node_modules"))?;
+    File::create(fixture.join("app").join("package.json"))?;
+
+    // A shared ruleset without roots
+    let config_path = temp_dir.path().join("rules.yaml");
+    fs::write(
+        &config_path,
+        "rules:\n  - name: node\n    file_match: package.json\n    exclusions: [node_modules]\n",
+    )?;
+    assert!(config::load_config(Some(config_path.to_str().unwrap()), false).is_err());
+
+    let (cfg, _) = config::read_config(Some(config_path.to_str().unwrap()), false)?;
+    let stats = explorer::run_simulation(cfg, &[fixture.display().to_string()], 1, false)?;
+
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.newly_excluded, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_run_on_paths_applies_rules_outside_the_roots() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let configured_root = temp_dir.path().join("Development");
+    fs::create_dir_all(configured_root.join("app").join("node_modules"))?;
+    File::create(configured_root.join("app").join("package.json"))?;
+    let clone = temp_dir.path().join("elsewhere").join("repo");
+    fs::create_dir_all(clone.join("node_modules"))?;
+    File::create(clone.join("package.json"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: configured_root.display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let stats = explorer::run_on_paths(cfg, &[clone.display().to_string()], 1, false)?;
+
+    // Only the given path is scanned, and its matches are applied (only reported on
+    // systems without Time Machine)
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(
+        stats.newly_excluded,
+        if cfg!(target_os = "macos") { 1 } else { 0 }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_review_answers() {
+    use explorer::ReviewAnswer;
+    assert_eq!(ReviewAnswer::parse("y\n"), Some(ReviewAnswer::Yes));
+    assert_eq!(ReviewAnswer::parse("Always"), Some(ReviewAnswer::Always));
+    assert_eq!(ReviewAnswer::parse("v"), Some(ReviewAnswer::Never));
+    // An empty answer is the default, no
+    assert_eq!(ReviewAnswer::parse("\n"), Some(ReviewAnswer::No));
+    assert_eq!(ReviewAnswer::parse("maybe"), None);
+}
+
+#[test]
+fn test_parse_skip_paths() -> Result<()> {
+    let home = dirs::home_dir().unwrap();
+    let skip_paths = explorer::parse_skip_paths(
+        r#"["/Users/me/Library/Developer/Xcode/DerivedData/","~/Downloads","/"]"#,
+    );
+
+    assert_eq!(
+        skip_paths,
+        vec![
+            PathBuf::from("/Users/me/Library/Developer/Xcode/DerivedData"),
+            home.join("Downloads"),
+            PathBuf::from("/"),
+        ]
+    );
+    assert!(explorer::parse_skip_paths("not json").is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_tmutil_command_line() {
+    assert_eq!(explorer::tmutil_command_line(None, None), vec!["tmutil"]);
+    assert_eq!(
+        explorer::tmutil_command_line(Some("/opt/shims/tmutil"), Some("sudo -n")),
+        vec!["sudo", "-n", "/opt/shims/tmutil"]
+    );
+    // An empty override falls back to `tmutil` from the PATH
+    assert_eq!(
+        explorer::tmutil_command_line(Some(""), Some("  ")),
+        vec!["tmutil"]
+    );
+}
+
+#[test]
+fn test_expand_path_args() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path();
+    for project in ["web", "api", "docs"] {
+        fs::create_dir_all(root.join(project))?;
+    }
+    fs::create_dir_all(root.join("web/node_modules"))?;
+    fs::create_dir_all(root.join("api/node_modules"))?;
+
+    let pattern = format!("{}/*/node_modules", root.display());
+    let plain = root.join("docs").display().to_string();
+    assert_eq!(
+        explorer::expand_path_args(&[pattern, plain])?,
+        vec![
+            root.join("api/node_modules"),
+            root.join("web/node_modules"),
+            root.join("docs"),
+        ]
+    );
+
+    // Patterns without matches fail like missing paths, before any change
+    let unmatched = format!("{}/*/target", root.display());
+    assert!(explorer::expand_path_args(&[unmatched]).is_err());
+    let missing = root.join("missing");
+    match explorer::expand_path_args(&[missing.display().to_string()]) {
+        Err(asimeow::error::Error::Io { path, source }) => {
+            assert_eq!(path, missing);
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use anyhow::Result;
+use asimeow::config;
+use asimeow::export::{self, ExportFormat};
+use std::fs::{self, File};
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+#[test]
+fn test_collect_exclusions_without_excluding() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("workspace");
+    let app = root.join("app");
+    fs::create_dir_all(app.join("node_modules").join("dep").join("node_modules"))?;
+    File::create(app.join("package.json"))?;
+    File::create(app.join("node_modules").join("dep").join("package.json"))?;
+    fs::create_dir_all(app.join("coverage"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: root.display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into()],
+                descend: Some(config::Descend::Continue),
+                ..Default::default()
+            },
+            config::Rule {
+                name: "coverage".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["coverage".into()],
+                action: config::RuleAction::Report,
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    // Nested matches and report-only findings are left out
+    let exclusions = export::collect_exclusions(cfg, 2, false)?;
+    assert_eq!(exclusions, vec![app.join("node_modules")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_render_restic_escapes_patterns() {
+    let exclusions = vec![
+        PathBuf::from("/Users/me/app/node_modules"),
+        PathBuf::from("/Users/me/[draft] $HOME*/target"),
+    ];
+
+    assert_eq!(
+        export::render(ExportFormat::Restic, &exclusions, &[]),
+        "# restic exclude file generated by asimeow\n\
+         /Users/me/app/node_modules\n\
+         /Users/me/\\[draft\\] $$HOME\\*/target\n"
+    );
+}
+
+#[test]
+fn test_render_borg_patterns() -> Result<()> {
+    let exclusions = vec![
+        PathBuf::from("/Users/me/app/node_modules"),
+        PathBuf::from("/Users/me/svc/target"),
+    ];
+
+    assert_eq!(
+        export::render(ExportFormat::Borg, &exclusions, &[]),
+        "# borg patterns file generated by asimeow\n\
+         - pp:/Users/me/app/node_modules\n\
+         - pp:/Users/me/svc/target\n"
+    );
+
+    let snippet: serde_yaml::Value =
+        serde_yaml::from_str(&export::render(ExportFormat::Borgmatic, &exclusions, &[]))?;
+    assert_eq!(
+        snippet["exclude_patterns"],
+        serde_yaml::to_value(vec![
+            "pp:/Users/me/app/node_modules",
+            "pp:/Users/me/svc/target"
+        ])?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_render_rsync_patterns_relative_to_roots() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let projects = temp_dir.path().join("projects");
+    let work = temp_dir.path().join("work");
+    fs::create_dir_all(projects.join("app").join("node_modules"))?;
+    fs::create_dir_all(projects.join("[old]").join("target"))?;
+    fs::create_dir_all(work.join("svc").join("target"))?;
+    File::create(work.join("svc").join("disk.img"))?;
+
+    let exclusions = vec![
+        projects.join("[old]").join("target"),
+        projects.join("app").join("node_modules"),
+        work.join("svc").join("disk.img"),
+        work.join("svc").join("target"),
+        temp_dir.path().join("elsewhere"),
+    ];
+    let content = export::render(
+        ExportFormat::Rsync,
+        &exclusions,
+        &[projects.clone(), work.clone()],
+    );
+
+    assert_eq!(
+        content,
+        format!(
+            "# rsync exclude patterns generated by asimeow\n\
+             \n# root: {}\n\
+             /\\[old]/target/\n\
+             /app/node_modules/\n\
+             \n# root: {}\n\
+             /svc/disk.img\n\
+             /svc/target/\n",
+            projects.display(),
+            work.display()
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_render_ccc_filter() {
+    let exclusions = vec![PathBuf::from("/Users/me/R&D/node_modules")];
+    let content = export::render(ExportFormat::Ccc, &exclusions, &[]);
+
+    assert!(content.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist"));
+    assert!(content.contains("\t\t\t<string>exclude</string>\n"));
+    assert!(content.contains("\t\t\t<string>/Users/me/R&amp;D/node_modules</string>\n"));
+    assert_eq!(content.matches("<dict>").count(), 2);
+    assert!(content.ends_with("\t</array>\n</dict>\n</plist>\n"));
+}
+
+#[test]
+fn test_render_arq_groups_by_folder() {
+    let exclusions = vec![
+        PathBuf::from("/Users/me/Library/Caches"),
+        PathBuf::from("/Users/me/projects/app/node_modules"),
+        PathBuf::from("/Users/me/work/svc/target"),
+    ];
+    let roots = vec![
+        PathBuf::from("/Users/me/projects"),
+        PathBuf::from("/Users/me/work"),
+    ];
+
+    assert_eq!(
+        export::render(ExportFormat::Arq, &exclusions, &roots),
+        "# Arq exclusions generated by asimeow\n\
+         \n# folder: /Users/me/projects\n\
+         /Users/me/projects/app/node_modules\n\
+         \n# folder: /Users/me/work\n\
+         /Users/me/work/svc/target\n\
+         \n# outside the roots\n\
+         /Users/me/Library/Caches\n"
+    );
+}
+
+#[test]
+fn test_render_backblaze_folder_filters() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let node_modules = temp_dir.path().join("app").join("node_modules");
+    fs::create_dir_all(&node_modules)?;
+    let disk_image = temp_dir.path().join("disk.img");
+    File::create(&disk_image)?;
+
+    let content = export::render(
+        ExportFormat::Backblaze,
+        &[node_modules.clone(), disk_image.clone()],
+        &[],
+    );
+
+    assert!(content.contains(&format!(
+        "<bzdirfilter dir=\"{}/\" whattodo=\"exclude\" filetype=\"*\" />\n",
+        node_modules.display()
+    )));
+    assert!(content.contains(&format!(
+        "<!-- not excluded, Backblaze only excludes folders: {} -->\n",
+        disk_image.display()
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn test_render_kopia_policy_commands() {
+    let exclusions = vec![
+        PathBuf::from("/Users/me/Library/Caches/pip"),
+        PathBuf::from("/Users/me/projects/app/node_modules"),
+        PathBuf::from("/Users/me/projects/it's/target"),
+    ];
+    let roots = vec![PathBuf::from("/Users/me/projects")];
+
+    assert_eq!(
+        export::render(ExportFormat::Kopia, &exclusions, &roots),
+        "# Kopia ignore rules generated by asimeow\n\
+         kopia policy set '/Users/me/Library/Caches' \\\n    --add-ignore '/pip'\n\
+         kopia policy set '/Users/me/projects' \\\n    --add-ignore '/app/node_modules' \\\n    \
+         --add-ignore '/it'\\''s/target'\n"
+    );
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use asimeow::backend::MockBackend;
+use asimeow::config;
+use asimeow::find;
+use std::fs::{self, File};
+use tempfile::tempdir;
+
+#[test]
+fn test_find_rule_exclusions() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("workspace");
+    for project in ["web", "api"] {
+        fs::create_dir_all(root.join(project).join("node_modules"))?;
+        File::create(root.join(project).join("package.json"))?;
+    }
+    fs::create_dir_all(root.join("crate/target"))?;
+    File::create(root.join("crate/Cargo.toml"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: root.display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into()],
+                // Exclusions made before the rule was disabled are still attributed to it
+                enabled: Some(false),
+                ..Default::default()
+            },
+            config::Rule {
+                name: "rust".to_string(),
+                file_match: "Cargo.toml".to_string(),
+                exclusions: vec!["target".into()],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    // Only the paths still excluded are listed
+    let backend =
+        MockBackend::with_excluded([root.join("web/node_modules"), root.join("crate/target")]);
+    let node = find::find_rule_exclusions(&cfg, "node", &backend, 2, false)?;
+    assert_eq!(node, vec![root.join("web/node_modules")]);
+    let rust = find::find_rule_exclusions(&cfg, "rust", &backend, 2, false)?;
+    assert_eq!(rust, vec![root.join("crate/target")]);
+
+    assert!(find::find_rule_exclusions(&cfg, "python", &backend, 2, false).is_err());
+    Ok(())
+}
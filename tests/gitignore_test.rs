@@ -0,0 +1,78 @@
+use anyhow::Result;
+use asimeow::{config, explorer, gitignore};
+use std::fs::{self, File};
+use tempfile::tempdir;
+
+#[test]
+fn test_parse_gitignore() {
+    let content =
+        "# build output\n/build/\ndist\n!dist/keep\nsrc/generated\n**/*.log\n\n*.egg-info/\n";
+    assert_eq!(
+        gitignore::parse_gitignore(content),
+        vec!["build", "dist", "*.egg-info"]
+    );
+}
+
+#[test]
+fn test_gitignore_candidates_are_excluded() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let repo = temp_dir.path().join("repo");
+    fs::create_dir_all(repo.join(".git"))?;
+    fs::create_dir_all(repo.join("build"))?;
+    fs::create_dir_all(repo.join("src"))?;
+    fs::write(repo.join("build").join("output.bin"), vec![0u8; 4096])?;
+    fs::write(repo.join(".gitignore"), "/build/\n.env\n")?;
+    File::create(repo.join(".env"))?;
+
+    assert_eq!(
+        gitignore::ignored_directories(&repo),
+        vec![repo.join("build")]
+    );
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: repo.display().to_string(),
+            ..Default::default()
+        }],
+        ignore: vec![".git".to_string()],
+        gitignore: Some(config::GitignoreConfig {
+            mode: config::GitignoreMode::Exclude,
+            min_size_mb: 0,
+        }),
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // build is excluded and not traversed: only the repo root and src are processed
+    assert_eq!(stats.exclusions_found, 1);
+    assert_eq!(stats.processed_paths, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_gitignore_candidates_below_threshold_are_skipped() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let repo = temp_dir.path().join("repo");
+    fs::create_dir_all(repo.join(".git"))?;
+    fs::create_dir_all(repo.join("build"))?;
+    fs::write(repo.join(".gitignore"), "build\n")?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: repo.display().to_string(),
+            ..Default::default()
+        }],
+        ignore: vec![".git".to_string()],
+        gitignore: Some(config::GitignoreConfig {
+            mode: config::GitignoreMode::Exclude,
+            min_size_mb: 1,
+        }),
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    assert_eq!(stats.exclusions_found, 0);
+
+    Ok(())
+}
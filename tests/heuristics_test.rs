@@ -0,0 +1,139 @@
+use anyhow::Result;
+use asimeow::{config, explorer, heuristics};
+use std::fs;
+use tempfile::tempdir;
+
+fn small_files_heuristics() -> config::HeuristicsConfig {
+    config::HeuristicsConfig {
+        min_size_mb: 0,
+        min_files: 10,
+        max_average_file_kb: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_directory_stats() -> Result<()> {
+    let temp_dir = tempdir()?;
+    fs::create_dir_all(temp_dir.path().join("nested"))?;
+    fs::write(temp_dir.path().join("a.bin"), vec![0u8; 100])?;
+    fs::write(temp_dir.path().join("nested").join("b.bin"), vec![0u8; 300])?;
+
+    let stats = heuristics::directory_stats(temp_dir.path());
+    assert_eq!(stats.size, 400);
+    assert_eq!(stats.files, 2);
+    assert_eq!(stats.average_file_size(), 200);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_like_directories_are_reported() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let project = temp_dir.path().join("project");
+    let cache = project.join(".zig-cache");
+    fs::create_dir_all(cache.join("build"))?;
+    fs::create_dir_all(project.join("src"))?;
+    for i in 0..20 {
+        fs::write(cache.join("build").join(format!("{}.o", i)), b"obj")?;
+        fs::write(project.join("src").join(format!("{}.zig", i)), b"src")?;
+    }
+
+    let heuristics_config = small_files_heuristics();
+    assert!(heuristics::evaluate(&cache, &heuristics_config).is_some());
+    // Not a cache-like name, however small its files are
+    assert!(heuristics::evaluate(&project.join("src"), &heuristics_config).is_none());
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: project.display().to_string(),
+            ..Default::default()
+        }],
+        heuristics: Some(heuristics_config),
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    // The nested build folder is not reported again, and nothing is excluded
+    assert_eq!(stats.candidates, 1);
+    assert_eq!(stats.exclusions_found, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_directories_with_too_many_entries_are_reported() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let project = temp_dir.path().join("project");
+    let graveyard = project.join("graveyard");
+    for i in 0..5 {
+        fs::create_dir_all(graveyard.join(format!("module-{}", i)))?;
+    }
+    fs::create_dir_all(project.join("src"))?;
+
+    let run = |stop: bool| {
+        let cfg = config::Config {
+            roots: vec![config::Root {
+                path: project.display().to_string(),
+                ..Default::default()
+            }],
+            entry_limit: Some(config::EntryLimitConfig {
+                max_entries: 3,
+                stop,
+            }),
+            ..Default::default()
+        };
+        explorer::run_explorer_with_stats(cfg, 1, false)
+    };
+
+    let stats = run(false)?;
+    assert_eq!(stats.crowded, 1);
+    // project, graveyard, its 5 modules and src
+    assert_eq!(stats.processed_paths, 8);
+
+    let stats = run(true)?;
+    assert_eq!(stats.crowded, 1);
+    // The modules of the graveyard are not traversed
+    assert_eq!(stats.processed_paths, 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_orphaned_build_outputs_are_reported() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let workspace = temp_dir.path().join("workspace");
+    // The Cargo.toml of the old project was deleted
+    fs::create_dir_all(workspace.join("old-crate").join("target").join("debug"))?;
+    // A live project keeps its marker, so its target is handled by the rule
+    fs::create_dir_all(workspace.join("live-crate").join("target"))?;
+    fs::write(workspace.join("live-crate").join("Cargo.toml"), b"")?;
+    fs::create_dir_all(workspace.join("old-crate").join("src"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: workspace.display().to_string(),
+            ..Default::default()
+        }],
+        orphans: Some(config::OrphansConfig {
+            min_size_mb: 0,
+            ..Default::default()
+        }),
+        rules: vec![config::Rule {
+            name: "rust".to_string(),
+            file_match: "Cargo.toml".to_string(),
+            exclusions: vec!["target".into()],
+            ..Default::default()
+        }],
+        dry_run: true,
+        ..Default::default()
+    };
+    let stats = explorer::run_explorer_with_stats(cfg, 1, false)?;
+
+    assert_eq!(stats.orphans, 1);
+    assert_eq!(stats.exclusions_found, 1);
+    // workspace, old-crate, its src and live-crate: the orphaned target is not traversed
+    assert_eq!(stats.processed_paths, 4);
+
+    Ok(())
+}
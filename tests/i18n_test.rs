@@ -0,0 +1,80 @@
+use asimeow::i18n::{self, Lang};
+use std::collections::BTreeSet;
+
+/// Returns the message ids of a Fluent catalog
+fn message_ids(catalog: &str) -> BTreeSet<String> {
+    catalog
+        .lines()
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(id, _)| id.to_string())
+        .filter(|id| !id.starts_with([' ', '#']))
+        .collect()
+}
+
+#[test]
+fn test_lang_from_locale() {
+    assert_eq!(Lang::from_locale("it_IT.UTF-8"), Some(Lang::It));
+    assert_eq!(Lang::from_locale("en-GB"), Some(Lang::En));
+    assert_eq!(Lang::from_locale("IT"), Some(Lang::It));
+    assert_eq!(Lang::from_locale("C"), None);
+    assert_eq!(Lang::from_locale(""), None);
+}
+
+#[test]
+fn test_translate() {
+    let count = |count: usize| [("count", count.into())];
+    assert_eq!(
+        i18n::translate(Lang::En, "exit-partial-failure", &count(1)),
+        "The scan completed with 1 error"
+    );
+    assert_eq!(
+        i18n::translate(Lang::En, "exit-partial-failure", &count(3)),
+        "The scan completed with 3 errors"
+    );
+    assert_eq!(
+        i18n::translate(Lang::It, "exit-partial-failure", &count(3)),
+        "La scansione è terminata con 3 errori"
+    );
+    assert_eq!(
+        i18n::translate(Lang::It, "config-using", &[("path", "/tmp/c.yaml".into())]),
+        "Configurazione in uso: /tmp/c.yaml"
+    );
+    // Unknown messages show their id
+    assert_eq!(
+        i18n::translate(Lang::It, "no-such-message", &[]),
+        "no-such-message"
+    );
+}
+
+#[test]
+fn test_catalogs_are_complete() {
+    let en = message_ids(include_str!("../src/locales/en.ftl"));
+    let it = message_ids(include_str!("../src/locales/it.ftl"));
+    assert!(!en.is_empty());
+    assert_eq!(en, it);
+}
+
+#[test]
+fn test_command_output_is_translated() {
+    let path = || [("path", "/tmp/a".into())];
+    assert_eq!(
+        i18n::translate(Lang::En, "exclude-done", &path()),
+        "✅ Successfully excluded: /tmp/a"
+    );
+    assert_eq!(
+        i18n::translate(Lang::It, "exclude-done", &path()),
+        "✅ Escluso: /tmp/a"
+    );
+    assert_eq!(
+        i18n::translate(Lang::It, "list-legend-fixed-path", &[]),
+        "(percorso fisso) - Esclusione a percorso fisso delle preferenze di Time Machine"
+    );
+    assert_eq!(
+        i18n::translate(
+            Lang::It,
+            "explain-outcome-skipped",
+            &[("reason", "marcatore .asimeow-keep".into())]
+        ),
+        "saltato (marcatore .asimeow-keep)"
+    );
+}
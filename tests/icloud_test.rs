@@ -0,0 +1,95 @@
+use anyhow::Result;
+use asimeow::config;
+use asimeow::icloud;
+use std::fs::{self, File};
+use tempfile::tempdir;
+
+#[test]
+fn test_icloud_drive_folders() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let home = temp_dir.path();
+    assert!(icloud::icloud_drive_folders(home).is_empty());
+
+    let cloud_docs = home.join("Library/Mobile Documents/com~apple~CloudDocs");
+    fs::create_dir_all(cloud_docs.join("Documents"))?;
+    assert_eq!(
+        icloud::icloud_drive_folders(home),
+        vec![cloud_docs.clone(), home.join("Documents")]
+    );
+    assert!(icloud::is_under_icloud_drive(
+        &home.join("Documents/app/node_modules"),
+        home
+    ));
+    assert!(!icloud::is_under_icloud_drive(
+        &home.join("Desktop/app/node_modules"),
+        home
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_move_to_nosync() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let app = temp_dir.path().join("app");
+    let node_modules = app.join("node_modules");
+    let nosync = app.join("node_modules.nosync");
+    fs::create_dir_all(node_modules.join("react"))?;
+
+    assert!(icloud::move_to_nosync(&node_modules)?);
+    assert!(node_modules.is_symlink());
+    assert!(nosync.join("react").is_dir());
+    assert!(node_modules.join("react").is_dir());
+    assert!(icloud::is_nosync_target(&nosync));
+
+    // Moving again is a no-op
+    assert!(!icloud::move_to_nosync(&node_modules)?);
+
+    // Files and existing .nosync counterparts are left untouched
+    File::create(app.join("package.json"))?;
+    assert!(icloud::move_to_nosync(&app.join("package.json")).is_err());
+    fs::create_dir_all(app.join("dist"))?;
+    fs::create_dir_all(app.join("dist.nosync"))?;
+    assert!(icloud::move_to_nosync(&app.join("dist")).is_err());
+    assert!(!app.join("dist").is_symlink());
+    assert!(!icloud::is_nosync_target(&app.join("dist.nosync")));
+
+    Ok(())
+}
+
+#[test]
+fn test_icloud_backend_is_applied_last() -> Result<()> {
+    let rule: config::Rule = serde_yaml::from_str(
+        r#"
+name: node
+file_match: package.json
+exclusions: [node_modules]
+backends: [icloud, time_machine]
+"#,
+    )?;
+
+    assert_eq!(
+        rule.backends(),
+        vec![config::Backend::TimeMachine, config::Backend::ICloud]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_local_items_are_not_dataless() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let folder = temp_dir.path().join("folder");
+    fs::create_dir_all(&folder)?;
+    fs::write(folder.join("file"), "content")?;
+
+    // Local items are listed and measured as usual
+    assert!(!icloud::is_dataless_path(&folder));
+    assert!(!icloud::is_dataless(&fs::symlink_metadata(
+        folder.join("file")
+    )?));
+    assert!(!icloud::is_dataless_path(&temp_dir.path().join("missing")));
+    assert_eq!(asimeow::size::directory_size(&folder), 7);
+
+    Ok(())
+}
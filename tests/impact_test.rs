@@ -0,0 +1,47 @@
+use anyhow::Result;
+use asimeow::impact;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+#[test]
+fn test_parse_uniquesize() {
+    assert_eq!(
+        impact::parse_uniquesize("  1.5G /Volumes/Backup/foo\n"),
+        Some(1610612736)
+    );
+    assert_eq!(
+        impact::parse_uniquesize("512K /Volumes/Backup/foo"),
+        Some(524288)
+    );
+    assert_eq!(
+        impact::parse_uniquesize("2MB /Volumes/Backup/foo"),
+        Some(2097152)
+    );
+    assert_eq!(impact::parse_uniquesize("0B /Volumes/Backup/foo"), Some(0));
+    assert_eq!(impact::parse_uniquesize("42 /Volumes/Backup/foo"), Some(42));
+    assert_eq!(impact::parse_uniquesize(""), None);
+    assert_eq!(
+        impact::parse_uniquesize("unknown /Volumes/Backup/foo"),
+        None
+    );
+}
+
+#[test]
+fn test_backup_path() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let backup = temp_dir.path().join("2026-10-01-120000.backup");
+    let in_backup = backup.join("Macintosh HD - Data/Users/alice/Development/app/target");
+    fs::create_dir_all(&in_backup)?;
+    fs::create_dir_all(backup.join("Macintosh HD"))?;
+
+    let path = Path::new("/Users/alice/Development/app/target");
+    assert_eq!(impact::backup_path(&backup, path), Some(in_backup.clone()));
+    // The firmlinked data volume location maps to the same backup path
+    let firmlinked = Path::new("/System/Volumes/Data/Users/alice/Development/app/target");
+    assert_eq!(impact::backup_path(&backup, firmlinked), Some(in_backup));
+    // Items missing from the backup (e.g. already excluded then) have no backup path
+    let missing = Path::new("/Users/alice/Development/app/node_modules");
+    assert_eq!(impact::backup_path(&backup, missing), None);
+    Ok(())
+}
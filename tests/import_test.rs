@@ -0,0 +1,97 @@
+use anyhow::Result;
+use asimeow::import;
+
+const ASIMOV_SCRIPT: &str = r#"#!/usr/bin/env bash
+set -Eeu -o pipefail
+
+# Paths to unconditionally skip over.
+readonly ASIMOV_SKIP_PATHS=(
+    ~/.Trash
+    ~/Library
+)
+
+# A list of "directory filename" pairs.
+readonly ASIMOV_VENDOR_DIR_SENTINELS=(
+    '.build Package.swift'          # Swift
+    'node_modules package.json'     # npm, Yarn (NodeJS)
+    'bower_components bower.json'   # Bower (JavaScript)
+    '.venv requirements.txt'        # virtualenv (Python)
+    'vendor composer.json'          # Composer (PHP)
+    'venv requirements.txt'         # virtualenv (Python)
+    'broken'
+)
+
+for i in "${ASIMOV_VENDOR_DIR_SENTINELS[@]}"; do
+    echo "$i"
+done
+"#;
+
+#[test]
+fn test_parse_asimov_script() -> Result<()> {
+    let import = import::parse_asimov_script(ASIMOV_SCRIPT)?;
+    let config = &import.config;
+
+    assert_eq!(config.roots[0].path, "~/");
+    assert_eq!(config.ignore, vec![".Trash", "Library"]);
+
+    let names: Vec<&str> = config.rules.iter().map(|rule| rule.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "asimov-package-swift",
+            "asimov-package-json",
+            "asimov-bower-json",
+            "asimov-requirements-txt",
+            "asimov-composer-json",
+        ]
+    );
+    let python = &config.rules[3];
+    assert_eq!(python.file_match, "requirements.txt");
+    assert_eq!(
+        python
+            .exclusions
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![".venv", "venv"]
+    );
+
+    // Approximated skip paths and the malformed entry are flagged
+    assert_eq!(import.warnings.len(), 3);
+    assert!(import.warnings[0].contains("'broken'"));
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_script_without_definitions() {
+    assert!(import::parse_asimov_script("#!/bin/sh\necho hello\n").is_err());
+}
+
+#[test]
+fn test_parse_tmignore_config() -> Result<()> {
+    let import = import::parse_tmignore_config(
+        r#"{
+  "searchPaths": ["~/Developer", "/Volumes/Work"],
+  "ignoredPaths": ["~/Developer/archive"],
+  "whitelist": ["*/application.yml"]
+}"#,
+    )?;
+    let config = &import.config;
+
+    let roots: Vec<&str> = config.roots.iter().map(|root| root.path.as_str()).collect();
+    assert_eq!(roots, vec!["~/Developer", "/Volumes/Work"]);
+    assert_eq!(config.ignore, vec!["archive"]);
+    assert_eq!(config.keep, vec!["*/application.yml"]);
+    let gitignore = config.gitignore.as_ref().unwrap();
+    assert_eq!(gitignore.mode, asimeow::config::GitignoreMode::Exclude);
+    assert_eq!(gitignore.min_size_mb, 0);
+    assert!(config.rules.is_empty());
+
+    // Missing keys fall back to the tmignore defaults
+    let defaults = import::parse_tmignore_config("{}")?;
+    assert_eq!(defaults.config.roots[0].path, "~");
+    assert!(defaults.config.ignore.contains(&"Library".to_string()));
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use asimeow::{explorer, inventory};
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+#[test]
+fn test_inventory_lists_fixed_paths_under_root() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("volume");
+    fs::create_dir_all(root.join("DerivedData"))?;
+
+    let skip_paths = vec![
+        root.join("DerivedData"),
+        root.join("deleted-project"),
+        PathBuf::from("/elsewhere/cache"),
+    ];
+    let entries = inventory::inventory(&root, &skip_paths);
+
+    let paths: Vec<&PathBuf> = entries.iter().map(|entry| &entry.path).collect();
+    assert_eq!(
+        paths,
+        vec![&root.join("DerivedData"), &root.join("deleted-project")]
+    );
+    assert!(entries
+        .iter()
+        .all(|entry| entry.mechanism == explorer::ExclusionMechanism::FixedPath));
+
+    Ok(())
+}
+
+#[test]
+fn test_stale_fixed_paths() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let existing = temp_dir.path().join("DerivedData");
+    fs::create_dir_all(&existing)?;
+    let deleted = temp_dir.path().join("deleted-project").join("build");
+
+    let stale = inventory::stale_fixed_paths(&[existing, deleted.clone()]);
+    assert_eq!(stale, vec![deleted]);
+
+    Ok(())
+}
@@ -1,4 +1,33 @@
 // Test modules
+mod backend_test;
+mod cloud_test;
 mod config_test;
+mod dropbox_test;
+mod estimate_test;
+mod events_test;
 mod exclusion_test;
+mod exit_test;
+mod explain_test;
 mod explorer_test;
+mod export_test;
+mod find_test;
+mod gitignore_test;
+mod heuristics_test;
+mod i18n_test;
+mod icloud_test;
+mod impact_test;
+mod import_test;
+mod inventory_test;
+mod onboarding_test;
+mod output_test;
+mod pagination_test;
+mod presets_test;
+mod progress_test;
+mod queue_test;
+mod remote_test;
+mod rules_test;
+mod size_test;
+mod snapshots_test;
+mod suggest_test;
+mod tui_test;
+mod users_test;
@@ -0,0 +1,82 @@
+use anyhow::Result;
+use asimeow::backend::MockBackend;
+use asimeow::config;
+use asimeow::onboarding;
+use asimeow::size::SizeMode;
+use std::fs::{self, File};
+use tempfile::tempdir;
+
+#[test]
+fn test_needs_onboarding() {
+    // An explicit config file skips the onboarding, even when it is missing
+    assert!(!onboarding::needs_onboarding(Some("missing.yaml")));
+}
+
+#[test]
+fn test_onboard() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("workspace");
+    fs::create_dir_all(root.join("web/node_modules"))?;
+    File::create(root.join("web/package.json"))?;
+    fs::write(root.join("web/node_modules/index.js"), vec![0u8; 1_000])?;
+    let config_file = temp_dir.path().join("conf/config.yaml");
+    let config_file = config_file.to_str().unwrap();
+
+    let mut cfg = config::default_config();
+    cfg.roots = vec![config::Root {
+        path: root.display().to_string(),
+        ..Default::default()
+    }];
+
+    // Declining the configuration stops before scanning
+    let backend = MockBackend::new();
+    let mut questions = Vec::new();
+    let excluded = onboarding::onboard(
+        cfg.clone(),
+        config_file,
+        &backend,
+        2,
+        SizeMode::Apparent,
+        false,
+        &mut |question| {
+            questions.push(question.to_string());
+            Ok(false)
+        },
+    )?;
+    assert_eq!(excluded, 0);
+    assert_eq!(questions.len(), 1);
+    assert!(!fs::exists(config_file)?);
+
+    // Declining the exclusions writes the config but changes nothing
+    let mut answers = vec![true, false].into_iter();
+    let excluded = onboarding::onboard(
+        cfg.clone(),
+        config_file,
+        &backend,
+        2,
+        SizeMode::Apparent,
+        false,
+        &mut |_| Ok(answers.next().unwrap()),
+    )?;
+    assert_eq!(excluded, 0);
+    assert!(backend.excluded_paths().is_empty());
+    let (written, _) = config::load_config(Some(config_file), false)?;
+    assert_eq!(written.roots[0].path, root.display().to_string());
+
+    // Accepting both excludes the detected paths
+    let excluded = onboarding::onboard(
+        cfg,
+        config_file,
+        &backend,
+        2,
+        SizeMode::Apparent,
+        false,
+        &mut |_| Ok(true),
+    )?;
+    assert_eq!(excluded, 1);
+    assert_eq!(
+        backend.excluded_paths(),
+        vec![root.join("web/node_modules")]
+    );
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use anyhow::Result;
+use asimeow::backend::MockBackend;
+use asimeow::config;
+use asimeow::events::{Event, ExclusionOutcome};
+use asimeow::explorer::{self, Explorer, ExplorerStats, State};
+use asimeow::output::{self, ErrorKind, OutputFormat, Reporter, ScanError};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tempfile::tempdir;
+
+#[test]
+fn test_scan_error_from_io() {
+    let error = ScanError::from_io(Path::new("/private"), &io::Error::from_raw_os_error(13));
+    assert_eq!(error.kind, ErrorKind::PermissionDenied);
+    assert_eq!(error.errno, Some(13));
+
+    let error = ScanError::from_io(
+        Path::new("/gone"),
+        &io::Error::new(io::ErrorKind::NotFound, "gone"),
+    );
+    assert_eq!(error.kind, ErrorKind::NotFound);
+    assert_eq!(error.errno, None);
+}
+
+#[test]
+fn test_json_reports() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().join("workspace");
+    fs::create_dir_all(root.join("app/node_modules"))?;
+    File::create(root.join("app/package.json"))?;
+    let missing = temp_dir.path().join("missing");
+
+    let cfg = config::Config {
+        roots: vec![
+            config::Root {
+                path: root.display().to_string(),
+                ..Default::default()
+            },
+            config::Root {
+                path: missing.display().to_string(),
+                ..Default::default()
+            },
+        ],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        format: OutputFormat::Json,
+        ..Default::default()
+    };
+    let (stats, state) =
+        explorer::run_explorer_with_backend(cfg, Arc::new(MockBackend::new()), 2, false)?;
+
+    // The missing root is collected as a structured error
+    assert_eq!(
+        *state.errors.read().unwrap(),
+        vec![ScanError::new(
+            ErrorKind::NotFound,
+            &missing,
+            "Path does not exist"
+        )]
+    );
+
+    let report = output::render_report(&stats, &state, OutputFormat::Json).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&report)?;
+    assert_eq!(report["newly_excluded"], 1);
+    assert_eq!(report["error_count"], 1);
+    assert_eq!(report["errors_by_kind"]["not-found"], 1);
+    assert_eq!(report["rules"]["node"]["matches"], 1);
+    assert_eq!(report["rules"]["node"]["exclusions_found"], 1);
+    assert_eq!(
+        report["roots"][root.display().to_string()]["exclusions_found"],
+        1
+    );
+    assert!(report["duration_secs"].is_f64());
+    assert!(report.get("bytes").is_none());
+    assert_eq!(
+        report["exclusions"][0],
+        root.join("app/node_modules").display().to_string()
+    );
+    assert_eq!(report["errors"][0]["kind"], "not-found");
+    assert_eq!(report["errors"][0]["path"], missing.display().to_string());
+    assert!(report["errors"][0]["errno"].is_null());
+
+    // One record per line: the exclusions, the errors, then the summary
+    let report = output::render_report(&stats, &state, OutputFormat::Ndjson).unwrap();
+    let types: Vec<String> = report
+        .lines()
+        .map(|line| {
+            let record: serde_json::Value = serde_json::from_str(line).unwrap();
+            record["type"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(types, vec!["exclusion", "error", "summary"]);
+
+    assert!(output::render_report(&stats, &state, OutputFormat::Text).is_none());
+    Ok(())
+}
+
+#[test]
+fn test_fatal_error_is_rendered_as_json() {
+    let error = anyhow::anyhow!("Failed to parse config file: config.yaml")
+        .context("Could not load the configuration");
+    let line = output::render_fatal_error(&error, 2);
+
+    let record: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(record["type"], "fatal");
+    assert_eq!(
+        record["message"],
+        "Could not load the configuration: Failed to parse config file: config.yaml"
+    );
+    assert_eq!(record["exit_code"], 2);
+}
+
+#[test]
+fn test_render_error_summary() {
+    let home = Path::new("/Users/me");
+    let errors = vec![
+        ScanError::new(
+            ErrorKind::PermissionDenied,
+            &home.join("Library/Caches/a"),
+            "Permission denied",
+        ),
+        ScanError::new(
+            ErrorKind::PermissionDenied,
+            &home.join("Library/Mail"),
+            "Permission denied",
+        ),
+        ScanError::new(
+            ErrorKind::PermissionDenied,
+            &home.join("Private"),
+            "Permission denied",
+        ),
+        ScanError::new(
+            ErrorKind::NotFound,
+            Path::new("/Volumes/Gone"),
+            "Path does not exist",
+        ),
+    ];
+
+    // Grouped by kind, most frequent first, with the directories below the root
+    assert_eq!(
+        output::render_error_summary(&errors, &[home.to_path_buf()]),
+        vec![
+            "⚠️  4 errors while scanning:",
+            "  Permission denied: 3 in /Users/me/Library, /Users/me/Private",
+            "  Not found: 1 in /Volumes/Gone",
+        ]
+    );
+    assert!(output::render_error_summary(&[], &[home.to_path_buf()]).is_empty());
+}
+
+/// Records what it is given, as a custom output format would render it
+#[derive(Default)]
+struct RecordingReporter {
+    excluded: Mutex<Vec<(PathBuf, ExclusionOutcome)>>,
+    finished: Mutex<Option<(i32, Vec<PathBuf>)>>,
+}
+
+impl Reporter for RecordingReporter {
+    fn event(&self, event: &Event) {
+        if let Event::Excluded { path, outcome, .. } = event {
+            self.excluded.lock().unwrap().push((path.clone(), *outcome));
+        }
+    }
+
+    fn finish(&self, stats: &ExplorerStats, _state: &State, roots: &[PathBuf]) {
+        *self.finished.lock().unwrap() = Some((stats.exclusions_found, roots.to_vec()));
+    }
+}
+
+#[test]
+fn test_custom_reporter() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let app = temp_dir.path().join("app");
+    fs::create_dir_all(app.join("node_modules"))?;
+    fs::create_dir_all(app.join("dist"))?;
+    File::create(app.join("package.json"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: temp_dir.path().display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into(), "dist".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let reporter = Arc::new(RecordingReporter::default());
+    Explorer::builder()
+        .config(cfg)
+        .backend(Arc::new(MockBackend::with_excluded([app.join("dist")])))
+        .reporter(reporter.clone())
+        .run()?;
+
+    let mut excluded = reporter.excluded.lock().unwrap().clone();
+    excluded.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        excluded,
+        vec![
+            (app.join("dist"), ExclusionOutcome::AlreadyExcluded),
+            (app.join("node_modules"), ExclusionOutcome::Excluded),
+        ]
+    );
+    assert_eq!(
+        *reporter.finished.lock().unwrap(),
+        Some((2, vec![temp_dir.path().to_path_buf()]))
+    );
+
+    Ok(())
+}
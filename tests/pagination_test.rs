@@ -0,0 +1,45 @@
+use asimeow::pagination::Pagination;
+
+#[test]
+fn test_pagination() {
+    let items: Vec<u32> = (1..=7).collect();
+
+    let all = Pagination::default();
+    assert_eq!(all.apply(&items), &items[..]);
+    assert_eq!(all.footer(items.len()), None);
+
+    let first = Pagination {
+        max_results: Some(3),
+        page: 1,
+    };
+    assert_eq!(first.apply(&items), &[1, 2, 3]);
+    assert_eq!(
+        first.footer(items.len()).unwrap(),
+        "Showing 1-3 of 7 entries (page 1 of 3, --page 2 for the next ones)"
+    );
+
+    let last = Pagination {
+        max_results: Some(3),
+        page: 3,
+    };
+    assert_eq!(last.apply(&items), &[7]);
+    assert_eq!(
+        last.footer(items.len()).unwrap(),
+        "Showing 7-7 of 7 entries (page 3 of 3)"
+    );
+
+    // Past the end, nothing is shown and the footer says why
+    let past = Pagination {
+        max_results: Some(3),
+        page: 4,
+    };
+    assert!(past.apply(&items).is_empty());
+    assert!(past
+        .footer(items.len())
+        .unwrap()
+        .starts_with("No entries on page 4"));
+
+    // A single page needs no footer, even when empty
+    assert_eq!(first.footer(3), None);
+    assert_eq!(first.footer(0), None);
+}
@@ -0,0 +1,99 @@
+use anyhow::Result;
+use asimeow::{config, presets};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_containers_preset_paths() -> Result<()> {
+    let home = tempdir()?;
+    let docker_vms = home
+        .path()
+        .join("Library/Containers/com.docker.docker/Data/vms");
+    fs::create_dir_all(&docker_vms)?;
+    fs::write(docker_vms.join("Docker.raw"), vec![0u8; 2048])?;
+    fs::create_dir_all(home.path().join(".colima"))?;
+
+    let preset = presets::find("containers").expect("containers preset");
+    assert_eq!(
+        presets::resolve_paths(preset, home.path()),
+        vec![docker_vms, home.path().join(".colima")]
+    );
+    assert!(presets::find("unknown").is_none());
+    assert!(presets::apply_preset("unknown", true, None, false).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_dev_caches_preset_skips_nested_paths() -> Result<()> {
+    let home = tempdir()?;
+    fs::create_dir_all(home.path().join("Library/Caches/Homebrew"))?;
+    fs::create_dir_all(home.path().join(".cargo/registry"))?;
+    fs::create_dir_all(home.path().join(".cargo/bin"))?;
+
+    let preset = presets::find("dev-caches").expect("dev-caches preset");
+    assert_eq!(
+        presets::resolve_paths(preset, home.path()),
+        vec![
+            home.path().join("Library/Caches"),
+            home.path().join(".cargo/registry"),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_rule_packs_parse() -> Result<()> {
+    for name in ["ios", "android", "gamedev", "ml", "devops"] {
+        let pack = presets::find_pack(name).expect("rule pack");
+        let rules = pack.parse_rules()?;
+        assert!(!rules.is_empty());
+        assert!(rules
+            .iter()
+            .all(|rule| rule.tags.contains(&name.to_string())));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_add_pack_skips_existing_rules() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let config_path = temp_dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        "roots:\n  - path: ~/Development\nrules:\n  - name: unity\n    file_match: Assets/\n    exclusions:\n      - Library\n",
+    )?;
+
+    presets::add_pack("gamedev", Some(config_path.to_str().unwrap()))?;
+
+    let (cfg, _) = config::load_config(Some(config_path.to_str().unwrap()), false)?;
+    let names: Vec<&str> = cfg.rules.iter().map(|rule| rule.name.as_str()).collect();
+    assert_eq!(names, vec!["unity", "unreal", "godot"]);
+    // The existing rule is left untouched
+    assert_eq!(cfg.rules[0].exclusions, vec!["Library".into()]);
+
+    Ok(())
+}
+
+#[test]
+fn test_ml_models_preset_paths() -> Result<()> {
+    let home = tempdir()?;
+    fs::create_dir_all(home.path().join(".cache/huggingface/hub"))?;
+    fs::create_dir_all(home.path().join(".ollama/models"))?;
+    fs::create_dir_all(home.path().join("miniconda3/pkgs"))?;
+    fs::create_dir_all(home.path().join("miniconda3/envs"))?;
+
+    let preset = presets::find("ml-models").expect("ml-models preset");
+    assert_eq!(
+        presets::resolve_paths(preset, home.path()),
+        vec![
+            home.path().join(".cache/huggingface"),
+            home.path().join(".ollama/models"),
+            home.path().join("miniconda3/pkgs"),
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use asimeow::backend::MockBackend;
+use asimeow::explorer::State;
+use asimeow::output::{ErrorKind, ScanError};
+use asimeow::progress;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_render_dashboard() {
+    let state = State::with_backend(Arc::new(MockBackend::new()));
+    *state.processed_paths.write().unwrap() = 120;
+    *state.exclusion_found.write().unwrap() = 7;
+    *state.newly_excluded.write().unwrap() = 3;
+    state.errors.write().unwrap().push(ScanError::new(
+        ErrorKind::PermissionDenied,
+        Path::new("/private"),
+        "Permission denied",
+    ));
+    state
+        .folder_queue
+        .write()
+        .unwrap()
+        .extend([PathBuf::from("/a"), PathBuf::from("/b")]);
+    state.worker_paths.write().unwrap().insert(
+        1,
+        (
+            PathBuf::from("/Users/me/Development/monorepo/packages/web"),
+            Instant::now(),
+        ),
+    );
+
+    let lines = progress::render_dashboard(&state, 2, Duration::from_secs(42), 200);
+    assert_eq!(lines[0], "asimeow scan - 42s elapsed");
+    assert_eq!(
+        lines[1],
+        "Processed: 120  Queue: 2  Exclusions: 7  Newly excluded: 3  Errors: 1"
+    );
+    assert_eq!(lines[3], "  #1   idle");
+    assert!(lines[4].starts_with("  #2 "));
+    assert!(lines[4].ends_with("/Users/me/Development/monorepo/packages/web"));
+
+    // Then the queue of each root
+    assert_eq!(lines[5], "Roots:");
+    assert!(lines[6].starts_with("  queue 1, workers 0, 0.0s"));
+    assert!(lines[6].ends_with("/a"));
+    assert!(lines[7].ends_with("/b"));
+
+    // Long paths keep their end within the width
+    let lines = progress::render_dashboard(&state, 2, Duration::from_secs(42), 40);
+    assert!(lines.iter().all(|line| line.chars().count() <= 40));
+    assert!(lines[4].contains('…'));
+    assert!(lines[4].ends_with("packages/web"));
+}
@@ -0,0 +1,69 @@
+use asimeow::queue::FolderQueue;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_roots_take_turns() {
+    let mut queue = FolderQueue::default();
+    queue.add_root(PathBuf::from("/Users/me"));
+    queue.add_root(PathBuf::from("/Volumes/External"));
+    queue.extend([
+        PathBuf::from("/Users/me/Development"),
+        PathBuf::from("/Users/me/Documents"),
+        PathBuf::from("/Volumes/External/projects"),
+        PathBuf::from("/opt/src"),
+    ]);
+    assert_eq!(queue.len(), 6);
+    assert_eq!(queue.roots().len(), 3);
+
+    // A directory outside of the roots gets a queue of its own
+    assert_eq!(queue.roots()[2].root, Path::new("/opt/src"));
+
+    // While a worker is busy with a root, the others go to the next roots
+    let (root, path) = queue.pop().unwrap();
+    assert_eq!(root, Path::new("/Volumes/External"));
+    assert_eq!(path, Path::new("/Volumes/External"));
+    let (root, _) = queue.pop().unwrap();
+    assert_eq!(root, Path::new("/opt/src"));
+    let (root, path) = queue.pop().unwrap();
+    assert_eq!(root, Path::new("/Users/me"));
+    assert_eq!(path, Path::new("/Users/me"));
+
+    // The slow external root still holds a worker, so the home directory is served again
+    queue.done(Path::new("/opt/src"));
+    queue.done(Path::new("/Users/me"));
+    assert!(queue.roots()[2].is_done());
+    let (root, path) = queue.pop().unwrap();
+    assert_eq!(root, Path::new("/Users/me"));
+    assert_eq!(path, Path::new("/Users/me/Development"));
+    assert_eq!(queue.roots()[0].active, 1);
+    assert_eq!(queue.roots()[1].active, 1);
+
+    // A root is done, and timed, once its last directory is scanned
+    queue.done(Path::new("/Volumes/External"));
+    assert!(!queue.roots()[1].is_done());
+    let (root, _) = queue.pop().unwrap();
+    assert_eq!(root, Path::new("/Volumes/External"));
+    queue.done(Path::new("/Volumes/External"));
+    assert!(queue.roots()[1].is_done());
+    assert_eq!(queue.roots()[1].pending(), 0);
+    assert_eq!(queue.len(), 1);
+}
+
+#[test]
+fn test_nested_roots() {
+    let mut queue = FolderQueue::default();
+    queue.add_root(PathBuf::from("/Users/me"));
+    queue.add_root(PathBuf::from("/Users/me/Development"));
+    queue.push(PathBuf::from("/Users/me/Development/app"));
+    queue.push(PathBuf::from("/Users/me/Music"));
+
+    // Directories go to the closest root
+    assert_eq!(queue.roots()[0].pending(), 2);
+    assert_eq!(queue.roots()[1].pending(), 2);
+    assert!(queue.pop().is_some());
+    assert!(queue.pop().is_some());
+    assert!(queue.pop().is_some());
+    assert!(queue.pop().is_some());
+    assert!(queue.pop().is_none());
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,72 @@
+use asimeow::output::OutputFormat;
+use asimeow::remote::{self, RemoteRecord};
+use std::path::PathBuf;
+
+#[test]
+fn test_remote_command_quotes_arguments() {
+    let args = vec![
+        "--dry-run".to_string(),
+        "explain".to_string(),
+        "~/Development/it's here".to_string(),
+    ];
+    assert_eq!(
+        remote::remote_command("/opt/homebrew/bin/asimeow", OutputFormat::Ndjson, &args),
+        "'/opt/homebrew/bin/asimeow' -c \"$HOME\"/.cache/asimeow/remote-config.yaml \
+         --format ndjson '--dry-run' 'explain' '~/Development/it'\\''s here'"
+    );
+
+    // Without arguments, the remote host runs a scan
+    assert_eq!(
+        remote::remote_command("asimeow", OutputFormat::Json, &[]),
+        "'asimeow' -c \"$HOME\"/.cache/asimeow/remote-config.yaml --format json"
+    );
+
+    // An explicit format of the remote arguments is kept
+    assert_eq!(
+        remote::remote_command(
+            "asimeow",
+            OutputFormat::Ndjson,
+            &["--format=json".to_string()]
+        ),
+        "'asimeow' -c \"$HOME\"/.cache/asimeow/remote-config.yaml '--format=json'"
+    );
+}
+
+#[test]
+fn test_remote_results_are_parsed() {
+    assert_eq!(
+        remote::remote_format(OutputFormat::Text),
+        OutputFormat::Ndjson
+    );
+    assert_eq!(
+        remote::remote_format(OutputFormat::Json),
+        OutputFormat::Json
+    );
+
+    let exclusion = r#"{"type":"exclusion","path":"/Users/ci/app/node_modules"}"#;
+    assert_eq!(
+        serde_json::from_str::<RemoteRecord>(exclusion).unwrap(),
+        RemoteRecord::Exclusion {
+            path: PathBuf::from("/Users/ci/app/node_modules")
+        }
+    );
+    assert_eq!(
+        remote::render_line("build-1", exclusion),
+        "📋 build-1: /Users/ci/app/node_modules"
+    );
+
+    let error = r#"{"type":"error","kind":"permission-denied","path":"/Users/ci/Library","message":"Permission denied","errno":13}"#;
+    assert_eq!(
+        remote::render_line("build-1", error),
+        "⚠️  build-1: /Users/ci/Library - Permission denied"
+    );
+
+    let summary = r#"{"type":"summary","processed_paths":120,"exclusions_found":3,"newly_excluded":1,"error_count":1,"duration_secs":0.5}"#;
+    assert!(remote::render_line("build-1", summary).contains("build-1"));
+
+    // Lines of other commands are relayed as they are
+    assert_eq!(
+        remote::render_line("build-1", "node: package.json"),
+        "node: package.json"
+    );
+}
@@ -0,0 +1,87 @@
+use anyhow::Result;
+use asimeow::config;
+use asimeow::rules::{self, Checklist, ToggleSummary};
+use ratatui::crossterm::event::KeyCode;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_rule_checklist() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let config_file = temp_dir.path().join("config.yaml");
+    let config_file = config_file.to_str().unwrap();
+    fs::write(
+        config_file,
+        r#"
+roots:
+  - path: "~/"
+rules:
+  - name: node
+    file_match: package.json
+    exclusions: [node_modules]
+  - name: rust
+    file_match: Cargo.toml
+    exclusions: [target]
+    enabled: false
+"#,
+    )?;
+    let (cfg, _) = config::load_config(Some(config_file), false)?;
+
+    // Configured rules first, with their state, then the unchecked pack rules
+    let choices = rules::rule_choices(&cfg)?;
+    assert_eq!(choices[0].rule.name, "node");
+    assert!(choices[0].enabled);
+    assert_eq!(choices[1].rule.name, "rust");
+    assert!(!choices[1].enabled);
+    assert!(choices[2..]
+        .iter()
+        .all(|choice| choice.pack.is_some() && !choice.enabled));
+    let pack_rule = choices[2].rule.name.clone();
+
+    // Toggle node off, rust on and the first pack rule on, then save
+    let mut checklist = Checklist::new(choices);
+    for key in [
+        KeyCode::Char(' '),
+        KeyCode::Down,
+        KeyCode::Char(' '),
+        KeyCode::Down,
+        KeyCode::Char(' '),
+    ] {
+        assert!(checklist.handle_key(key));
+    }
+    assert!(!checklist.handle_key(KeyCode::Enter));
+    assert!(checklist.saved);
+
+    let summary = rules::apply_choices(config_file, &checklist.choices)?;
+    assert_eq!(
+        summary,
+        ToggleSummary {
+            enabled: vec!["rust".to_string()],
+            disabled: vec!["node".to_string()],
+            added: vec![pack_rule.clone()],
+        }
+    );
+    let (written, _) = config::load_config(Some(config_file), false)?;
+    let states: Vec<(&str, bool)> = written
+        .rules
+        .iter()
+        .map(|rule| (rule.name.as_str(), rule.is_enabled()))
+        .collect();
+    assert_eq!(
+        states,
+        vec![("node", false), ("rust", true), (pack_rule.as_str(), true)]
+    );
+
+    // Saving the same state again changes nothing
+    let choices = rules::rule_choices(&written)?;
+    assert_eq!(
+        rules::apply_choices(config_file, &choices)?,
+        ToggleSummary::default()
+    );
+
+    // Cancelling does not save
+    let mut checklist = Checklist::new(choices);
+    assert!(!checklist.handle_key(KeyCode::Char('q')));
+    assert!(!checklist.saved);
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use anyhow::Result;
+use asimeow::size::{self, SizeMode};
+use std::fs;
+use std::time::{Duration, SystemTime};
+use tempfile::tempdir;
+
+#[test]
+fn test_directory_size() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let nested = temp_dir.path().join("a").join("b");
+    fs::create_dir_all(&nested)?;
+    fs::write(temp_dir.path().join("a").join("one.bin"), vec![0u8; 1000])?;
+    fs::write(nested.join("two.bin"), vec![0u8; 24])?;
+
+    assert_eq!(size::directory_size(temp_dir.path()), 1024);
+    assert_eq!(size::directory_size(&nested.join("two.bin")), 24);
+
+    Ok(())
+}
+
+#[test]
+fn test_size_modified_since() -> Result<()> {
+    let temp_dir = tempdir()?;
+    fs::write(temp_dir.path().join("new.bin"), vec![0u8; 100])?;
+
+    let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+    assert_eq!(size::size_modified_since(temp_dir.path(), an_hour_ago), 100);
+    let in_an_hour = SystemTime::now() + Duration::from_secs(3600);
+    assert_eq!(size::size_modified_since(temp_dir.path(), in_an_hour), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(size::format_size(512), "512 B");
+    assert_eq!(size::format_size(1536), "1.5 KB");
+    assert_eq!(size::format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+}
+
+#[test]
+fn test_directory_sizes_keep_order() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let paths: Vec<_> = (1..=5)
+        .map(|i| {
+            let path = temp_dir.path().join(format!("f{}", i));
+            fs::write(&path, vec![0u8; i * 10]).map(|_| path)
+        })
+        .collect::<std::io::Result<_>>()?;
+
+    assert_eq!(
+        size::directory_sizes(&paths, 2, SizeMode::Apparent),
+        vec![10, 20, 30, 40, 50]
+    );
+    assert_eq!(
+        size::directory_sizes(&paths, 16, SizeMode::Apparent),
+        vec![10, 20, 30, 40, 50]
+    );
+    assert!(size::directory_sizes(&[], 4, SizeMode::Apparent).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_size_cache() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let cache_file = temp_dir.path().join("cache/sizes.yaml");
+    let dir = temp_dir.path().join("dir");
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("a"), vec![0u8; 100])?;
+
+    let mut cache = size::SizeCache::load(&cache_file);
+    let max_age = Duration::from_secs(60);
+    assert_eq!(
+        cache.sizes(std::slice::from_ref(&dir), 1, SizeMode::Apparent, max_age),
+        vec![100]
+    );
+    cache.save()?;
+
+    // A later run reuses the cached size until it expires
+    fs::write(dir.join("b"), vec![0u8; 50])?;
+    let mut cache = size::SizeCache::load(&cache_file);
+    assert_eq!(cache.get(&dir, SizeMode::Apparent, max_age), Some(100));
+    assert_eq!(
+        cache.sizes(std::slice::from_ref(&dir), 1, SizeMode::Apparent, max_age),
+        vec![100]
+    );
+    assert_eq!(
+        cache.sizes(
+            std::slice::from_ref(&dir),
+            1,
+            SizeMode::Apparent,
+            Duration::ZERO
+        ),
+        vec![150]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_size_modes() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let dir = temp_dir.path().join("vm");
+    fs::create_dir_all(&dir)?;
+
+    // A sparse file is long but allocates (almost) no blocks, like Docker.raw
+    let sparse = fs::File::create(dir.join("Docker.raw"))?;
+    sparse.set_len(64 * 1024 * 1024)?;
+    fs::write(dir.join("data.bin"), vec![1u8; 8192])?;
+
+    let apparent = size::directory_size_as(&dir, SizeMode::Apparent);
+    let on_disk = size::directory_size_as(&dir, SizeMode::OnDisk);
+    assert_eq!(apparent, 64 * 1024 * 1024 + 8192);
+    assert_eq!(apparent, size::directory_size(&dir));
+    assert!(on_disk >= 8192);
+    assert!(on_disk < 1024 * 1024);
+
+    // The cache keeps the sizes of each mode apart
+    let mut cache = size::SizeCache::load(temp_dir.path().join("sizes.yaml"));
+    let max_age = Duration::from_secs(60);
+    let paths = std::slice::from_ref(&dir);
+    assert_eq!(
+        cache.sizes(paths, 1, SizeMode::Apparent, max_age),
+        vec![apparent]
+    );
+    assert_eq!(
+        cache.sizes(paths, 1, SizeMode::OnDisk, max_age),
+        vec![on_disk]
+    );
+    Ok(())
+}
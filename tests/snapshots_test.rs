@@ -0,0 +1,40 @@
+use asimeow::snapshots;
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn test_parse_local_snapshots() {
+    let output = "Snapshots for disk /:\n\
+                  com.apple.TimeMachine.2026-10-16-093012.local\n\
+                  com.apple.TimeMachine.2026-10-15-211500.local\n\
+                  com.apple.os.update-7A2B\n";
+    let snapshots = snapshots::parse_local_snapshots(output);
+    let dates: Vec<&str> = snapshots.iter().map(|s| s.date.as_str()).collect();
+    assert_eq!(dates, vec!["2026-10-15-211500", "2026-10-16-093012"]);
+    assert_eq!(
+        snapshots[0].name,
+        "com.apple.TimeMachine.2026-10-15-211500.local"
+    );
+
+    // Older macOS versions print the names without the `.local` suffix nor a header
+    let snapshots = snapshots::parse_local_snapshots("com.apple.TimeMachine.2019-03-01-101010\n");
+    assert_eq!(snapshots[0].date, "2019-03-01-101010");
+}
+
+#[test]
+fn test_snapshot_created() {
+    let snapshot = snapshots::LocalSnapshot {
+        name: "com.apple.TimeMachine.2024-03-01-123045.local".to_string(),
+        date: "2024-03-01-123045".to_string(),
+    };
+    // 2024-03-01T12:30:45Z
+    assert_eq!(
+        snapshot.created(),
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1709296245))
+    );
+
+    let malformed = snapshots::LocalSnapshot {
+        name: "com.apple.TimeMachine.latest".to_string(),
+        date: "latest".to_string(),
+    };
+    assert_eq!(malformed.created(), None);
+}
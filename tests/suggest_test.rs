@@ -0,0 +1,70 @@
+use anyhow::Result;
+use asimeow::{config, suggest};
+use std::fs::{self, File};
+use tempfile::tempdir;
+
+#[test]
+fn test_suggest_rule_from_common_marker() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let mut caches = Vec::new();
+    for project in ["app", "lib"] {
+        let project_dir = temp_dir.path().join(project);
+        fs::create_dir_all(project_dir.join(".zig-cache"))?;
+        File::create(project_dir.join("build.zig"))?;
+        File::create(project_dir.join("README.md"))?;
+        caches.push(project_dir.join(".zig-cache"));
+    }
+    File::create(temp_dir.path().join("app").join("build.zig.zon"))?;
+
+    let suggestions = suggest::suggest_rules(&caches, &[]);
+    assert_eq!(suggestions.len(), 1);
+
+    let rule = &suggestions[0].rule;
+    assert_eq!(rule.name, "build-zig-cache");
+    assert_eq!(rule.file_match, "build.zig");
+    assert_eq!(rule.exclusions, vec![".zig-cache".into()]);
+    assert_eq!(suggestions[0].paths, caches);
+
+    // An existing rule with the same name is not suggested again
+    assert!(suggest::suggest_rules(&caches, &["build-zig-cache".to_string()]).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_suggest_skips_paths_without_marker() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let cache = temp_dir.path().join("project").join("cache");
+    fs::create_dir_all(&cache)?;
+    File::create(temp_dir.path().join("project").join("LICENSE"))?;
+
+    assert!(suggest::suggest_rules(&[cache], &[]).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_append_rules_to_config() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let config_path = temp_dir.path().join("config.yaml");
+    fs::write(
+        &config_path,
+        "roots:\n  - path: ~/Development\nrules:\n  - name: rust\n    file_match: Cargo.toml\n    exclusions:\n      - target\n",
+    )?;
+
+    config::append_rules(
+        config_path.to_str().unwrap(),
+        vec![config::Rule {
+            name: "build-zig-cache".to_string(),
+            file_match: "build.zig".to_string(),
+            exclusions: vec![".zig-cache".into()],
+            ..Default::default()
+        }],
+    )?;
+
+    let (cfg, _) = config::load_config(Some(config_path.to_str().unwrap()), false)?;
+    let names: Vec<&str> = cfg.rules.iter().map(|rule| rule.name.as_str()).collect();
+    assert_eq!(names, vec!["rust", "build-zig-cache"]);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use anyhow::Result;
+use asimeow::backend::{BackupExclusionBackend, MockBackend};
+use asimeow::config;
+use asimeow::tui::{fuzzy_score, App, View};
+use ratatui::crossterm::event::KeyCode;
+use std::fs::{self, File};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+#[test]
+fn test_tui_browse_toggle_and_plan() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().to_path_buf();
+    let app_dir = root.join("app");
+    fs::create_dir_all(app_dir.join("node_modules"))?;
+    File::create(app_dir.join("package.json"))?;
+    fs::create_dir_all(root.join("notes"))?;
+
+    let cfg = config::Config {
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let backend = Arc::new(MockBackend::new());
+    let mut app = App::new(cfg, backend.clone(), root.clone(), 1);
+
+    // Directories first, sorted by name
+    let names: Vec<_> = app.entries.iter().map(|e| e.path.clone()).collect();
+    assert_eq!(names, vec![app_dir.clone(), root.join("notes")]);
+
+    // Entering the project shows the rule targeting node_modules
+    assert!(app.handle_key(KeyCode::Enter));
+    assert_eq!(app.current, app_dir);
+    let node_modules = &app.entries[0];
+    assert_eq!(node_modules.path, app_dir.join("node_modules"));
+    assert_eq!(node_modules.rules, vec!["node"]);
+
+    // A rescan plans the exclusion without applying it, then `a` applies it
+    app.handle_key(KeyCode::Char('r'));
+    assert_eq!(app.view, View::Plan);
+    assert_eq!(app.plan, vec![app_dir.join("node_modules")]);
+    assert!(backend.excluded_paths().is_empty());
+    app.handle_key(KeyCode::Char('a'));
+    assert_eq!(backend.excluded_paths(), vec![app_dir.join("node_modules")]);
+    assert!(app.entries[0].excluded);
+
+    // Space toggles the exclusion back off
+    app.handle_key(KeyCode::Char(' '));
+    assert!(!backend.is_excluded(&app_dir.join("node_modules")));
+
+    // Leaving selects the directory left, q quits
+    app.handle_key(KeyCode::Left);
+    assert_eq!(app.current, root);
+    assert_eq!(app.selected, 0);
+    assert!(!app.handle_key(KeyCode::Char('q')));
+    Ok(())
+}
+
+#[test]
+fn test_fuzzy_score() {
+    assert_eq!(fuzzy_score("", "anything"), Some(0));
+    assert_eq!(fuzzy_score("xyz", "react-app"), None);
+    assert!(fuzzy_score("React", "clients/react-app").is_some());
+
+    // Consecutive characters at the start of a component beat scattered ones
+    let compact = fuzzy_score("react", "work/react-app").unwrap();
+    let scattered = fuzzy_score("react", "work/r/e/a/c/t").unwrap();
+    let spread = fuzzy_score("react", "work/rear-exact").unwrap();
+    assert!(compact > scattered);
+    assert!(compact > spread);
+}
+
+#[test]
+fn test_tui_fuzzy_search_jumps_to_project() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let root = temp_dir.path().to_path_buf();
+    for project in ["clients/react-dashboard", "clients/api", "tools/reader"] {
+        fs::create_dir_all(root.join(project).join("node_modules"))?;
+        File::create(root.join(project).join("package.json"))?;
+    }
+
+    let cfg = config::Config {
+        rules: vec![config::Rule {
+            name: "node".to_string(),
+            file_match: "package.json".to_string(),
+            exclusions: vec!["node_modules".into()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let backend = Arc::new(MockBackend::new());
+    let mut app = App::new(cfg, backend.clone(), root.clone(), 1);
+
+    // The index holds the projects and the paths the rules target
+    app.handle_key(KeyCode::Char('/'));
+    assert_eq!(app.view, View::Search);
+    assert!(app
+        .candidates
+        .contains(&root.join("clients/react-dashboard")));
+    assert!(app
+        .candidates
+        .contains(&root.join("tools/reader/node_modules")));
+    assert!(backend.excluded_paths().is_empty());
+
+    // Shortcuts are typed into the query while searching
+    for c in "rdash".chars() {
+        assert!(app.handle_key(KeyCode::Char(c)));
+    }
+    assert_eq!(app.query, "rdash");
+    assert_eq!(app.matches[0], root.join("clients/react-dashboard"));
+    assert!(!app.matches.contains(&root.join("clients/api")));
+
+    // Enter browses to the parent with the match selected, ready to toggle
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.view, View::Tree);
+    assert_eq!(app.current, root.join("clients"));
+    assert_eq!(
+        app.entries[app.selected].path,
+        root.join("clients/react-dashboard")
+    );
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use anyhow::Result;
+use asimeow::backend::MockBackend;
+use asimeow::{config, explorer, users};
+use std::fs::{self, File};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+#[test]
+fn test_user_homes_and_configs() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let users_dir = temp_dir.path().join("Users");
+    for name in ["alice", "bob", "Shared", "Guest", ".localized"] {
+        fs::create_dir_all(users_dir.join(name))?;
+    }
+    let alice_config = users_dir.join("alice/.config/asimeow/config.yaml");
+    fs::create_dir_all(alice_config.parent().unwrap())?;
+    File::create(&alice_config)?;
+
+    let homes = users::user_homes(&users_dir);
+    assert_eq!(homes, vec![users_dir.join("alice"), users_dir.join("bob")]);
+
+    // Users without their own config fall back to the system config, if any
+    let system_config = temp_dir.path().join("system.yaml");
+    assert_eq!(
+        users::user_config(&homes[0], &system_config),
+        Some(alice_config)
+    );
+    assert_eq!(users::user_config(&homes[1], &system_config), None);
+    File::create(&system_config)?;
+    assert_eq!(
+        users::user_config(&homes[1], &system_config),
+        Some(system_config)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_user_configs_cannot_run_commands_as_root() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let mut cfg = config::Config {
+        tmutil_path: Some("/Users/alice/bin/tmutil".to_string()),
+        privilege_helper: Some("sudo -n".to_string()),
+        rules: vec![
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec![
+                    "node_modules".into(),
+                    config::Exclusion::Command {
+                        command: "touch /tmp/owned".to_string(),
+                        in_project: false,
+                    },
+                ],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "pnpm".to_string(),
+                file_match: "pnpm-lock.yaml".to_string(),
+                exclusions: vec![config::Exclusion::Command {
+                    command: "pnpm store path".to_string(),
+                    in_project: false,
+                }],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "disk-images".to_string(),
+                file_match: "*.qcow2".to_string(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let dropped = users::restrict_user_config(&mut cfg, temp_dir.path());
+    assert_eq!(dropped.len(), 4);
+    assert_eq!(cfg.tmutil_path, None);
+    assert_eq!(cfg.privilege_helper, None);
+
+    // Rules left without exclusions are dropped, instead of excluding the matched entry
+    let rules: Vec<&str> = cfg.rules.iter().map(|rule| rule.name.as_str()).collect();
+    assert_eq!(rules, vec!["node", "disk-images"]);
+    assert_eq!(cfg.rules[0].exclusions, vec!["node_modules".into()]);
+
+    Ok(())
+}
+
+#[test]
+fn test_user_configs_cannot_reach_other_homes() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let alice = temp_dir.path().join("Users/alice");
+    let bob = temp_dir.path().join("Users/bob");
+    fs::create_dir_all(alice.join("Development"))?;
+    fs::create_dir_all(bob.join("Documents"))?;
+    std::os::unix::fs::symlink(&bob, alice.join("bob"))?;
+
+    let entry = |path: &std::path::Path| path.display().to_string();
+    let mut cfg = config::Config {
+        roots: vec![
+            config::Root {
+                path: entry(&alice.join("Development")),
+                ..Default::default()
+            },
+            config::Root {
+                path: entry(&bob),
+                ..Default::default()
+            },
+        ],
+        exclude_paths: vec![
+            entry(&alice.join("Development")),
+            entry(&bob.join("Documents")),
+            entry(&alice.join("bob/Documents")),
+            entry(&alice.join("../bob/Documents")),
+            "/Library".to_string(),
+        ],
+        symlink_bases: vec![entry(temp_dir.path())],
+        rules: vec![
+            config::Rule {
+                name: "rust".to_string(),
+                file_match: "Cargo.toml".to_string(),
+                exclusions: vec!["target".into()],
+                exclusion_type: config::ExclusionType::Fixed,
+                ..Default::default()
+            },
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into()],
+                backends: vec![config::Backend::TimeMachine, config::Backend::Dropbox],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "rename".to_string(),
+                file_match: "*.photoslibrary".to_string(),
+                backends: vec![config::Backend::ICloud],
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let dropped = users::restrict_user_config(&mut cfg, &alice);
+    assert_eq!(dropped.len(), 9);
+    assert_eq!(cfg.roots.len(), 1);
+    assert_eq!(cfg.roots[0].path, entry(&alice.join("Development")));
+    assert_eq!(cfg.exclude_paths, vec![entry(&alice.join("Development"))]);
+    assert!(cfg.symlink_bases.is_empty());
+
+    // Fixed-path exclusions need root and land in the system preferences, they are made
+    // sticky; rules only left without backends are dropped
+    let rules: Vec<&str> = cfg.rules.iter().map(|rule| rule.name.as_str()).collect();
+    assert_eq!(rules, vec!["rust", "node"]);
+    assert_eq!(cfg.rules[0].exclusion_type, config::ExclusionType::Sticky);
+    assert_eq!(cfg.rules[1].backends, vec![config::Backend::TimeMachine]);
+    assert_eq!(cfg.confine_to, Some(alice.canonicalize()?));
+
+    Ok(())
+}
+
+#[test]
+fn test_confined_scans_skip_symlinks_to_other_homes() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let alice = temp_dir.path().join("Users/alice");
+    let bob = temp_dir.path().join("Users/bob");
+    let project = alice.join("Development/app");
+    fs::create_dir_all(&project)?;
+    fs::create_dir_all(bob.join("Documents"))?;
+    fs::create_dir_all(project.join("target"))?;
+    File::create(project.join("Cargo.toml"))?;
+    std::os::unix::fs::symlink(bob.join("Documents"), project.join("node_modules"))?;
+    File::create(project.join("package.json"))?;
+
+    let cfg = config::Config {
+        roots: vec![config::Root {
+            path: alice.join("Development").display().to_string(),
+            ..Default::default()
+        }],
+        rules: vec![
+            config::Rule {
+                name: "rust".to_string(),
+                file_match: "Cargo.toml".to_string(),
+                exclusions: vec!["target".into()],
+                ..Default::default()
+            },
+            config::Rule {
+                name: "node".to_string(),
+                file_match: "package.json".to_string(),
+                exclusions: vec!["node_modules".into()],
+                ..Default::default()
+            },
+        ],
+        confine_to: Some(alice.canonicalize()?),
+        ..Default::default()
+    };
+
+    let backend = Arc::new(MockBackend::new());
+    explorer::run_explorer_with_backend(cfg, backend.clone(), 2, false)?;
+    assert_eq!(backend.excluded_paths(), vec![project.join("target")]);
+
+    Ok(())
+}